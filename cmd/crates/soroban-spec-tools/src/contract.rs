@@ -36,6 +36,17 @@ pub enum Error {
 
     #[error(transparent)]
     Parser(#[from] wasmparser::BinaryReaderError),
+
+    #[error("wasm is a component, not a core module, build the contract as a `cdylib` for `wasm32-unknown-unknown`")]
+    NotAModule,
+    #[error("wasm uses 64-bit memory, build the contract for `wasm32-unknown-unknown`")]
+    NotWasm32,
+    #[error("wasm has no `contractenvmetav0` custom section, was it built with soroban-sdk?")]
+    MissingEnvMeta,
+    #[error("wasm has no `contractspecv0` custom section, was it built with soroban-sdk?")]
+    MissingSpec,
+    #[error("wasm doesn't export any of the functions in its contract spec, was it built as a `cdylib`?")]
+    NoContractFunctionExported,
 }
 
 impl Spec {
@@ -100,6 +111,65 @@ impl Spec {
         })
     }
 
+    /// Parse the wasm like [`Spec::new`], and check that it is a wasm32 module
+    /// with the env meta and spec custom sections, that exports at least one of
+    /// the functions in its spec. The `contractmetav0` section is optional.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first check that failed.
+    pub fn validate(bytes: &[u8]) -> Result<Self, Error> {
+        let spec = Spec::new(bytes)?;
+        let mut exports = Vec::new();
+        for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+            match payload? {
+                wasmparser::Payload::Version {
+                    encoding: wasmparser::Encoding::Component,
+                    ..
+                } => return Err(Error::NotAModule),
+                wasmparser::Payload::MemorySection(memories) => {
+                    for memory in memories {
+                        if memory?.memory64 {
+                            return Err(Error::NotWasm32);
+                        }
+                    }
+                }
+                wasmparser::Payload::ImportSection(imports) => {
+                    for import in imports {
+                        if let wasmparser::TypeRef::Memory(memory) = import?.ty {
+                            if memory.memory64 {
+                                return Err(Error::NotWasm32);
+                            }
+                        }
+                    }
+                }
+                wasmparser::Payload::ExportSection(section) => {
+                    for export in section {
+                        let export = export?;
+                        if export.kind == wasmparser::ExternalKind::Func {
+                            exports.push(export.name.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if spec.env_meta_base64.is_none() {
+            return Err(Error::MissingEnvMeta);
+        }
+        if spec.spec_base64.is_none() {
+            return Err(Error::MissingSpec);
+        }
+        let exported = spec.spec.iter().any(|entry| match entry {
+            ScSpecEntry::FunctionV0(func) => exports.contains(&func.name.to_utf8_string_lossy()),
+            _ => false,
+        });
+        if !exported {
+            return Err(Error::NoContractFunctionExported);
+        }
+        Ok(spec)
+    }
+
     pub fn spec_as_json_array(&self) -> Result<String, Error> {
         let spec = self
             .spec
@@ -295,3 +365,22 @@ fn format_name(lib: &StringM<80>, name: &StringM<60>) -> String {
         name.to_utf8_string_lossy()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &[u8] = b"\0asm\x01\0\0\0";
+
+    #[test]
+    fn validate_rejects_module_without_sections() {
+        assert!(matches!(Spec::validate(HEADER), Err(Error::MissingEnvMeta)));
+    }
+
+    #[test]
+    fn validate_rejects_memory64() {
+        // Memory section with a single 64-bit memory of one page.
+        let wasm = [HEADER, &[0x05, 0x03, 0x01, 0x04, 0x01]].concat();
+        assert!(matches!(Spec::validate(&wasm), Err(Error::NotWasm32)));
+    }
+}
@@ -787,9 +787,17 @@ pub fn from_json_primitives(v: &Value, t: &ScType) -> Result<ScVal, Error> {
             })
         }
 
-        // Number parsing
+        // Numbers nested in JSON, e.g. in a vec or struct, aren't kept as strings like top
+        // level arguments are.
+        (ScType::U128 | ScType::I128 | ScType::U256 | ScType::I256, Value::Number(n)) => {
+            from_json_primitives(&Value::String(n.to_string()), t)?
+        }
+
+        // Number parsing, from decimal or `0x` prefixed hex strings
         (ScType::U256, Value::String(s)) => {
-            let (hi, lo) = ethnum::U256::from_str_prefixed(s)?.into_words();
+            let (hi, lo) = ethnum::U256::from_str_prefixed(s)
+                .map_err(|_| Error::InvalidValue(Some(t.clone())))?
+                .into_words();
             let hi_bytes = hi.to_be_bytes();
             let (hi_hi, hi_lo) = hi_bytes.split_at(8);
             let lo_bytes = lo.to_be_bytes();
@@ -802,7 +810,9 @@ pub fn from_json_primitives(v: &Value, t: &ScType) -> Result<ScVal, Error> {
             })
         }
         (ScType::I256, Value::String(s)) => {
-            let (hi, lo) = ethnum::I256::from_str_prefixed(s)?.into_words();
+            let (hi, lo) = ethnum::I256::from_str_prefixed(s)
+                .map_err(|_| Error::InvalidValue(Some(t.clone())))?
+                .into_words();
             let hi_bytes = hi.to_be_bytes();
             let (hi_hi, hi_lo) = hi_bytes.split_at(8);
             let lo_bytes = lo.to_be_bytes();
@@ -1198,6 +1208,31 @@ impl Spec {
             .collect::<Option<Vec<_>>>()
             .map(|v| v.join(" | "))
     }
+
+    /// Render `function` on a single line, e.g. `fn hello(to: Symbol) -> Array<Symbol>`, naming
+    /// types the same way as the help of `contract invoke`.
+    pub fn function_signature(&self, function: &ScSpecFunctionV0) -> String {
+        let type_name = |type_: &ScType| {
+            self.arg_value_name(type_, 0)
+                .unwrap_or_else(|| "Val".to_string())
+        };
+        let name = function.name.to_utf8_string_lossy();
+        let inputs = function
+            .inputs
+            .iter()
+            .map(|input| {
+                format!(
+                    "{}: {}",
+                    input.name.to_utf8_string_lossy(),
+                    type_name(&input.type_)
+                )
+            })
+            .join(", ");
+        match function.outputs.first() {
+            Some(output) => format!("fn {name}({inputs}) -> {}", type_name(output)),
+            None => format!("fn {name}({inputs})"),
+        }
+    }
 }
 
 fn arg_value_enum(enum_: &ScSpecUdtEnumV0) -> String {
@@ -1357,11 +1392,170 @@ impl Spec {
     }
 }
 
+// Placeholder implementation
+impl Spec {
+    /// A JSON object mapping each argument of `function` to a placeholder of its type, see
+    /// [`Spec::placeholder`].
+    pub fn example_args(&self, function: &str) -> Result<Value, Error> {
+        let function = self.find_function(function)?;
+        Ok(Value::Object(
+            function
+                .inputs
+                .iter()
+                .map(|input| {
+                    (
+                        input.name.to_utf8_string_lossy(),
+                        self.placeholder(&input.type_).unwrap_or(Value::Null),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    /// A value of `type_` to be filled in, in the JSON accepted by [`Spec::from_json`]: zero for
+    /// numbers, empty strings and bytes, an empty array for vectors, the first case of enums and
+    /// unions, and a single entry for maps so that the shape of the keys and values is shown.
+    #[must_use]
+    pub fn placeholder(&self, type_: &ScType) -> Option<Value> {
+        match type_ {
+            ScType::U32
+            | ScType::I32
+            | ScType::U64
+            | ScType::I64
+            | ScType::Timepoint
+            | ScType::Duration => Some(json!(0)),
+            ScType::U128 | ScType::I128 | ScType::U256 | ScType::I256 => Some(json!("0")),
+            ScType::Bool => Some(json!(false)),
+            ScType::Symbol | ScType::String | ScType::Bytes => Some(json!("")),
+            ScType::Address => Some(json!(
+                "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"
+            )),
+            ScType::BytesN(n) => Some(json!("00".repeat(n.n as usize))),
+            ScType::Void => Some(Value::Null),
+            ScType::Option(val) => self.placeholder(&val.value_type),
+            ScType::Vec(_) => Some(json!([])),
+            ScType::Result(val) => self.placeholder(&val.ok_type),
+            ScType::Tuple(val) => val
+                .value_types
+                .iter()
+                .map(|t| self.placeholder(t))
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array),
+            ScType::Map(map) => {
+                let key = match self.placeholder(&map.key_type)? {
+                    Value::String(key) => key,
+                    key => key.to_string(),
+                };
+                let val = self.placeholder(&map.value_type)?;
+                Some(json!({ key: val }))
+            }
+            ScType::Udt(ScSpecTypeUdt { name }) => {
+                self.placeholder_udt(&name.to_utf8_string_lossy())
+            }
+            ScType::Error | ScType::Val => None,
+        }
+    }
+
+    fn placeholder_udt(&self, name: &str) -> Option<Value> {
+        match self.find(name).ok()? {
+            ScSpecEntry::UdtStructV0(strukt)
+                if strukt
+                    .fields
+                    .first()
+                    .is_some_and(|f| f.name.to_utf8_string_lossy() == "0") =>
+            {
+                strukt
+                    .fields
+                    .iter()
+                    .map(|f| self.placeholder(&f.type_))
+                    .collect::<Option<Vec<_>>>()
+                    .map(Value::Array)
+            }
+            ScSpecEntry::UdtStructV0(strukt) => strukt
+                .fields
+                .iter()
+                .map(|f| Some((f.name.to_utf8_string_lossy(), self.placeholder(&f.type_)?)))
+                .collect::<Option<serde_json::Map<_, _>>>()
+                .map(Value::Object),
+            ScSpecEntry::UdtUnionV0(union) => match union.cases.first()? {
+                ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 { name, .. }) => {
+                    Some(json!(name.to_utf8_string_lossy()))
+                }
+                ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                    name, type_, ..
+                }) => {
+                    let mut values = type_
+                        .iter()
+                        .map(|t| self.placeholder(t))
+                        .collect::<Option<Vec<_>>>()?;
+                    let value = if values.len() == 1 {
+                        values.remove(0)
+                    } else {
+                        Value::Array(values)
+                    };
+                    Some(json!({ name.to_utf8_string_lossy(): value }))
+                }
+            },
+            ScSpecEntry::UdtEnumV0(enum_) => enum_.cases.first().map(|c| json!(c.value)),
+            ScSpecEntry::FunctionV0(_) | ScSpecEntry::UdtErrorEnumV0(_) => None,
+        }
+    }
+}
+
+// Decoding values of an unknown type
+impl Spec {
+    /// Convert `val` to JSON as the user defined type it has the shape of, see
+    /// [`Spec::udt_for_value`], or untyped with [`to_json`] if there is none.
+    pub fn xdr_to_json_inferred(&self, val: &ScVal) -> Result<Value, Error> {
+        match self.udt_for_value(val) {
+            Some(name) => self.xdr_to_json(val, &ScType::Udt(ScSpecTypeUdt { name: name.clone() })),
+            None => to_json(val),
+        }
+    }
+
+    /// Name of the user defined type that `val` has the shape of: a struct whose field names are
+    /// the keys of a map in order, or a union with a case named after the symbol starting a vec
+    /// and taking the rest of its elements.
+    #[must_use]
+    pub fn udt_for_value(&self, val: &ScVal) -> Option<&StringM<60>> {
+        self.0.as_ref()?.iter().find_map(|entry| match (entry, val) {
+            (ScSpecEntry::UdtStructV0(strukt), ScVal::Map(Some(map)))
+                if strukt.fields.len() == map.len()
+                    && strukt.fields.iter().zip(map.iter()).all(|(field, entry)| {
+                        matches!(&entry.key, ScVal::Symbol(key) if key.as_vec() == field.name.as_vec())
+                    }) =>
+            {
+                Some(&strukt.name)
+            }
+            (ScSpecEntry::UdtUnionV0(union), ScVal::Vec(Some(vec_))) => {
+                let (ScVal::Symbol(case_name), rest) = vec_.split_first()? else {
+                    return None;
+                };
+                union
+                    .cases
+                    .iter()
+                    .any(|case| match case {
+                        ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 { name, .. }) => {
+                            name.as_vec() == case_name.as_vec() && rest.is_empty()
+                        }
+                        ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                            name,
+                            type_,
+                            ..
+                        }) => name.as_vec() == case_name.as_vec() && type_.len() == rest.len(),
+                    })
+                    .then_some(&union.name)
+            }
+            _ => None,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use stellar_xdr::curr::ScSpecTypeBytesN;
+    use stellar_xdr::curr::{ScSpecFunctionInputV0, ScSpecTypeBytesN};
 
     #[test]
     fn from_json_primitives_bytesn() {
@@ -1391,6 +1585,311 @@ mod tests {
         );
     }
 
+    #[test]
+    fn function_signature() {
+        let function = ScSpecFunctionV0 {
+            doc: StringM::default(),
+            name: ScSymbol(StringM::from_str("swap").unwrap()),
+            inputs: vec![
+                ScSpecFunctionInputV0 {
+                    doc: StringM::default(),
+                    name: StringM::from_str("pair").unwrap(),
+                    type_: ScType::Tuple(Box::new(ScSpecTypeTuple {
+                        value_types: vec![ScType::Symbol, ScType::U32].try_into().unwrap(),
+                    })),
+                },
+                ScSpecFunctionInputV0 {
+                    doc: StringM::default(),
+                    name: StringM::from_str("flags").unwrap(),
+                    type_: ScType::Map(Box::new(ScSpecTypeMap {
+                        key_type: Box::new(ScType::U32),
+                        value_type: Box::new(ScType::Bool),
+                    })),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+            outputs: vec![ScType::Vec(Box::new(ScSpecTypeVec {
+                element_type: Box::new(ScType::U32),
+            }))]
+            .try_into()
+            .unwrap(),
+        };
+        let spec = Spec::new(vec![ScSpecEntry::FunctionV0(function.clone())]);
+        assert_eq!(
+            spec.function_signature(&function),
+            "fn swap(pair: Tuple<Symbol, u32>, flags: Map<u32, bool>) -> Array<u32>"
+        );
+
+        let function = ScSpecFunctionV0 {
+            outputs: VecM::default(),
+            inputs: VecM::default(),
+            ..function
+        };
+        assert_eq!(spec.function_signature(&function), "fn swap()");
+    }
+
+    #[test]
+    fn xdr_to_json_inferred() {
+        use stellar_xdr::curr::ScSpecUdtStructFieldV0;
+
+        let strukt = ScSpecUdtStructV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: StringM::from_str("Transfer").unwrap(),
+            fields: vec![
+                ScSpecUdtStructFieldV0 {
+                    doc: StringM::default(),
+                    name: StringM::from_str("amount").unwrap(),
+                    type_: ScType::I128,
+                },
+                ScSpecUdtStructFieldV0 {
+                    doc: StringM::default(),
+                    name: StringM::from_str("memo").unwrap(),
+                    type_: ScType::U64,
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        };
+        let union = ScSpecUdtUnionV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: StringM::from_str("Action").unwrap(),
+            cases: vec![
+                ScSpecUdtUnionCaseV0::VoidV0(ScSpecUdtUnionCaseVoidV0 {
+                    doc: StringM::default(),
+                    name: StringM::from_str("Stop").unwrap(),
+                }),
+                ScSpecUdtUnionCaseV0::TupleV0(ScSpecUdtUnionCaseTupleV0 {
+                    doc: StringM::default(),
+                    name: StringM::from_str("Move").unwrap(),
+                    type_: vec![ScType::U32].try_into().unwrap(),
+                }),
+            ]
+            .try_into()
+            .unwrap(),
+        };
+        let spec = Spec::new(vec![
+            ScSpecEntry::UdtStructV0(strukt),
+            ScSpecEntry::UdtUnionV0(union),
+        ]);
+        let symbol = |s: &str| ScVal::Symbol(ScSymbol(StringM::from_str(s).unwrap()));
+        let vec = |v: Vec<ScVal>| ScVal::Vec(Some(ScVec(v.try_into().unwrap())));
+
+        let transfer = ScVal::Map(Some(ScMap(
+            vec![
+                ScMapEntry {
+                    key: symbol("amount"),
+                    val: ScVal::I128(Int128Parts { hi: 0, lo: 5 }),
+                },
+                ScMapEntry {
+                    key: symbol("memo"),
+                    val: ScVal::U64(7),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        )));
+        assert_eq!(
+            spec.xdr_to_json_inferred(&transfer).unwrap(),
+            json!({ "amount": "5", "memo": 7 })
+        );
+        assert_eq!(
+            spec.xdr_to_json_inferred(&vec(vec![symbol("Move"), ScVal::U32(3)]))
+                .unwrap(),
+            json!({ "Move": 3 })
+        );
+        assert_eq!(
+            spec.xdr_to_json_inferred(&vec(vec![symbol("Stop")]))
+                .unwrap(),
+            json!("Stop")
+        );
+        // Values not matching a type of the spec are converted without one
+        assert_eq!(
+            spec.xdr_to_json_inferred(&vec(vec![symbol("Jump"), ScVal::U32(3)]))
+                .unwrap(),
+            json!(["Jump", 3])
+        );
+        assert_eq!(
+            spec.xdr_to_json_inferred(&symbol("transfer")).unwrap(),
+            json!("transfer")
+        );
+    }
+
+    #[test]
+    fn example_args() {
+        use stellar_xdr::curr::{ScSpecUdtEnumCaseV0, ScSpecUdtStructFieldV0};
+
+        let map = |key_type, value_type| {
+            ScType::Map(Box::new(ScSpecTypeMap {
+                key_type: Box::new(key_type),
+                value_type: Box::new(value_type),
+            }))
+        };
+        let udt = |name| {
+            ScType::Udt(ScSpecTypeUdt {
+                name: StringM::from_str(name).unwrap(),
+            })
+        };
+        let strukt = ScSpecUdtStructV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: StringM::from_str("Config").unwrap(),
+            fields: vec![
+                ScSpecUdtStructFieldV0 {
+                    doc: StringM::default(),
+                    name: StringM::from_str("admin").unwrap(),
+                    type_: ScType::Address,
+                },
+                ScSpecUdtStructFieldV0 {
+                    doc: StringM::default(),
+                    name: StringM::from_str("limits").unwrap(),
+                    type_: map(ScType::Symbol, map(ScType::U32, ScType::I128)),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        };
+        let enum_ = ScSpecUdtEnumV0 {
+            doc: StringM::default(),
+            lib: StringM::default(),
+            name: StringM::from_str("Mode").unwrap(),
+            cases: vec![ScSpecUdtEnumCaseV0 {
+                doc: StringM::default(),
+                name: StringM::from_str("Open").unwrap(),
+                value: 3,
+            }]
+            .try_into()
+            .unwrap(),
+        };
+        let input = |name, type_| ScSpecFunctionInputV0 {
+            doc: StringM::default(),
+            name: StringM::from_str(name).unwrap(),
+            type_,
+        };
+        let function = ScSpecFunctionV0 {
+            doc: StringM::default(),
+            name: ScSymbol(StringM::from_str("init").unwrap()),
+            inputs: vec![
+                input("config", udt("Config")),
+                input("mode", udt("Mode")),
+                input(
+                    "tags",
+                    ScType::Vec(Box::new(ScSpecTypeVec {
+                        element_type: Box::new(ScType::String),
+                    })),
+                ),
+            ]
+            .try_into()
+            .unwrap(),
+            outputs: VecM::default(),
+        };
+        let spec = Spec::new(vec![
+            ScSpecEntry::FunctionV0(function),
+            ScSpecEntry::UdtStructV0(strukt),
+            ScSpecEntry::UdtEnumV0(enum_),
+        ]);
+        assert_eq!(
+            spec.example_args("init").unwrap(),
+            json!({
+                "config": {
+                    "admin": "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+                    "limits": { "": { "0": "0" } },
+                },
+                "mode": 3,
+                "tags": [],
+            })
+        );
+    }
+
+    fn round_trip(s: &str, t: &ScType) -> Value {
+        to_json(&from_json_primitives(&Value::String(s.to_string()), t).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn from_json_primitives_u256() {
+        let max = ethnum::U256::MAX.to_string();
+        assert_eq!(round_trip(&max, &ScType::U256), json!(max));
+        assert_eq!(
+            from_json_primitives(&Value::String(max), &ScType::U256).unwrap(),
+            ScVal::U256(UInt256Parts {
+                hi_hi: u64::MAX,
+                hi_lo: u64::MAX,
+                lo_hi: u64::MAX,
+                lo_lo: u64::MAX,
+            })
+        );
+        assert_eq!(round_trip("0", &ScType::U256), json!("0"));
+        assert_eq!(
+            from_json_primitives(&Value::String("0x0100".to_string()), &ScType::U256).unwrap(),
+            ScVal::U256(UInt256Parts {
+                hi_hi: 0,
+                hi_lo: 0,
+                lo_hi: 0,
+                lo_lo: 256,
+            })
+        );
+        assert_eq!(
+            from_json_primitives(&Value::Number(7.into()), &ScType::U256).unwrap(),
+            from_json_primitives(&Value::String("7".to_string()), &ScType::U256).unwrap()
+        );
+
+        // One past the maximum, and negative values, are rejected
+        let overflow = format!("{}0", ethnum::U256::MAX);
+        for s in [overflow.as_str(), "-1", "1.5", "abc"] {
+            assert!(
+                matches!(
+                    from_json_primitives(&Value::String(s.to_string()), &ScType::U256),
+                    Err(Error::InvalidValue(Some(ScType::U256)))
+                ),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn from_json_primitives_i256() {
+        let min = ethnum::I256::MIN.to_string();
+        let max = ethnum::I256::MAX.to_string();
+        assert_eq!(round_trip(&min, &ScType::I256), json!(min));
+        assert_eq!(round_trip(&max, &ScType::I256), json!(max));
+        assert_eq!(
+            from_json_primitives(&Value::String(min), &ScType::I256).unwrap(),
+            ScVal::I256(Int256Parts {
+                hi_hi: i64::MIN,
+                hi_lo: 0,
+                lo_hi: 0,
+                lo_lo: 0,
+            })
+        );
+        assert_eq!(
+            from_json_primitives(&Value::String("-1".to_string()), &ScType::I256).unwrap(),
+            ScVal::I256(Int256Parts {
+                hi_hi: -1,
+                hi_lo: u64::MAX,
+                lo_hi: u64::MAX,
+                lo_lo: u64::MAX,
+            })
+        );
+        let negative = "-170000000000000000000000000000000000000";
+        assert_eq!(round_trip(negative, &ScType::I256), json!(negative));
+        assert_eq!(round_trip("-0x10", &ScType::I256), json!("-16"));
+        assert_eq!(
+            from_json_primitives(&Value::Number((-7).into()), &ScType::I256).unwrap(),
+            from_json_primitives(&Value::String("-7".to_string()), &ScType::I256).unwrap()
+        );
+
+        let overflow = format!("{}0", ethnum::I256::MAX);
+        let underflow = format!("{}0", ethnum::I256::MIN);
+        for s in [overflow, underflow] {
+            assert!(matches!(
+                from_json_primitives(&Value::String(s), &ScType::I256),
+                Err(Error::InvalidValue(Some(ScType::I256)))
+            ));
+        }
+    }
+
     #[test]
     fn from_json_primitives_bytes() {
         // Check it parses hex-encoded bytes
@@ -70,6 +70,7 @@ impl Default for TestEnv {
                 rpc_url: "http://localhost:8889/soroban/rpc".to_string(),
                 network_passphrase: LOCAL_NETWORK_PASSPHRASE.to_string(),
                 rpc_headers: [].to_vec(),
+                default_identity: None,
             },
         }
     }
@@ -170,7 +171,7 @@ impl TestEnv {
 
     pub fn generate_account(&self, account: &str, seed: Option<String>) -> Command {
         let mut cmd = self.new_assert_cmd("keys");
-        cmd.arg("generate").arg(account);
+        cmd.arg("generate").arg(account).arg("--local");
         if let Some(seed) = seed {
             cmd.arg(format!("--seed={seed}"));
         }
@@ -261,10 +262,12 @@ impl TestEnv {
                 rpc_headers: [].to_vec(),
                 network_passphrase: Some(LOCAL_NETWORK_PASSPHRASE.to_string()),
                 network: None,
+                check_rpc: false,
             },
-            source_account: account.parse().unwrap(),
+            source_account: Some(account.parse().unwrap()),
             locator: config::locator::Args {
                 global: false,
+                local: false,
                 config_dir,
             },
             hd_path: None,
@@ -287,6 +290,7 @@ impl TestEnv {
                 very_verbose: false,
                 list: false,
                 no_cache: false,
+                ..Default::default()
             }),
             Some(&config),
         )
@@ -300,7 +304,7 @@ impl TestEnv {
 
     /// Returns the public key corresponding to the test keys's `hd_path`
     pub fn test_address(&self, hd_path: usize) -> String {
-        self.cmd::<keys::public_key::Cmd>(&format!("--hd-path={hd_path}"))
+        self.cmd::<keys::public_key::Cmd>(&format!("test --hd-path={hd_path}"))
             .public_key()
             .unwrap()
             .to_string()
@@ -308,7 +312,7 @@ impl TestEnv {
 
     /// Returns the private key corresponding to the test keys's `hd_path`
     pub fn test_show(&self, hd_path: usize) -> String {
-        self.cmd::<keys::secret::Cmd>(&format!("--hd-path={hd_path}"))
+        self.cmd::<keys::secret::Cmd>(&format!("test --hd-path={hd_path}"))
             .private_key()
             .unwrap()
             .to_string()
@@ -88,6 +88,34 @@ fn parse_i256() {
     );
 }
 
+#[test]
+fn parse_u256_bounds() {
+    let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+    let parsed = from_string_primitive(max, &ScSpecTypeDef::U256).unwrap();
+    assert_eq!(soroban_spec_tools::to_json(&parsed).unwrap(), json!(max));
+    let parsed = from_string_primitive("0", &ScSpecTypeDef::U256).unwrap();
+    assert_eq!(soroban_spec_tools::to_json(&parsed).unwrap(), json!("0"));
+    assert!(from_string_primitive(&format!("{max}0"), &ScSpecTypeDef::U256).is_err());
+    assert!(from_string_primitive("-1", &ScSpecTypeDef::U256).is_err());
+}
+
+#[test]
+fn parse_i256_bounds() {
+    for num in [
+        "57896044618658097711785492504343953926634992332820282019728792003956564819967",
+        "-57896044618658097711785492504343953926634992332820282019728792003956564819968",
+        "-1",
+    ] {
+        let parsed = from_string_primitive(num, &ScSpecTypeDef::I256).unwrap();
+        assert_eq!(soroban_spec_tools::to_json(&parsed).unwrap(), json!(num));
+    }
+    assert!(from_string_primitive(
+        "-57896044618658097711785492504343953926634992332820282019728792003956564819969",
+        &ScSpecTypeDef::I256
+    )
+    .is_err());
+}
+
 #[test]
 fn parse_bytes() {
     let b = from_string_primitive(r"beefface", &ScSpecTypeDef::Bytes).unwrap();
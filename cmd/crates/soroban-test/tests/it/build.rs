@@ -75,7 +75,7 @@ fn build_no_package_found() {
         .failure()
         .stderr(predicate::eq(
             "\
-❌ error: package nopkgwiththisname not found
+error: package nopkgwiththisname not found
 ",
         ));
 }
@@ -120,6 +120,65 @@ cargo rustc --manifest-path=contracts/add/Cargo.toml --crate-type=cdylib --targe
         ));
 }
 
+#[test]
+fn build_manifests_file() {
+    let sandbox = TestEnv::default();
+    let cargo_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture_path = cargo_dir.join("tests/fixtures/workspace/");
+    sandbox
+        .new_assert_cmd("contract")
+        .current_dir(fixture_path)
+        .arg("build")
+        .arg("--print-commands-only")
+        .arg("--manifests-file=manifests.txt")
+        .assert()
+        .success()
+        .stdout(predicate::eq("\
+cargo rustc --manifest-path=contracts/call/Cargo.toml --crate-type=cdylib --target=wasm32-unknown-unknown --release
+cargo rustc --manifest-path=contracts/add/add2/Cargo.toml --crate-type=cdylib --target=wasm32-unknown-unknown --release
+"));
+}
+
+#[test]
+fn build_manifests_file_relative_to_file() {
+    let sandbox = TestEnv::default();
+    let cargo_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture_path = cargo_dir.join("tests/fixtures/workspace/contracts/add");
+    sandbox
+        .new_assert_cmd("contract")
+        .current_dir(fixture_path)
+        .arg("build")
+        .arg("--print-commands-only")
+        .arg("--manifests-file=../../manifests.txt")
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "\
+cargo rustc --manifest-path=../call/Cargo.toml --crate-type=cdylib --target=wasm32-unknown-unknown --release
+cargo rustc --manifest-path=add2/Cargo.toml --crate-type=cdylib --target=wasm32-unknown-unknown --release
+",
+        ));
+}
+
+#[test]
+fn build_manifests_file_missing_manifest() {
+    let sandbox = TestEnv::default();
+    let cargo_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture_path = cargo_dir.join("tests/fixtures/workspace/");
+    sandbox
+        .new_assert_cmd("contract")
+        .current_dir(fixture_path)
+        .arg("build")
+        .arg("--print-commands-only")
+        .arg("--manifests-file=manifests-missing.txt")
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "contracts/missing/Cargo.toml does not exist",
+        ));
+}
+
 #[test]
 fn build_with_metadata() {
     let sandbox = TestEnv::default();
@@ -186,6 +245,136 @@ fn build_with_metadata() {
     assert_eq!(entries, expected_entries);
 }
 
+#[test]
+fn build_with_meta_file() {
+    let sandbox = TestEnv::default();
+    let cargo_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture_path = cargo_dir.join("tests/fixtures/workspace/contracts/add");
+    let outdir = sandbox.dir().join("out");
+    let meta_file = sandbox.dir().join("meta.txt");
+    std::fs::write(
+        &meta_file,
+        "# meta added on build\nhome_domain=example.com\nversion=1\n",
+    )
+    .unwrap();
+
+    sandbox
+        .new_assert_cmd("contract")
+        .current_dir(&fixture_path)
+        .arg("build")
+        .arg("--meta-file")
+        .arg(&meta_file)
+        .arg("--meta")
+        .arg("version=2")
+        .arg("--out-dir")
+        .arg(&outdir)
+        .assert()
+        .success();
+
+    let wasm = std::fs::read(outdir.join("add.wasm")).unwrap();
+    let meta = Spec::new(&wasm).unwrap().meta_base64.unwrap();
+    let entries = ScMetaEntry::read_xdr_base64_iter(&mut Limited::new(
+        Cursor::new(meta.as_bytes()),
+        Limits::none(),
+    ))
+    .map(|entry| match entry.unwrap() {
+        ScMetaEntry::ScMetaV0(ScMetaV0 { key, val }) => (key.to_string(), val.to_string()),
+    })
+    .filter(|(key, _)| !matches!(key.as_str(), "rsver" | "rssdkver" | "Description"))
+    .collect::<Vec<_>>();
+
+    assert_eq!(
+        entries,
+        [
+            ("home_domain".to_string(), "example.com".to_string()),
+            ("version".to_string(), "2".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn build_with_invalid_meta_file() {
+    let sandbox = TestEnv::default();
+    let cargo_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture_path = cargo_dir.join("tests/fixtures/workspace/contracts/add");
+    let meta_file = sandbox.dir().join("meta.txt");
+    std::fs::write(&meta_file, "version=1\n=no key\n").unwrap();
+
+    sandbox
+        .new_assert_cmd("contract")
+        .current_dir(&fixture_path)
+        .arg("build")
+        .arg("--print-commands-only")
+        .arg("--meta-file")
+        .arg(&meta_file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "line 2 of meta file: invalid meta entry: key must not be empty",
+        ));
+}
+
+#[test]
+fn build_verify_reproducible() {
+    use sha2::{Digest, Sha256};
+
+    let sandbox = TestEnv::default();
+    let cargo_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture_path = cargo_dir.join("tests/fixtures/workspace/contracts/add");
+    let outdir = sandbox.dir().join("out");
+
+    sandbox
+        .new_assert_cmd("contract")
+        .current_dir(&fixture_path)
+        .arg("build")
+        .arg("--out-dir")
+        .arg(&outdir)
+        .arg("--verify-reproducible")
+        .arg("00".repeat(32))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "does not match the expected hash {}",
+            "00".repeat(32)
+        )));
+
+    // The wasm is still copied to the out dir to inspect it
+    let wasm = std::fs::read(outdir.join("add.wasm")).unwrap();
+    let hash = hex::encode(Sha256::digest(wasm));
+
+    sandbox
+        .new_assert_cmd("contract")
+        .current_dir(&fixture_path)
+        .arg("build")
+        .arg("--verify-reproducible")
+        .arg(&hash)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Wasm hash {hash} matches the expected hash"
+        )));
+}
+
+#[test]
+fn build_verify_reproducible_multiple_packages() {
+    let sandbox = TestEnv::default();
+    let cargo_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture_path = cargo_dir.join("tests/fixtures/workspace/");
+    sandbox
+        .new_assert_cmd("contract")
+        .current_dir(fixture_path)
+        .arg("build")
+        .arg("--verify-reproducible")
+        .arg("00".repeat(32))
+        .assert()
+        .failure()
+        .stderr(predicate::eq(
+            "\
+error: --verify-reproducible requires building exactly one package, found 3
+",
+        ));
+}
+
 // Test that bins don't contain absolute paths to the local crate registry.
 //
 // See make_rustflags_to_remap_absolute_paths
@@ -1,5 +1,5 @@
 use assert_fs::TempDir;
-use predicates::prelude::predicate;
+use predicates::prelude::{predicate, PredicateBooleanExt};
 use soroban_test::{AssertExt, TestEnv};
 use std::{fs, path::Path};
 
@@ -290,6 +290,106 @@ fn use_env() {
         .stdout("SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD\n");
 }
 
+#[test]
+fn sign_data() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .env(
+            "SOROBAN_SECRET_KEY",
+            "SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD",
+        )
+        .arg("add")
+        .arg("bob")
+        .assert()
+        .success();
+
+    let signature =
+        "2S3BSx6OY+6scN0sm11P9M4jFhzmr0RYTi+BfOauTiLQPPm9xM38GT3PL8duQZkAfDN9hNHQRnkfn2qZqN9vAg==\n";
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("sign-data")
+        .arg("bob")
+        .arg("--data=68656c6c6f")
+        .assert()
+        .success()
+        .stdout(signature);
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("sign-data")
+        .arg("bob")
+        .arg("--encoding=base64")
+        .write_stdin("aGVsbG8=\n")
+        .assert()
+        .success()
+        .stdout(signature);
+}
+
+#[test]
+fn show_key() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .env(
+            "SOROBAN_SECRET_KEY",
+            "SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD",
+        )
+        .arg("add")
+        .arg("bob")
+        .arg("--local")
+        .assert()
+        .success();
+
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("show")
+        .arg("bob")
+        .assert()
+        .success()
+        .stdout("GA4UDP4BFQHDUT3SUIKDHFQXXS7TFQ3NGXLPYTZBWZI6MHMB35KPTA2K\n");
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("show")
+        .arg("bob")
+        .arg("--reveal")
+        .assert()
+        .success()
+        .stdout(
+            "GA4UDP4BFQHDUT3SUIKDHFQXXS7TFQ3NGXLPYTZBWZI6MHMB35KPTA2K\n\
+             SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD\n",
+        )
+        .stderr(predicates::str::contains("Revealing the secret key of bob"));
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("ls")
+        .arg("--long")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Name: bob\nAddress: GA4UDP4BFQHDUT3SUIKDHFQXXS7TFQ3NGXLPYTZBWZI6MHMB35KPTA2K\n",
+        ));
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("secret")
+        .arg("bob")
+        .assert()
+        .success()
+        .stdout("SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD\n");
+
+    fs::write(
+        sandbox.dir().join(".stellar/identity/broken.toml"),
+        "not a key",
+    )
+    .unwrap();
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("ls")
+        .arg("--long")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Failed to read identity broken"));
+}
+
 #[test]
 fn config_dirs_precedence() {
     let sandbox = TestEnv::default();
@@ -302,6 +402,7 @@ fn config_dirs_precedence() {
         )
         .arg("add")
         .arg("alice")
+        .arg("--local")
         .assert()
         .success();
 
@@ -319,6 +420,7 @@ fn config_dirs_precedence() {
         )
         .arg("add")
         .arg("alice")
+        .arg("--local")
         .assert()
         .success();
 
@@ -341,6 +443,154 @@ fn config_dirs_precedence() {
         .stdout("SAQMV6P3OWM2SKCK3OEWNXSRYWK5RNNUL5CPHQGIJF2WVT4EI2BZ63GG\n");
 }
 
+#[test]
+fn generate_key_config_scopes() {
+    let sandbox = TestEnv::default();
+    let global_identity = |name: &str| {
+        sandbox
+            .dir()
+            .join("config")
+            .join("stellar")
+            .join("identity")
+            .join(format!("{name}.toml"))
+    };
+    let local_identity = |dir: &Path, name: &str| {
+        dir.join(".stellar")
+            .join("identity")
+            .join(format!("{name}.toml"))
+    };
+    let generate = |name: &str| {
+        let mut cmd = sandbox.new_assert_cmd("keys");
+        cmd.args(["generate", "--no-fund", name]);
+        cmd
+    };
+
+    // A local config in the current directory is used by default
+    fs::create_dir(sandbox.dir().join(".stellar")).unwrap();
+    generate("project").assert().success();
+    assert!(local_identity(sandbox.dir(), "project").exists());
+
+    generate("global").arg("--global").assert().success();
+    assert!(global_identity("global").exists());
+    assert!(!local_identity(sandbox.dir(), "global").exists());
+
+    // Without a local config in the current directory or its parents the global config is used,
+    // unless `--local` or `--config-dir` is given
+    let outside = TempDir::new().unwrap();
+    generate("outside")
+        .current_dir(outside.path())
+        .assert()
+        .success();
+    assert!(global_identity("outside").exists());
+    assert!(!local_identity(outside.path(), "outside").exists());
+
+    generate("local")
+        .current_dir(outside.path())
+        .arg("--local")
+        .assert()
+        .success();
+    assert!(local_identity(outside.path(), "local").exists());
+
+    generate("config_dir")
+        .arg("--config-dir")
+        .arg(outside.path())
+        .assert()
+        .success();
+    assert!(local_identity(outside.path(), "config_dir").exists());
+}
+
+#[test]
+fn rename_and_remove_identity() {
+    let sandbox = TestEnv::default();
+    let global_identity = |name: &str| {
+        sandbox
+            .dir()
+            .join("config")
+            .join("stellar")
+            .join("identity")
+            .join(format!("{name}.toml"))
+    };
+    let local_identity = |name: &str| {
+        sandbox
+            .dir()
+            .join(".stellar")
+            .join("identity")
+            .join(format!("{name}.toml"))
+    };
+    let keys = |args: &[&str]| {
+        let mut cmd = sandbox.new_assert_cmd("keys");
+        cmd.args(args);
+        cmd
+    };
+
+    fs::create_dir(sandbox.dir().join(".stellar")).unwrap();
+    keys(&["generate", "--no-fund", "alice"]).assert().success();
+    keys(&["generate", "--no-fund", "bob"]).assert().success();
+    keys(&["generate", "--no-fund", "--global", "carol"])
+        .assert()
+        .success();
+
+    // Renaming refuses to replace an existing identity unless `--overwrite` is given
+    keys(&["rename", "alice", "bob"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+    assert!(local_identity("alice").exists());
+
+    keys(&["rename", "alice", "dave"]).assert().success();
+    assert!(!local_identity("alice").exists());
+    assert!(local_identity("dave").exists());
+
+    keys(&["rename", "--overwrite", "dave", "bob"])
+        .assert()
+        .success();
+    assert!(!local_identity("dave").exists());
+    assert!(local_identity("bob").exists());
+
+    // An identity found in the global config is renamed there
+    keys(&["rename", "carol", "erin"]).assert().success();
+    assert!(!global_identity("carol").exists());
+    assert!(global_identity("erin").exists());
+    assert!(!local_identity("erin").exists());
+
+    // `--local` only looks in the local config
+    keys(&["rm", "--yes", "--local", "erin"]).assert().failure();
+    assert!(global_identity("erin").exists());
+
+    keys(&["rm", "--yes", "erin"]).assert().success();
+    assert!(!global_identity("erin").exists());
+
+    keys(&["rm", "--yes", "bob"]).assert().success();
+    assert!(!local_identity("bob").exists());
+
+    keys(&["rm", "--yes", "bob"]).assert().failure();
+}
+
+#[test]
+fn rename_default_identity() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--no-fund", "alice"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["use", "alice"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["rename", "alice", "bob"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("env")
+        .env_remove("SOROBAN_ACCOUNT")
+        .assert()
+        .stdout(predicate::str::contains("STELLAR_ACCOUNT=bob"));
+}
+
 #[test]
 fn set_default_identity() {
     let sandbox = TestEnv::default();
@@ -373,6 +623,58 @@ fn set_default_identity() {
         .success();
 }
 
+#[test]
+fn json_log_format() {
+    let sandbox = TestEnv::default();
+
+    sandbox
+        .new_assert_cmd("keys")
+        .env(
+            "SOROBAN_SECRET_KEY",
+            "SC4ZPYELVR7S7EE7KZDZN3ETFTNQHHLTUL34NUAAWZG5OK2RGJ4V2U3Z",
+        )
+        .arg("add")
+        .arg("alice")
+        .assert()
+        .success();
+
+    sandbox
+        .new_assert_cmd("keys")
+        .env("STELLAR_LOG_FORMAT", "json")
+        .arg("use")
+        .arg("alice")
+        .assert()
+        .stderr(predicate::str::contains(
+            r#"{"level":"info","msg":"The default source account is set to `alice`"}"#,
+        ))
+        .success();
+}
+
+#[test]
+fn no_color() {
+    let sandbox = TestEnv::default();
+
+    sandbox
+        .new_assert_cmd("keys")
+        .env(
+            "SOROBAN_SECRET_KEY",
+            "SC4ZPYELVR7S7EE7KZDZN3ETFTNQHHLTUL34NUAAWZG5OK2RGJ4V2U3Z",
+        )
+        .arg("add")
+        .arg("alice")
+        .assert()
+        .success();
+
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("use")
+        .arg("alice")
+        .arg("--no-color")
+        .assert()
+        .stderr("info: The default source account is set to `alice`\n")
+        .success();
+}
+
 #[test]
 fn set_default_network() {
     let sandbox = TestEnv::default();
@@ -415,6 +717,77 @@ fn cannot_create_contract_with_test_name() {
         .failure();
 }
 
+#[test]
+fn ls_json() {
+    let sandbox = TestEnv::default();
+    fs::create_dir(sandbox.dir().join(".stellar")).unwrap();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--no-fund", "alice"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--no-fund", "--global", "bob"])
+        .assert()
+        .success();
+    let address = sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "alice"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let keys: serde_json::Value = serde_json::from_str(
+        &sandbox
+            .new_assert_cmd("keys")
+            .args(["ls", "--json"])
+            .assert()
+            .success()
+            .stdout_as_str(),
+    )
+    .unwrap();
+    let keys = keys.as_array().unwrap();
+    let key = |name: &str| {
+        keys.iter()
+            .find(|key| key["name"] == name)
+            .unwrap_or_else(|| panic!("{name} not in {keys:?}"))
+    };
+    assert_eq!(key("alice")["public_key"], address.as_str());
+    assert_eq!(key("alice")["scope"], "local");
+    assert_eq!(key("bob")["scope"], "global");
+
+    sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "alias",
+            "add",
+            "token",
+            "--id=CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE",
+        ])
+        .assert()
+        .success();
+    let aliases: serde_json::Value = serde_json::from_str(
+        &sandbox
+            .new_assert_cmd("contract")
+            .args(["alias", "ls", "--json"])
+            .assert()
+            .success()
+            .stdout_as_str(),
+    )
+    .unwrap();
+    let [alias] = aliases.as_array().unwrap().as_slice() else {
+        panic!("expected one alias in {aliases}");
+    };
+    assert_eq!(alias["name"], "token");
+    assert_eq!(
+        alias["contract_id"],
+        "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"
+    );
+    assert_eq!(alias["network_passphrase"], LOCAL_NETWORK_PASSPHRASE);
+    assert!(alias["network"].is_string());
+    assert_eq!(alias["scope"], "local");
+}
+
 #[test]
 fn cannot_create_key_with_alias() {
     let sandbox = TestEnv::default();
@@ -437,3 +810,158 @@ fn cannot_create_key_with_alias() {
         ))
         .failure();
 }
+
+#[test]
+fn contract_id_asset_native_aliases() {
+    let sandbox = TestEnv::default();
+    let id = |asset: &str| {
+        sandbox
+            .new_assert_cmd("contract")
+            .args(["id", "asset", "--asset", asset])
+            .assert()
+            .success()
+            .stdout_as_str()
+    };
+    let native = id("native");
+    assert!(native.starts_with('C'));
+    assert_eq!(native, id("XLM"));
+}
+
+#[test]
+fn contract_id_asset_invalid_issuer() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "id",
+            "asset",
+            "--asset",
+            "USDC:GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        ])
+        .assert()
+        .stderr(predicate::str::contains("invalid asset issuer"))
+        .failure();
+}
+
+#[test]
+fn import_key() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("import")
+        .arg("carol")
+        .write_stdin("SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD\n")
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicates::str::contains("SDIY6AQQ").not());
+
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("address")
+        .arg("carol")
+        .assert()
+        .success()
+        .stdout("GA4UDP4BFQHDUT3SUIKDHFQXXS7TFQ3NGXLPYTZBWZI6MHMB35KPTA2K\n");
+
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("import")
+        .arg("carol")
+        .arg("--secret-key")
+        .arg("SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--overwrite"));
+
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("import")
+        .arg("carol")
+        .arg("--overwrite")
+        .arg("--seed-phrase")
+        .arg("GA4UDP4BFQHDUT3SUIKDHFQXXS7TFQ3NGXLPYTZBWZI6MHMB35KPTA2K")
+        .assert()
+        .failure();
+
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("import")
+        .arg("dave")
+        .arg("--seed-phrase")
+        .arg(DEFAULT_SEED_PHRASE)
+        .arg("--hd-path")
+        .arg("1")
+        .arg("--local")
+        .assert()
+        .success();
+    let file_contents =
+        fs::read_to_string(sandbox.dir().join(".stellar/identity/dave.toml")).unwrap();
+    assert_eq!(
+        file_contents,
+        format!("seed_phrase = \"{DEFAULT_SEED_PHRASE}\"\nhd_path = 1\n")
+    );
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("address")
+        .arg("dave")
+        .assert()
+        .success();
+}
+
+#[test]
+fn network_default_identity() {
+    let sandbox = TestEnv::default();
+    add_network(&sandbox, "custom");
+    sandbox
+        .new_assert_cmd("network")
+        .args(["use", "custom", "--identity", "bob"])
+        .assert()
+        .failure();
+    sandbox
+        .new_assert_cmd("keys")
+        .env(
+            "SOROBAN_SECRET_KEY",
+            "SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD",
+        )
+        .arg("add")
+        .arg("bob")
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("network")
+        .args(["use", "custom", "--identity", "bob"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("network")
+        .args(["ls", "--long"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "default_identity: Some(\n        \"bob\",\n    ),",
+        ));
+}
+
+#[test]
+fn address_all() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .env(
+            "SOROBAN_SECRET_KEY",
+            "SDIY6AQQ75WMD4W46EYB7O6UYMHOCGQHLAQGQTKHDX4J2DYQCHVCQYFD",
+        )
+        .arg("add")
+        .arg("bob")
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "--all"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "bob: GA4UDP4BFQHDUT3SUIKDHFQXXS7TFQ3NGXLPYTZBWZI6MHMB35KPTA2K\n",
+        ));
+}
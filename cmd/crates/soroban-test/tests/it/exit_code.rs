@@ -0,0 +1,52 @@
+use predicates::prelude::{predicate, PredicateBooleanExt};
+use soroban_test::TestEnv;
+
+#[test]
+fn failing_command_exits_nonzero_with_error_on_stderr() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "does-not-exist"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("error:").and(predicate::str::contains("does-not-exist")));
+}
+
+#[test]
+fn json_error_format() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "does-not-exist", "--error-format", "json"])
+        .assert()
+        .code(12)
+        .stderr(
+            predicate::str::starts_with(r#"{"error":{"#)
+                .and(predicate::str::contains(r#""type":"keys""#)),
+        );
+}
+
+#[test]
+fn json_error_format_of_invalid_arguments() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "--no-such-flag", "--error-format=json"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(r#""type":"usage""#));
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "--no-such-flag"])
+        .env("STELLAR_ERROR_FORMAT", "json")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(r#""type":"usage""#));
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["--help", "--error-format=json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage"));
+}
@@ -1,5 +1,5 @@
 use soroban_cli::commands::contract::{self, arg_parsing};
-use soroban_test::TestEnv;
+use soroban_test::{AssertExt, TestEnv};
 
 use crate::util::{invoke_custom as invoke, CUSTOM_TYPES, DEFAULT_CONTRACT_ID};
 
@@ -99,3 +99,101 @@ fn build() {
         .assert()
         .success();
 }
+
+#[test]
+fn contract_inspect_report() {
+    let sandbox = TestEnv::default();
+    let wasm = CUSTOM_TYPES;
+    sandbox
+        .new_assert_cmd("contract")
+        .arg("inspect")
+        .arg("--wasm")
+        .arg(wasm.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "Size: {} bytes",
+            wasm.bytes().len()
+        )))
+        .stdout(predicates::str::contains(format!(
+            "SHA256: {}",
+            wasm.hash().unwrap()
+        )))
+        .stdout(predicates::str::contains("Protocol Version:"))
+        .stdout(predicates::str::contains("Function: strukt"));
+}
+
+#[test]
+fn contract_info_validate() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["info", "validate", "--wasm"])
+        .arg(CUSTOM_TYPES.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("Valid contract wasm with"));
+}
+
+#[test]
+fn contract_info_out_dir() {
+    let sandbox = TestEnv::default();
+    let out_dir = sandbox.dir().path().join("info");
+    let info = |section: &str| {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args(["info", section, "--output", "json", "--wasm"])
+            .arg(CUSTOM_TYPES.path())
+            .arg("--out-dir")
+            .arg(&out_dir);
+        cmd
+    };
+    info("meta")
+        .assert()
+        .success()
+        .stdout(predicates::str::is_empty());
+    info("spec").assert().success();
+    assert!(out_dir.join("meta.json").exists());
+    assert!(out_dir.join("spec.json").exists());
+
+    info("meta")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--overwrite"));
+    info("meta").arg("--overwrite").assert().success();
+}
+
+#[test]
+fn contract_info_example_args() {
+    let sandbox = TestEnv::default();
+    let output = sandbox
+        .new_assert_cmd("contract")
+        .args(["info", "example-args", "strukt", "--wasm"])
+        .arg(CUSTOM_TYPES.path())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let args: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        args,
+        serde_json::json!({ "strukt": { "a": 0, "b": false, "c": "" } })
+    );
+}
+
+#[test]
+fn contract_info_ls() {
+    let sandbox = TestEnv::default();
+    let ls = |json: bool| {
+        let mut cmd = sandbox.new_assert_cmd("contract");
+        cmd.args(["info", "ls", "--wasm"]).arg(CUSTOM_TYPES.path());
+        if json {
+            cmd.arg("--json");
+        }
+        cmd.assert().success().stdout_as_str()
+    };
+    let names = ls(false);
+    let names = names.lines().collect::<Vec<_>>();
+    assert!(names.contains(&"strukt"));
+    assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+    let json: Vec<String> = serde_json::from_str(&ls(true)).unwrap();
+    assert_eq!(json, names);
+}
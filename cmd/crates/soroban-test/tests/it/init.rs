@@ -85,3 +85,107 @@ fn init_and_deploy() {
 
     assert.success();
 }
+
+fn git(dir: &std::path::Path, args: &[&str]) -> std::process::Output {
+    std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn init_git() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["init", "project", "--git"])
+        .env("GIT_AUTHOR_NAME", "test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .assert()
+        .success();
+
+    let project = sandbox.dir().join("project");
+    assert!(project.join(".git").is_dir());
+    let log = git(&project, &["log", "--format=%s"]);
+    assert_eq!(String::from_utf8(log.stdout).unwrap(), "Initial commit\n");
+    let status = git(&project, &["status", "--porcelain"]);
+    assert!(status.stdout.is_empty());
+}
+
+#[test]
+fn init_git_inside_existing_repo() {
+    let sandbox = TestEnv::default();
+    assert!(git(sandbox.dir(), &["init", "--quiet"]).status.success());
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["init", "project", "--git"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("already inside"));
+
+    assert!(!sandbox.dir().join("project/.git").exists());
+}
+
+#[test]
+fn init_without_git() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["init", "project"])
+        .assert()
+        .success();
+
+    assert!(!sandbox.dir().join("project/.git").exists());
+}
+
+#[test]
+fn init_nested_path_with_trailing_slash() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["init", "nested/project/", "--name", "counter"])
+        .assert()
+        .success();
+
+    let project = sandbox.dir().join("nested/project");
+    assert!(project.join("Cargo.toml").exists());
+    assert!(project.join("contracts/counter/Cargo.toml").exists());
+}
+
+#[test]
+fn init_current_dir() {
+    let sandbox = TestEnv::default();
+    let project = sandbox.dir().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    for path in [".", "./"] {
+        sandbox
+            .new_assert_cmd("contract")
+            .current_dir(&project)
+            .args(["init", path, "--overwrite"])
+            .assert()
+            .success();
+    }
+
+    assert!(project.join("Cargo.toml").exists());
+    assert!(project.join("contracts/hello-world/Cargo.toml").exists());
+}
+
+#[test]
+fn init_invalid_name() {
+    let sandbox = TestEnv::default();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["init", "project", "--name", "my contract"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid contract name"));
+
+    assert!(!sandbox.dir().join("project").exists());
+}
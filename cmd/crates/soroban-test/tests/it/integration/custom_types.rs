@@ -1,7 +1,7 @@
 use serde_json::json;
 
 use soroban_cli::commands;
-use soroban_test::TestEnv;
+use soroban_test::{AssertExt, TestEnv};
 
 use crate::integration::util::{deploy_custom, extend_contract};
 
@@ -23,6 +23,9 @@ async fn parse() {
     symbol_with_quotes(sandbox, id).await;
     multi_arg_success(sandbox, id);
     bytes_as_file(sandbox, id);
+    bytes_as_at_path(sandbox, id);
+    strukt_as_arg_file(sandbox, id);
+    strukt_as_at_path(sandbox, id);
     map(sandbox, id).await;
     vec_(sandbox, id).await;
     tuple(sandbox, id).await;
@@ -48,12 +51,17 @@ async fn parse() {
     number_arg_return_ok(sandbox, id);
     void(sandbox, id);
     val(sandbox, id);
+    output_xdr(sandbox, id);
+    output_json_formatted(sandbox, id);
     parse_u128(sandbox, id);
     parse_i128(sandbox, id);
     parse_negative_i128(sandbox, id);
     parse_u256(sandbox, id);
     parse_i256(sandbox, id);
     parse_negative_i256(sandbox, id);
+    parse_u256_max(sandbox, id);
+    parse_u256_hex(sandbox, id);
+    parse_i256_bounds(sandbox, id);
     boolean(sandbox, id);
     boolean_two(sandbox, id);
     boolean_no_flag(sandbox, id);
@@ -106,6 +114,54 @@ fn bytes_as_file(sandbox: &TestEnv, id: &str) {
         .stdout("\"0000000000000000007374656c6c6172\"\n");
 }
 
+fn bytes_as_at_path(sandbox: &TestEnv, id: &str) {
+    let env = &TestEnv::default();
+    let path = env.temp_dir.join("bytes.txt");
+    std::fs::write(&path, 0x0073_7465_6c6c_6172u128.to_be_bytes()).unwrap();
+    invoke_custom(sandbox, id, "bytes")
+        .arg(format!("--bytes=@{}", path.display()))
+        .assert()
+        .success()
+        .stdout("\"0000000000000000007374656c6c6172\"\n");
+}
+
+const STRUKT: &str = r#"{"a":42,"b":true,"c":"world"}"#;
+
+fn strukt_inline(sandbox: &TestEnv, id: &str) -> String {
+    invoke_custom(sandbox, id, "strukt")
+        .arg("--strukt")
+        .arg(STRUKT)
+        .assert()
+        .success()
+        .stdout_as_str()
+}
+
+fn strukt_as_arg_file(sandbox: &TestEnv, id: &str) {
+    let env = &TestEnv::default();
+    let path = env.temp_dir.join("strukt.json");
+    std::fs::write(&path, STRUKT).unwrap();
+    let from_file = invoke_custom(sandbox, id, "strukt")
+        .arg("--arg-file")
+        .arg(format!("strukt={}", path.display()))
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(from_file, strukt_inline(sandbox, id));
+}
+
+fn strukt_as_at_path(sandbox: &TestEnv, id: &str) {
+    let env = &TestEnv::default();
+    let path = env.temp_dir.join("strukt.json");
+    std::fs::write(&path, STRUKT).unwrap();
+    let from_file = invoke_custom(sandbox, id, "strukt")
+        .arg("--strukt")
+        .arg(format!("@{}", path.display()))
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(from_file, strukt_inline(sandbox, id));
+}
+
 async fn map(sandbox: &TestEnv, id: &str) {
     invoke_with_roundtrip(sandbox, id, "map", json!({"0": true, "1": false})).await;
 }
@@ -213,6 +269,40 @@ fn val(sandbox: &TestEnv, id: &str) {
         .stdout("null\n");
 }
 
+fn output_xdr(sandbox: &TestEnv, id: &str) {
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id", id, "--output", "xdr", "--", "multi_args"])
+        .args(["--a", "42", "--b"])
+        .assert()
+        .success()
+        .stdout("AAAAAwAAACo=\n");
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["invoke", "--id", id, "--output", "xdr", "--", "woid"])
+        .assert()
+        .success()
+        .stdout("AAAAAQ==\n");
+}
+
+fn output_json_formatted(sandbox: &TestEnv, id: &str) {
+    sandbox
+        .new_assert_cmd("contract")
+        .args([
+            "invoke",
+            "--id",
+            id,
+            "--output",
+            "json-formatted",
+            "--",
+            "strukt",
+        ])
+        .args(["--strukt", STRUKT])
+        .assert()
+        .success()
+        .stdout("{\n  \"a\": 42,\n  \"b\": true,\n  \"c\": \"world\"\n}\n");
+}
+
 async fn i32(sandbox: &TestEnv, id: &str) {
     invoke_with_roundtrip(sandbox, id, "i32_", 42).await;
 }
@@ -357,6 +447,48 @@ fn parse_negative_i256(sandbox: &TestEnv, id: &str) {
         ));
 }
 
+fn parse_u256_max(sandbox: &TestEnv, id: &str) {
+    let num = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+    invoke_custom(sandbox, id, "u256")
+        .arg("--u256")
+        .arg(num)
+        .assert()
+        .success()
+        .stdout(format!(
+            r#""{num}"
+"#,
+        ));
+}
+
+fn parse_u256_hex(sandbox: &TestEnv, id: &str) {
+    invoke_custom(sandbox, id, "u256")
+        .arg("--u256")
+        .arg("0xff")
+        .assert()
+        .success()
+        .stdout(
+            r#""255"
+"#,
+        );
+}
+
+fn parse_i256_bounds(sandbox: &TestEnv, id: &str) {
+    for num in [
+        "57896044618658097711785492504343953926634992332820282019728792003956564819967",
+        "-57896044618658097711785492504343953926634992332820282019728792003956564819968",
+    ] {
+        invoke_custom(sandbox, id, "i256")
+            .arg("--i256")
+            .arg(num)
+            .assert()
+            .success()
+            .stdout(format!(
+                r#""{num}"
+"#,
+            ));
+    }
+}
+
 fn boolean(sandbox: &TestEnv, id: &str) {
     invoke_custom(sandbox, id, "boolean")
         .arg("--boolean")
@@ -100,6 +100,7 @@ async fn invoke() {
     invoke_hello_world_with_lib(sandbox, id).await;
     let config_locator = locator::Args {
         global: false,
+        local: false,
         config_dir: Some(dir.to_path_buf()),
     };
     config_locator
@@ -247,7 +248,7 @@ fn contract_data_read_failure(sandbox: &TestEnv, id: &str) {
         .assert()
         .failure()
         .stderr(
-            "❌ error: no matching contract data entries were found for the specified contract id\n",
+            "error: no matching contract data entries were found for the specified contract id\n",
         );
 }
 
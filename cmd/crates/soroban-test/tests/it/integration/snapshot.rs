@@ -1,5 +1,7 @@
 use assert_fs::prelude::*;
 use predicates::prelude::*;
+use soroban_cli::xdr::LedgerKey;
+use soroban_ledger_snapshot::LedgerSnapshot;
 use soroban_test::{AssertExt, TestEnv};
 
 #[test]
@@ -80,4 +82,30 @@ fn snapshot() {
         .assert(predicates::str::contains(&account_b).not())
         .assert(predicates::str::contains(&contract_b))
         .assert(predicates::str::contains(&contract_a).not());
+    // Contract entries are saved with the live-until ledger of their TTL
+    // entry instead of a placeholder.
+    let snapshot = LedgerSnapshot::read_file(sandbox.dir().join("snapshot.json")).unwrap();
+    let contract_entries = snapshot
+        .ledger_entries
+        .iter()
+        .filter(|(key, _)| matches!(**key, LedgerKey::ContractData(_)))
+        .collect::<Vec<_>>();
+    assert!(!contract_entries.is_empty());
+    for (_, (_, live_until)) in contract_entries {
+        let live_until = live_until.unwrap();
+        assert_ne!(live_until, u32::MAX);
+        assert!(live_until >= snapshot.sequence_number);
+    }
+    // With `--out -` the snapshot is written to stdout instead.
+    sandbox
+        .new_assert_cmd("snapshot")
+        .arg("create")
+        .arg("--output=json")
+        .arg("--out=-")
+        .arg("--address")
+        .arg(&account_a)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(&account_a))
+        .stderr("");
 }
@@ -1,3 +1,4 @@
+use predicates::prelude::PredicateBooleanExt;
 use soroban_cli::assembled::simulate_and_assemble_transaction;
 use soroban_cli::xdr::{Limits, ReadXdr, TransactionEnvelope, WriteXdr};
 use soroban_test::{AssertExt, TestEnv};
@@ -84,3 +85,256 @@ async fn build_simulate_sign_send() {
         .success()
         .stdout(predicates::str::contains("SUCCESS"));
 }
+
+#[tokio::test]
+async fn send_dry_run() {
+    let sandbox = &TestEnv::new();
+    let tx_simulated = deploy_contract(sandbox, HELLO_WORLD, DeployKind::SimOnly, None).await;
+    let tx_env = TransactionEnvelope::from_xdr_base64(&tx_simulated, Limits::none()).unwrap();
+    let tx = soroban_cli::commands::tx::xdr::unwrap_envelope_v1(tx_env).unwrap();
+    let hash = hex::encode(
+        soroban_cli::utils::transaction_hash(&tx, &sandbox.network.network_passphrase).unwrap(),
+    );
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("send")
+        .arg("--dry-run")
+        .write_stdin(tx_simulated.as_bytes())
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicates::str::contains(format!(
+            "Transaction hash is {hash}"
+        )))
+        .stderr(predicates::str::contains("Operation 1: InvokeHostFunction"));
+}
+
+#[tokio::test]
+async fn sign_with_key_from_stdin() {
+    let sandbox = &TestEnv::new();
+    let tx_simulated = deploy_contract(sandbox, HELLO_WORLD, DeployKind::SimOnly, None).await;
+    let input = sandbox.dir().join("tx.txt");
+    std::fs::write(&input, &tx_simulated).unwrap();
+    let secret_key = sandbox
+        .new_assert_cmd("keys")
+        .arg("secret")
+        .arg("test")
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    let tx_signed = sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .arg("--sign-with-key=test")
+        .write_stdin(tx_simulated.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .arg("--sign-with-key-stdin")
+        .arg("--input")
+        .arg(&input)
+        .write_stdin(secret_key.as_bytes())
+        .assert()
+        .success()
+        .stdout(format!("{tx_signed}\n"))
+        .stderr(predicates::str::contains(secret_key.as_str()).not());
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .arg("--sign-with-key-stdin")
+        .write_stdin(secret_key.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("`--input` is required"));
+}
+
+#[tokio::test]
+async fn sign_to_output_file() {
+    let sandbox = &TestEnv::new();
+    let tx_simulated = deploy_contract(sandbox, HELLO_WORLD, DeployKind::SimOnly, None).await;
+    let output_file = sandbox.dir().join("signed/tx.txt");
+
+    let tx_signed = sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .arg("--sign-with-key=test")
+        .write_stdin(tx_simulated.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .arg("--sign-with-key=test")
+        .arg("--output-file")
+        .arg(&output_file)
+        .write_stdin(tx_simulated.as_bytes())
+        .assert()
+        .success()
+        .stdout("");
+    assert_eq!(
+        std::fs::read_to_string(&output_file).unwrap(),
+        format!("{tx_signed}\n")
+    );
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .arg("--sign-with-key=test")
+        .arg("--output-file")
+        .arg(&output_file)
+        .write_stdin(tx_simulated.as_bytes())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("use --overwrite to replace it"));
+
+    sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .arg("--sign-with-key=test")
+        .arg("--output-file")
+        .arg(&output_file)
+        .arg("--overwrite")
+        .write_stdin(tx_simulated.as_bytes())
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+async fn sign_with_multiple_signers() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("keys")
+        .arg("generate")
+        .arg("test2")
+        .assert()
+        .success();
+    let tx_simulated = deploy_contract(sandbox, HELLO_WORLD, DeployKind::SimOnly, None).await;
+
+    let tx_signed = sandbox
+        .new_assert_cmd("tx")
+        .arg("sign")
+        .arg("--signers=test,test2,test")
+        .write_stdin(tx_simulated.as_bytes())
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("already signed"))
+        .stdout_as_str();
+
+    let TransactionEnvelope::Tx(tx_env) =
+        TransactionEnvelope::from_xdr_base64(&tx_signed, Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 transaction envelope");
+    };
+    assert_eq!(tx_env.signatures.len(), 2);
+}
+
+#[tokio::test]
+async fn simulate_resource_only() {
+    let sandbox = &TestEnv::new();
+    let xdr_base64_build_only =
+        deploy_contract(sandbox, HELLO_WORLD, DeployKind::BuildOnly, None).await;
+    let resources = sandbox
+        .new_assert_cmd("tx")
+        .arg("simulate")
+        .arg("--resource-only")
+        .write_stdin(xdr_base64_build_only.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let resources: serde_json::Value = serde_json::from_str(&resources).unwrap();
+    assert!(resources["resources"]["instructions"].as_u64().unwrap() > 0);
+    assert!(resources["resources"]["write_bytes"].as_u64().unwrap() > 0);
+    assert!(resources["resources"]["read_bytes"].is_u64());
+    assert!(resources["resource_fee"].as_i64().unwrap() > 0);
+}
+
+#[tokio::test]
+async fn rebuild_footprint() {
+    let sandbox = &TestEnv::new();
+    let xdr_base64_build_only =
+        deploy_contract(sandbox, HELLO_WORLD, DeployKind::BuildOnly, None).await;
+    let xdr_base64_sim_only =
+        deploy_contract(sandbox, HELLO_WORLD, DeployKind::SimOnly, None).await;
+    let rebuilt = sandbox
+        .new_assert_cmd("tx")
+        .arg("rebuild-footprint")
+        .write_stdin(xdr_base64_build_only.as_bytes())
+        .assert()
+        .success()
+        .stdout_as_str();
+    let tx = |xdr: &str| {
+        soroban_cli::commands::tx::xdr::unwrap_envelope_v1(
+            TransactionEnvelope::from_xdr_base64(xdr, Limits::none()).unwrap(),
+        )
+        .unwrap()
+    };
+    let (build_only, sim_only, rebuilt) = (
+        tx(&xdr_base64_build_only),
+        tx(&xdr_base64_sim_only),
+        tx(&rebuilt),
+    );
+    let footprint = |tx: &soroban_cli::xdr::Transaction| match &tx.ext {
+        soroban_cli::xdr::TransactionExt::V1(data) => data.resources.footprint.clone(),
+        soroban_cli::xdr::TransactionExt::V0 => panic!("missing soroban transaction data"),
+    };
+    assert_eq!(footprint(&rebuilt), footprint(&sim_only));
+    assert_eq!(rebuilt.fee, build_only.fee);
+    assert_eq!(rebuilt.operations, build_only.operations);
+}
+
+#[tokio::test]
+async fn build_fetches_seq_num() {
+    let sandbox = &TestEnv::new();
+    let source = sandbox.test_address(0);
+    let account = sandbox.client().get_account(&source).await.unwrap();
+    let tx_env = sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "build",
+            "bump-sequence",
+            "--source",
+            "test",
+            "--bump-to",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout_as_str();
+    let tx = soroban_cli::commands::tx::xdr::unwrap_envelope_v1(
+        TransactionEnvelope::from_xdr_base64(tx_env, Limits::none()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(tx.seq_num.0, account.seq_num.0 + 1);
+}
+
+#[tokio::test]
+async fn build_unfunded_source_suggests_funding() {
+    let sandbox = &TestEnv::new();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["generate", "--no-fund", "unfunded"])
+        .assert()
+        .success();
+    sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "build",
+            "bump-sequence",
+            "--source",
+            "unfunded",
+            "--bump-to",
+            "1",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("fund it first"));
+}
@@ -101,3 +101,43 @@ async fn burn() {
         .assert()
         .stdout("\"9223372036854775707\"\n");
 }
+
+#[tokio::test]
+async fn info_meta_describes_asset() {
+    let sandbox = &TestEnv::new();
+    let issuer = sandbox
+        .new_assert_cmd("keys")
+        .args(["address", "test"])
+        .assert()
+        .stdout_as_str();
+    let asset = format!("META:{issuer}");
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["asset", "deploy", "--source=test", "--asset", &asset])
+        .assert()
+        .success();
+    let id = sandbox
+        .new_assert_cmd("contract")
+        .args(["id", "asset", "--asset", &asset])
+        .assert()
+        .success()
+        .stdout_as_str();
+    sandbox
+        .new_assert_cmd("contract")
+        .args(["info", "meta", "--id", &id])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            " • type: Stellar Asset Contract\n • asset: {asset}\n"
+        )));
+    let json = sandbox
+        .new_assert_cmd("contract")
+        .args(["info", "meta", "--id", &id, "--output", "json"])
+        .assert()
+        .success()
+        .stdout_as_str();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+        serde_json::json!({ "type": "stellar_asset_contract", "asset": asset })
+    );
+}
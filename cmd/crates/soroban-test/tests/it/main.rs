@@ -1,12 +1,16 @@
 mod arg_parsing;
 mod build;
 mod config;
+mod exit_code;
 mod help;
 mod init;
 // #[cfg(feature = "it")]
 mod integration;
 mod log;
 mod plugin;
+#[cfg(feature = "quickstart-tests")]
+mod quickstart;
 mod rpc_provider;
+mod tx_build;
 mod util;
 mod version;
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use serde_json::json;
+use soroban_test::TestEnv;
+use testcontainers::{
+    core::{ContainerPort, WaitFor},
+    runners::AsyncRunner,
+    ContainerAsync, Image,
+};
+use tokio::time::{sleep, Instant};
+
+const NAME: &str = "docker.io/stellar/quickstart";
+const TAG: &str = "testing";
+/// Port serving rpc (under `/soroban/rpc`) and friendbot (under `/friendbot`)
+const RPC_PORT: ContainerPort = ContainerPort::Tcp(8000);
+/// How long to wait for rpc to report it is healthy once the container is up
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(180);
+/// Delay between `getHealth` requests
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A local quickstart network with core, rpc and friendbot, the same services
+/// `stellar network container start local` runs.
+#[derive(Debug)]
+pub struct Quickstart {
+    cmd: Vec<String>,
+}
+
+impl Default for Quickstart {
+    fn default() -> Self {
+        Quickstart {
+            cmd: ["--local", "--enable", "rpc,horizon"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl Image for Quickstart {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn tag(&self) -> &str {
+        TAG
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::message_on_stdout("up and ready")]
+    }
+
+    fn expose_ports(&self) -> &[ContainerPort] {
+        &[RPC_PORT]
+    }
+
+    fn cmd(&self) -> impl IntoIterator<Item = impl Into<std::borrow::Cow<'_, str>>> {
+        self.cmd.iter().map(String::as_str)
+    }
+}
+
+/// A running quickstart container and the rpc url it is reachable at. The
+/// container is removed when this is dropped.
+pub struct LocalNetwork {
+    pub container: ContainerAsync<Quickstart>,
+    pub rpc_url: String,
+}
+
+impl LocalNetwork {
+    /// Start a quickstart container on a random host port and wait until its
+    /// rpc answers `getHealth` with `healthy`.
+    pub async fn start() -> LocalNetwork {
+        let container = Quickstart::default().start().await.unwrap();
+        let host = container.get_host().await.unwrap();
+        let port = container.get_host_port_ipv4(RPC_PORT).await.unwrap();
+        let rpc_url = format!("http://{host}:{port}/soroban/rpc");
+        wait_for_health(&rpc_url).await;
+        LocalNetwork { container, rpc_url }
+    }
+
+    /// A test environment targeting this network, with a funded `test` account.
+    pub fn test_env(&self) -> TestEnv {
+        TestEnv::with_rpc_url(&self.rpc_url)
+    }
+}
+
+/// Poll `getHealth` until rpc reports `healthy`, panicking after
+/// [`HEALTH_TIMEOUT`].
+async fn wait_for_health(rpc_url: &str) {
+    let client = reqwest::Client::new();
+    let deadline = Instant::now() + HEALTH_TIMEOUT;
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "getHealth" });
+    loop {
+        let status = match client.post(rpc_url).json(&request).send().await {
+            Ok(response) => response
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|body| body["result"]["status"].as_str().map(str::to_string)),
+            Err(_) => None,
+        };
+        if status.as_deref() == Some("healthy") {
+            return;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "rpc at {rpc_url} was not healthy after {HEALTH_TIMEOUT:?}, last status: {status:?}"
+        );
+        sleep(HEALTH_POLL_INTERVAL).await;
+    }
+}
+
+#[tokio::test]
+async fn quickstart_serves_rpc() {
+    let network = LocalNetwork::start().await;
+    let sandbox = network.test_env();
+    sandbox
+        .new_assert_cmd("keys")
+        .args(["fund", "test"])
+        .assert()
+        .success();
+}
@@ -0,0 +1,238 @@
+use soroban_cli::xdr::{
+    AlphaNum12, AlphaNum4, Asset, Limits, Memo, OperationBody, ReadXdr, TransactionEnvelope,
+    TransactionV1Envelope,
+};
+use soroban_test::{AssertExt, TestEnv};
+
+/// A sandbox with an unfunded `test` identity, building a transaction needs no network.
+fn sandbox() -> TestEnv {
+    let sandbox = TestEnv::default();
+    sandbox
+        .generate_account("test", None)
+        .arg("--no-fund")
+        .assert()
+        .success();
+    sandbox
+}
+
+fn build(sandbox: &TestEnv, args: &[&str]) -> TransactionV1Envelope {
+    let out = sandbox
+        .new_assert_cmd("tx")
+        .arg("build")
+        .args(args)
+        .assert()
+        .success()
+        .stdout_as_str();
+    let TransactionEnvelope::Tx(tx_env) =
+        TransactionEnvelope::from_xdr_base64(out, Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 transaction envelope");
+    };
+    tx_env
+}
+
+#[test]
+fn payment() {
+    let sandbox = sandbox();
+    let source = sandbox.test_address(0);
+    let destination = sandbox.test_address(1);
+    let tx_env = build(
+        &sandbox,
+        &[
+            "payment",
+            "--source",
+            &source,
+            "--seq-num",
+            "42",
+            "--fee",
+            "200",
+            "--destination",
+            &destination,
+            "--amount",
+            "10_000_000",
+            "--memo-id",
+            "7",
+        ],
+    );
+    assert!(tx_env.signatures.is_empty());
+    let tx = tx_env.tx;
+    assert_eq!(tx.source_account.to_string(), source);
+    assert_eq!(tx.seq_num.0, 42);
+    assert_eq!(tx.fee, 200);
+    assert_eq!(tx.memo, Memo::Id(7));
+    let [op] = tx.operations.as_slice() else {
+        panic!("expected one operation");
+    };
+    let OperationBody::Payment(payment) = &op.body else {
+        panic!("expected a payment");
+    };
+    assert_eq!(payment.destination.to_string(), destination);
+    assert_eq!(payment.amount, 10_000_000);
+}
+
+fn payment_of(sandbox: &TestEnv, asset: &str) -> Asset {
+    let destination = sandbox.test_address(1);
+    let tx_env = build(
+        sandbox,
+        &[
+            "payment",
+            "--source",
+            "test",
+            "--seq-num",
+            "1",
+            "--destination",
+            &destination,
+            "--amount",
+            "1",
+            "--asset",
+            asset,
+        ],
+    );
+    let OperationBody::Payment(payment) = &tx_env.tx.operations[0].body else {
+        panic!("expected a payment");
+    };
+    payment.asset.clone()
+}
+
+#[test]
+fn payment_asset_issued_by_identity() {
+    let sandbox = sandbox();
+    let issuer = sandbox.test_address(0);
+    let Asset::CreditAlphanum4(AlphaNum4 {
+        asset_code,
+        issuer: id,
+    }) = payment_of(&sandbox, "USDC:test")
+    else {
+        panic!("expected a CreditAlphanum4 asset");
+    };
+    assert_eq!(asset_code.to_string(), "USDC");
+    assert_eq!(id.to_string(), issuer);
+
+    let Asset::CreditAlphanum12(AlphaNum12 {
+        asset_code,
+        issuer: id,
+    }) = payment_of(&sandbox, &format!("LONGCODE:{issuer}"))
+    else {
+        panic!("expected a CreditAlphanum12 asset");
+    };
+    assert_eq!(asset_code.to_string(), "LONGCODE");
+    assert_eq!(id.to_string(), issuer);
+
+    assert!(matches!(payment_of(&sandbox, "XLM"), Asset::Native));
+}
+
+#[test]
+fn payment_invalid_asset_code() {
+    let sandbox = sandbox();
+    sandbox
+        .new_assert_cmd("tx")
+        .args([
+            "build",
+            "payment",
+            "--source",
+            "test",
+            "--seq-num",
+            "1",
+            "--destination",
+            &sandbox.test_address(1),
+            "--amount",
+            "1",
+            "--asset",
+            "TOOLONGASSETCODE:test",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("TOOLONGASSETCODE"));
+}
+
+#[test]
+fn create_account() {
+    let sandbox = sandbox();
+    let destination = sandbox.test_address(1);
+    let tx_env = build(
+        &sandbox,
+        &[
+            "create-account",
+            "--source",
+            "test",
+            "--seq-num",
+            "1",
+            "--destination",
+            &destination,
+        ],
+    );
+    assert_eq!(
+        tx_env.tx.source_account.to_string(),
+        sandbox.test_address(0)
+    );
+    assert_eq!(tx_env.tx.fee, 100);
+    let OperationBody::CreateAccount(op) = &tx_env.tx.operations[0].body else {
+        panic!("expected a create account operation");
+    };
+    assert_eq!(op.destination.to_string(), destination);
+    assert_eq!(op.starting_balance, 10_000_000);
+}
+
+#[test]
+fn bump_sequence() {
+    let sandbox = sandbox();
+    let tx_env = build(
+        &sandbox,
+        &[
+            "bump-sequence",
+            "--source",
+            "test",
+            "--seq-num",
+            "5",
+            "--bump-to",
+            "100",
+        ],
+    );
+    let OperationBody::BumpSequence(op) = &tx_env.tx.operations[0].body else {
+        panic!("expected a bump sequence operation");
+    };
+    assert_eq!(op.bump_to.0, 100);
+}
+
+#[test]
+fn requires_seq_num_without_network() {
+    let sandbox = sandbox();
+    sandbox
+        .new_assert_cmd("tx")
+        .env_remove("SOROBAN_RPC_URL")
+        .env_remove("SOROBAN_NETWORK_PASSPHRASE")
+        .args([
+            "build",
+            "bump-sequence",
+            "--source",
+            "test",
+            "--bump-to",
+            "1",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--seq-num is required"));
+}
+
+#[test]
+fn rejects_send_options() {
+    let sandbox = sandbox();
+    for flag in ["--build-only", "--sim-only", "--cost", "--instructions=1"] {
+        sandbox
+            .new_assert_cmd("tx")
+            .args([
+                "build",
+                "bump-sequence",
+                "--source",
+                "test",
+                "--seq-num",
+                "5",
+                "--bump-to",
+                "100",
+                flag,
+            ])
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("unexpected argument"));
+    }
+}
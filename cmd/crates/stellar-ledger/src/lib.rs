@@ -70,6 +70,34 @@ pub enum Error {
 
     #[error(transparent)]
     DecodeError(#[from] DecodeError),
+
+    #[error("Unexpected app configuration response from Ledger device: {0}")]
+    UnexpectedAppConfiguration(String),
+}
+
+/// Information about the Stellar app installed on the Ledger device, parsed from the
+/// `GET_APP_CONFIGURATION` response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// Version of the Stellar app, e.g. `5.0.3`
+    pub app_version: String,
+    /// Whether the user enabled hash signing in the app's settings
+    pub hash_signing_enabled: bool,
+}
+
+impl TryFrom<&[u8]> for DeviceInfo {
+    type Error = Error;
+
+    fn try_from(config: &[u8]) -> Result<Self, Self::Error> {
+        // The response is the hash signing flag followed by the major, minor and patch versions
+        let [hash_signing, major, minor, patch] = config else {
+            return Err(Error::UnexpectedAppConfiguration(hex::encode(config)));
+        };
+        Ok(DeviceInfo {
+            app_version: format!("{major}.{minor}.{patch}"),
+            hash_signing_enabled: *hash_signing != 0,
+        })
+    }
 }
 
 pub struct LedgerSigner<T: Exchange> {
@@ -111,6 +139,13 @@ where
         self.send_command_to_ledger(command).await
     }
 
+    /// Get the version of the Stellar app and whether hash signing is enabled
+    /// # Errors
+    /// Returns an error if there is an issue with connecting with the device or if the response cannot be parsed
+    pub async fn get_device_info(&self) -> Result<DeviceInfo, Error> {
+        DeviceInfo::try_from(self.get_app_configuration().await?.as_slice())
+    }
+
     /// Sign a Stellar transaction hash with the account on the Ledger device
     /// based on impl from [https://github.com/LedgerHQ/ledger-live/blob/develop/libs/ledgerjs/packages/hw-app-str/src/Str.ts#L166](https://github.com/LedgerHQ/ledger-live/blob/develop/libs/ledgerjs/packages/hw-app-str/src/Str.ts#L166)
     /// # Errors
@@ -302,7 +337,10 @@ pub fn test_network_hash() -> Hash {
 mod test {
     mod test_helpers {
         pub mod test {
-            include!("../tests/utils/mod.rs");
+            // Only the transport, speculos itself is only run by the emulator tests
+            pub(crate) mod emulator_http_transport {
+                include!("../tests/utils/emulator_http_transport.rs");
+            }
         }
     }
     use httpmock::prelude::*;
@@ -315,7 +353,7 @@ mod test {
 
     use super::xdr::{self, Operation, OperationBody, Transaction, Uint256};
 
-    use crate::{test_network_hash, Error, LedgerSigner};
+    use crate::{test_network_hash, DeviceInfo, Error, LedgerSigner};
 
     use stellar_xdr::curr::{
         Memo, MuxedAccount, PaymentOp, Preconditions, SequenceNumber, TransactionExt,
@@ -368,6 +406,38 @@ mod test {
         mock_server.assert();
     }
 
+    #[tokio::test]
+    async fn test_get_device_info() {
+        let server = MockServer::start();
+        let mock_server = server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .header("accept", "application/json")
+                .header("content-type", "application/json")
+                .json_body(json!({ "apduHex": "e006000000" }));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(json!({"data": "010500039000"}));
+        });
+        let ledger = ledger(&server);
+        let info = ledger.get_device_info().await.unwrap();
+        assert_eq!(
+            info,
+            DeviceInfo {
+                app_version: "5.0.3".to_string(),
+                hash_signing_enabled: true,
+            }
+        );
+
+        mock_server.assert();
+    }
+
+    #[test]
+    fn test_device_info_rejects_malformed_configuration() {
+        let err = DeviceInfo::try_from([0u8, 5].as_slice()).unwrap_err();
+        assert!(matches!(err, Error::UnexpectedAppConfiguration(s) if s == "0005"));
+    }
+
     #[tokio::test]
     async fn test_sign_tx() {
         let server = MockServer::start();
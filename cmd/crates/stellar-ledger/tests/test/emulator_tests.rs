@@ -1,6 +1,8 @@
 use ledger_transport::Exchange;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
+use std::fmt::Display;
+use std::future::Future;
 use std::ops::Range;
 use std::sync::Mutex;
 use std::vec;
@@ -18,10 +20,19 @@ use stellar_xdr::curr::{
 };
 
 use testcontainers::{core::ContainerPort, runners::AsyncRunner, ContainerAsync, ImageExt};
-use tokio::time::sleep;
+use tokio::time::{sleep, timeout};
 
 static PORT_RANGE: Lazy<Mutex<Range<u16>>> = Lazy::new(|| Mutex::new(40000..50000));
 
+/// How long to wait for the emulator's screen to change after a button press
+const SCREEN_CHANGE_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long to wait for the emulator to show its start screen
+const EMULATOR_START_TIMEOUT: Duration = Duration::from_secs(60);
+/// Delay between polls of the emulator's events
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Longest delay between retries of a request to the emulator's HTTP API while it starts up
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
 pub const TEST_NETWORK_PASSPHRASE: &[u8] = b"Test SDF Network ; September 2015";
 pub fn test_network_hash() -> Hash {
     use sha2::Digest;
@@ -39,14 +50,17 @@ mod test_helpers {
 }
 
 use test_case::test_case;
-use test_helpers::test::{emulator_http_transport::EmulatorHttpTransport, speculos::Speculos};
+use test_helpers::test::{
+    emulator_http_transport::EmulatorHttpTransport,
+    speculos::{LedgerModel, Speculos},
+};
 
-#[test_case("nanos".to_string() ; "when the device is NanoS")]
-#[test_case("nanox".to_string() ; "when the device is NanoX")]
-#[test_case("nanosp".to_string() ; "when the device is NanoS Plus")]
+#[test_case(LedgerModel::NanoS ; "when the device is NanoS")]
+#[test_case(LedgerModel::NanoX ; "when the device is NanoX")]
+#[test_case(LedgerModel::NanoSP ; "when the device is NanoS Plus")]
 #[tokio::test]
-async fn test_get_public_key(ledger_device_model: String) {
-    let container = get_container(ledger_device_model.clone()).await;
+async fn test_get_public_key(ledger_device_model: LedgerModel) {
+    let container = get_container(ledger_device_model).await;
     let host_port = container.get_host_port_ipv4(9998).await.unwrap();
     let ui_host_port: u16 = container.get_host_port_ipv4(5000).await.unwrap();
     wait_for_emulator_start_text(ui_host_port).await;
@@ -68,12 +82,12 @@ async fn test_get_public_key(ledger_device_model: String) {
     }
 }
 
-#[test_case("nanos".to_string() ; "when the device is NanoS")]
-#[test_case("nanox".to_string() ; "when the device is NanoX")]
-#[test_case("nanosp".to_string() ; "when the device is NanoS Plus")]
+#[test_case(LedgerModel::NanoS ; "when the device is NanoS")]
+#[test_case(LedgerModel::NanoX ; "when the device is NanoX")]
+#[test_case(LedgerModel::NanoSP ; "when the device is NanoS Plus")]
 #[tokio::test]
-async fn test_get_app_configuration(ledger_device_model: String) {
-    let container = get_container(ledger_device_model.clone()).await;
+async fn test_get_app_configuration(ledger_device_model: LedgerModel) {
+    let container = get_container(ledger_device_model).await;
     let host_port = container.get_host_port_ipv4(9998).await.unwrap();
     let ui_host_port: u16 = container.get_host_port_ipv4(5000).await.unwrap();
     wait_for_emulator_start_text(ui_host_port).await;
@@ -91,12 +105,35 @@ async fn test_get_app_configuration(ledger_device_model: String) {
     };
 }
 
-#[test_case("nanos".to_string() ; "when the device is NanoS")]
-#[test_case("nanox".to_string() ; "when the device is NanoX")]
-#[test_case("nanosp".to_string() ; "when the device is NanoS Plus")]
+#[test_case(LedgerModel::NanoS ; "when the device is NanoS")]
+#[test_case(LedgerModel::NanoX ; "when the device is NanoX")]
+#[test_case(LedgerModel::NanoSP ; "when the device is NanoS Plus")]
+#[tokio::test]
+async fn test_get_device_info(ledger_device_model: LedgerModel) {
+    let container = get_container(ledger_device_model).await;
+    let host_port = container.get_host_port_ipv4(9998).await.unwrap();
+    let ui_host_port: u16 = container.get_host_port_ipv4(5000).await.unwrap();
+    wait_for_emulator_start_text(ui_host_port).await;
+
+    let ledger = ledger(host_port).await;
+
+    match ledger.get_device_info().await {
+        Ok(info) => {
+            assert_eq!(info.app_version, "5.0.3");
+            assert!(!info.hash_signing_enabled);
+        }
+        Err(e) => {
+            panic!("Unexpected result: {e}");
+        }
+    };
+}
+
+#[test_case(LedgerModel::NanoS ; "when the device is NanoS")]
+#[test_case(LedgerModel::NanoX ; "when the device is NanoX")]
+#[test_case(LedgerModel::NanoSP ; "when the device is NanoS Plus")]
 #[tokio::test]
-async fn test_sign_tx(ledger_device_model: String) {
-    let container = get_container(ledger_device_model.clone()).await;
+async fn test_sign_tx(ledger_device_model: LedgerModel) {
+    let container = get_container(ledger_device_model).await;
     let host_port = container.get_host_port_ipv4(9998).await.unwrap();
     let ui_host_port: u16 = container.get_host_port_ipv4(5000).await.unwrap();
     wait_for_emulator_start_text(ui_host_port).await;
@@ -162,7 +199,7 @@ async fn test_sign_tx(ledger_device_model: String) {
     let approve = tokio::task::spawn(approve_tx_signature(ui_host_port, ledger_device_model));
 
     let result = sign.await.unwrap();
-    let _ = approve.await.unwrap();
+    approve.await.unwrap().unwrap();
 
     match result {
         Ok(response) => {
@@ -175,12 +212,12 @@ async fn test_sign_tx(ledger_device_model: String) {
     };
 }
 
-#[test_case("nanos".to_string() ; "when the device is NanoS")]
-#[test_case("nanox".to_string() ; "when the device is NanoX")]
-#[test_case("nanosp".to_string() ; "when the device is NanoS Plus")]
+#[test_case(LedgerModel::NanoS ; "when the device is NanoS")]
+#[test_case(LedgerModel::NanoX ; "when the device is NanoX")]
+#[test_case(LedgerModel::NanoSP ; "when the device is NanoS Plus")]
 #[tokio::test]
-async fn test_sign_tx_hash_when_hash_signing_is_not_enabled(ledger_device_model: String) {
-    let container = get_container(ledger_device_model.clone()).await;
+async fn test_sign_tx_hash_when_hash_signing_is_not_enabled(ledger_device_model: LedgerModel) {
+    let container = get_container(ledger_device_model).await;
     let host_port = container.get_host_port_ipv4(9998).await.unwrap();
     let ui_host_port: u16 = container.get_host_port_ipv4(5000).await.unwrap();
     wait_for_emulator_start_text(ui_host_port).await;
@@ -199,17 +236,17 @@ async fn test_sign_tx_hash_when_hash_signing_is_not_enabled(ledger_device_model:
     }
 }
 
-#[test_case("nanos".to_string() ; "when the device is NanoS")]
-#[test_case("nanox".to_string() ; "when the device is NanoX")]
-#[test_case("nanosp".to_string() ; "when the device is NanoS Plus")]
+#[test_case(LedgerModel::NanoS ; "when the device is NanoS")]
+#[test_case(LedgerModel::NanoX ; "when the device is NanoX")]
+#[test_case(LedgerModel::NanoSP ; "when the device is NanoS Plus")]
 #[tokio::test]
-async fn test_sign_tx_hash_when_hash_signing_is_enabled(ledger_device_model: String) {
-    let container = get_container(ledger_device_model.clone()).await;
+async fn test_sign_tx_hash_when_hash_signing_is_enabled(ledger_device_model: LedgerModel) {
+    let container = get_container(ledger_device_model).await;
     let host_port = container.get_host_port_ipv4(9998).await.unwrap();
     let ui_host_port: u16 = container.get_host_port_ipv4(5000).await.unwrap();
 
     wait_for_emulator_start_text(ui_host_port).await;
-    enable_hash_signing(ui_host_port).await;
+    enable_hash_signing(ui_host_port).await.unwrap();
 
     let ledger = Arc::new(ledger(host_port).await);
 
@@ -233,7 +270,7 @@ async fn test_sign_tx_hash_when_hash_signing_is_enabled(ledger_device_model: Str
     let approve = tokio::task::spawn(approve_tx_hash_signature(ui_host_port, ledger_device_model));
 
     let response = sign.await.unwrap();
-    let _ = approve.await.unwrap();
+    approve.await.unwrap().unwrap();
 
     match response {
         Ok(response) => {
@@ -245,45 +282,75 @@ async fn test_sign_tx_hash_when_hash_signing_is_enabled(ledger_device_model: Str
     }
 }
 
-async fn click(ui_host_port: u16, url: &str) {
-    let previous_events = get_emulator_events(ui_host_port).await;
+/// A button on the emulated device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Button {
+    Right,
+    Both,
+}
+
+impl Display for Button {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The path segment of the speculos API, e.g. `/button/right`
+        f.write_str(match self {
+            Button::Right => "right",
+            Button::Both => "both",
+        })
+    }
+}
+
+/// The emulator did not reach the expected state in time, or its HTTP API failed
+#[derive(thiserror::Error, Debug)]
+enum EmulatorError {
+    #[error("timed out: {0}")]
+    Timeout(String),
+    #[error(transparent)]
+    Http(reqwest::Error),
+}
+
+async fn click(ui_host_port: u16, button: Button) -> Result<(), EmulatorError> {
+    let previous_events = get_emulator_events(ui_host_port, SCREEN_CHANGE_TIMEOUT).await?;
 
     let client = reqwest::Client::new();
     let mut payload = HashMap::new();
     payload.insert("action", "press-and-release");
 
-    let mut screen_has_changed = false;
-
-    client
-        .post(format!("http://localhost:{ui_host_port}/{url}"))
-        .json(&payload)
-        .send()
-        .await
-        .unwrap();
-
-    while !screen_has_changed {
-        let current_events = get_emulator_events(ui_host_port).await;
+    // Only connection errors are retried, so the button is never pressed twice
+    retry(SCREEN_CHANGE_TIMEOUT, reqwest::Error::is_connect, || {
+        client
+            .post(format!("http://localhost:{ui_host_port}/button/{button}"))
+            .json(&payload)
+            .send()
+    })
+    .await?;
 
-        if !(previous_events == current_events) {
-            screen_has_changed = true
+    timeout(SCREEN_CHANGE_TIMEOUT, async {
+        while previous_events == get_emulator_events(ui_host_port, SCREEN_CHANGE_TIMEOUT).await? {
+            sleep(POLL_INTERVAL).await;
         }
-    }
+        Ok::<_, EmulatorError>(())
+    })
+    .await
+    .map_err(|_| {
+        EmulatorError::Timeout(format!("screen did not change after pressing {button}"))
+    })??;
 
     sleep(Duration::from_secs(1)).await;
+    Ok(())
 }
 
-async fn enable_hash_signing(ui_host_port: u16) {
-    click(ui_host_port, "button/right").await;
+async fn enable_hash_signing(ui_host_port: u16) -> Result<(), EmulatorError> {
+    click(ui_host_port, Button::Right).await?;
 
-    click(ui_host_port, "button/both").await;
+    click(ui_host_port, Button::Both).await?;
 
-    click(ui_host_port, "button/both").await;
+    click(ui_host_port, Button::Both).await?;
 
-    click(ui_host_port, "button/right").await;
+    click(ui_host_port, Button::Right).await?;
 
-    click(ui_host_port, "button/right").await;
+    click(ui_host_port, Button::Right).await?;
 
-    click(ui_host_port, "button/both").await;
+    click(ui_host_port, Button::Both).await
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -300,7 +367,7 @@ struct EventsResponse {
     events: Vec<EmulatorEvent>,
 }
 
-async fn get_container(ledger_device_model: String) -> ContainerAsync<Speculos> {
+async fn get_container(ledger_device_model: LedgerModel) -> ContainerAsync<Speculos> {
     let (tcp_port_1, tcp_port_2) = get_available_ports(2);
     Speculos::new(ledger_device_model)
         .with_mapped_port(tcp_port_1, ContainerPort::Tcp(9998))
@@ -353,65 +420,117 @@ async fn get_http_transport(host: &str, port: u16) -> Result<impl Exchange, Erro
 }
 
 async fn wait_for_emulator_start_text(ui_host_port: u16) {
-    let mut ready = false;
-    while !ready {
-        let events = get_emulator_events_with_retries(ui_host_port, 5).await;
-
-        if events.iter().any(|event| event.text == "is ready") {
-            ready = true;
-        }
-    }
+    wait_for_screen(ui_host_port, "is ready", EMULATOR_START_TIMEOUT)
+        .await
+        .unwrap();
 }
 
-async fn get_emulator_events(ui_host_port: u16) -> Vec<EmulatorEvent> {
-    // Allowing for less retries here because presumably the emulator should be up and running since we waited
-    // for the "is ready" text via wait_for_emulator_start_text
-    get_emulator_events_with_retries(ui_host_port, 1).await
+/// Poll the emulator's events until one of them shows `text`
+async fn wait_for_screen(
+    ui_host_port: u16,
+    text: &str,
+    wait_timeout: Duration,
+) -> Result<(), EmulatorError> {
+    timeout(wait_timeout, async {
+        while !get_emulator_events(ui_host_port, wait_timeout)
+            .await?
+            .iter()
+            .any(|event| event.text == text)
+        {
+            sleep(POLL_INTERVAL).await;
+        }
+        Ok::<_, EmulatorError>(())
+    })
+    .await
+    .map_err(|_| EmulatorError::Timeout(format!("screen did not show {text:?}")))?
 }
 
-async fn get_emulator_events_with_retries(
+/// Get the events of the emulator's screen, retrying while its HTTP API is not up yet
+async fn get_emulator_events(
     ui_host_port: u16,
-    max_retries: u16,
-) -> Vec<EmulatorEvent> {
+    wait_timeout: Duration,
+) -> Result<Vec<EmulatorEvent>, EmulatorError> {
     let client = reqwest::Client::new();
-    let mut retries = 0;
-    let mut wait_time = Duration::from_secs(1);
-    loop {
-        match client
+    let response = retry(wait_timeout, is_starting_up, || async {
+        client
             .get(format!("http://localhost:{ui_host_port}/events"))
             .send()
-            .await
-        {
-            Ok(req) => {
-                let resp = req.json::<EventsResponse>().await.unwrap();
-                return resp.events;
-            }
-            Err(e) => {
-                retries += 1;
-                if retries >= max_retries {
-                    println!("get_emulator_events_with_retries: Exceeded max retries");
-                    panic!("get_emulator_events_with_retries: Failed to get emulator events: {e}");
-                }
-                sleep(wait_time).await;
-                wait_time *= 2;
+            .await?
+            .error_for_status()
+    })
+    .await?;
+    let events = response
+        .json::<EventsResponse>()
+        .await
+        .map_err(EmulatorError::Http)?;
+    Ok(events.events)
+}
+
+/// Whether `e` is what the emulator's HTTP API answers while the container is still starting:
+/// the connection is refused until the proxy listens, and it may answer with server errors
+/// until speculos is ready behind it.
+fn is_starting_up(e: &reqwest::Error) -> bool {
+    e.is_connect() || e.status().is_some_and(|status| status.is_server_error())
+}
+
+/// Send the request made by `request` until it succeeds, retrying the `retryable` errors with
+/// exponential backoff for at most `wait_timeout`.
+async fn retry<T, Fut>(
+    wait_timeout: Duration,
+    retryable: impl Fn(&reqwest::Error) -> bool,
+    request: impl Fn() -> Fut,
+) -> Result<T, EmulatorError>
+where
+    Fut: Future<Output = Result<T, reqwest::Error>>,
+{
+    let mut last_error = None;
+    let result = timeout(wait_timeout, async {
+        let mut wait_time = POLL_INTERVAL;
+        loop {
+            match request().await {
+                Err(e) if retryable(&e) => last_error = Some(e),
+                result => return result,
             }
+            sleep(wait_time).await;
+            wait_time = (wait_time * 2).min(MAX_RETRY_INTERVAL);
         }
+    })
+    .await;
+    match result {
+        Ok(result) => result.map_err(EmulatorError::Http),
+        Err(_) => Err(EmulatorError::Timeout(format!(
+            "emulator HTTP API did not respond in {wait_timeout:?}, last error: {last_error:?}"
+        ))),
     }
 }
 
-async fn approve_tx_hash_signature(ui_host_port: u16, device_model: String) {
-    let number_of_right_clicks = if device_model == "nanos" { 10 } else { 6 };
+async fn approve_tx_hash_signature(
+    ui_host_port: u16,
+    device_model: LedgerModel,
+) -> Result<(), EmulatorError> {
+    let number_of_right_clicks = if device_model == LedgerModel::NanoS {
+        10
+    } else {
+        6
+    };
     for _ in 0..number_of_right_clicks {
-        click(ui_host_port, "button/right").await;
+        click(ui_host_port, Button::Right).await?;
     }
 
-    click(ui_host_port, "button/both").await;
+    click(ui_host_port, Button::Both).await
 }
 
-async fn approve_tx_signature(ui_host_port: u16, device_model: String) {
-    let number_of_right_clicks = if device_model == "nanos" { 17 } else { 11 };
+async fn approve_tx_signature(
+    ui_host_port: u16,
+    device_model: LedgerModel,
+) -> Result<(), EmulatorError> {
+    let number_of_right_clicks = if device_model == LedgerModel::NanoS {
+        17
+    } else {
+        11
+    };
     for _ in 0..number_of_right_clicks {
-        click(ui_host_port, "button/right").await;
+        click(ui_host_port, Button::Right).await?;
     }
-    click(ui_host_port, "button/both").await;
+    click(ui_host_port, Button::Both).await
 }
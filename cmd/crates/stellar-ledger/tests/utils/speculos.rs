@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashMap, path::PathBuf};
+use std::{borrow::Cow, collections::HashMap, fmt::Display, path::PathBuf};
 use testcontainers::{
     core::{Mount, WaitFor},
     Image,
@@ -9,7 +9,6 @@ const TAG: &str = "speculos-3a3439f6b45eca7f56395673caaf434c202e7005";
 const TEST_SEED_PHRASE: &str =
     "\"other base behind follow wet put glad muscle unlock sell income october\"";
 
-#[allow(dead_code)]
 static ENV: &Map = &Map(phf::phf_map! {
     "BOLOS_SDK"=> "/project/deps/nanos-secure-sdk",
     "BOLOS_ENV" => "/opt/bolos",
@@ -26,7 +25,42 @@ impl From<&Map> for HashMap<String, String> {
     }
 }
 
-#[derive(Debug, Default)]
+/// Ledger device models that speculos can emulate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerModel {
+    NanoS,
+    NanoSP,
+    NanoX,
+}
+
+impl LedgerModel {
+    /// The value of speculos' `-m` flag
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LedgerModel::NanoS => "nanos",
+            LedgerModel::NanoSP => "nanosp",
+            LedgerModel::NanoX => "nanox",
+        }
+    }
+
+    /// Path of the Stellar app ELF for this model inside the container
+    fn elf_path(self) -> String {
+        let elf = match self {
+            LedgerModel::NanoS => "stellarNanoSApp.elf",
+            LedgerModel::NanoSP => "stellarNanoSPApp.elf",
+            LedgerModel::NanoX => "stellarNanoXApp.elf",
+        };
+        format!("{DEFAULT_APP_PATH}/{elf}")
+    }
+}
+
+impl Display for LedgerModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug)]
 pub struct Speculos {
     env: HashMap<String, String>,
     volumes: Vec<Mount>,
@@ -35,8 +69,7 @@ pub struct Speculos {
 
 const DEFAULT_APP_PATH: &str = "/project/app/bin";
 impl Speculos {
-    #[allow(dead_code)]
-    pub fn new(ledger_device_model: String) -> Self {
+    pub fn new(model: LedgerModel) -> Self {
         #[allow(unused_mut)]
         let apps_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("tests")
@@ -46,7 +79,7 @@ impl Speculos {
             apps_dir.to_str().unwrap(),
             DEFAULT_APP_PATH,
         )];
-        let cmd = Self::get_cmd(ledger_device_model);
+        let cmd = Self::get_cmd(model);
         Speculos {
             env: ENV.into(),
             volumes,
@@ -54,15 +87,9 @@ impl Speculos {
         }
     }
 
-    fn get_cmd(ledger_device_model: String) -> String {
-        let device_model = ledger_device_model.clone();
-        let container_elf_path = match device_model.as_str() {
-            "nanos" => format!("{DEFAULT_APP_PATH}/stellarNanoSApp.elf"),
-            "nanosp" => format!("{DEFAULT_APP_PATH}/stellarNanoSPApp.elf"),
-            "nanox" => format!("{DEFAULT_APP_PATH}/stellarNanoXApp.elf"),
-            _ => panic!("Unsupported device model"),
-        };
-        format!("/home/zondax/speculos/speculos.py --log-level speculos:DEBUG --color JADE_GREEN --display headless -s {TEST_SEED_PHRASE} -m {device_model}  {container_elf_path}")
+    fn get_cmd(model: LedgerModel) -> String {
+        let container_elf_path = model.elf_path();
+        format!("/home/zondax/speculos/speculos.py --log-level speculos:DEBUG --color JADE_GREEN --display headless -s {TEST_SEED_PHRASE} -m {model}  {container_elf_path}")
     }
 }
 
@@ -92,4 +119,4 @@ impl Image for Speculos {
     fn cmd(&self) -> impl IntoIterator<Item = impl Into<std::borrow::Cow<'_, str>>> {
         vec![self.cmd.clone()].into_iter()
     }
-}
\ No newline at end of file
+}
@@ -17,13 +17,23 @@ pub async fn simulate_and_assemble_transaction(
     client: &soroban_rpc::Client,
     tx: &Transaction,
 ) -> Result<Assembled, Error> {
-    let sim_res = client
-        .simulate_transaction_envelope(&TransactionEnvelope::Tx(TransactionV1Envelope {
-            tx: tx.clone(),
-            signatures: VecM::default(),
-        }))
-        .await?;
+    let tx_env = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: tx.clone(),
+        signatures: VecM::default(),
+    });
+    crate::log::rpc::request("simulateTransaction", &tx_env);
+    let sim_res = client.simulate_transaction_envelope(&tx_env).await?;
+    crate::log::rpc::simulate_response(&sim_res);
     tracing::trace!("{sim_res:#?}");
+    assemble_simulation(tx, sim_res)
+}
+
+/// Assemble `tx` with the result of its simulation, failing with the simulation's error if it
+/// has one.
+pub fn assemble_simulation(
+    tx: &Transaction,
+    sim_res: SimulateTransactionResponse,
+) -> Result<Assembled, Error> {
     if let Some(e) = &sim_res.error {
         crate::log::event::all(&sim_res.events()?);
         Err(Error::TransactionSimulationFailed(e.clone()))
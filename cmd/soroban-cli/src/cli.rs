@@ -1,11 +1,15 @@
 use clap::CommandFactory;
 use dotenvy::dotenv;
+use serde_json::json;
 use tracing_subscriber::{fmt, EnvFilter};
 
 use crate::config::Config;
-use crate::print::Print;
+use crate::print::{self, Print};
 use crate::upgrade_check::upgrade_check;
-use crate::{commands, Root};
+use crate::{
+    commands::{self, global::ErrorFormat},
+    config, Root,
+};
 
 #[tokio::main]
 pub async fn main() {
@@ -36,17 +40,31 @@ pub async fn main() {
 
     set_env_from_config();
 
-    let mut root = Root::new().unwrap_or_else(|e| match e {
-        commands::Error::Clap(e) => {
-            let mut cmd = Root::command();
-            e.format(&mut cmd).exit();
+    let mut root = Root::new().unwrap_or_else(|e| {
+        // The arguments failed to parse, so `--error-format` is read from them directly. Help
+        // and version, which clap also returns as errors, are printed as usual.
+        let is_help = matches!(&e, commands::Error::Clap(e) if !e.use_stderr());
+        let error_format =
+            ErrorFormat::from_raw_args(std::env::args_os().filter_map(|a| a.into_string().ok()));
+        if !is_help && error_format == ErrorFormat::Json {
+            exit_with_json_error(&e);
         }
-        e => {
-            eprintln!("{e}");
-            std::process::exit(1);
+        match e {
+            commands::Error::Clap(e) => {
+                let mut cmd = Root::command();
+                e.format(&mut cmd).exit();
+            }
+            e => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
         }
     });
 
+    print::set_log_format(root.global_args.log_format);
+    print::set_no_color(root.global_args.no_color);
+    config::network::set_rpc_timeout(root.global_args.rpc_timeout());
+
     // Now use root to setup the logger
     if let Some(level) = root.global_args.log_level() {
         let mut e_filter = EnvFilter::from_default_env()
@@ -54,6 +72,9 @@ pub async fn main() {
             .add_directive(format!("stellar_cli={level}").parse().unwrap())
             .add_directive("stellar_rpc_client=off".parse().unwrap())
             .add_directive(format!("soroban_cli={level}").parse().unwrap());
+        if root.global_args.log_rpc_xdr {
+            e_filter = e_filter.add_directive(crate::log::rpc::DIRECTIVE.parse().unwrap());
+        }
 
         for filter in &root.global_args.filter_logs {
             e_filter = e_filter.add_directive(
@@ -85,12 +106,31 @@ pub async fn main() {
     });
 
     let printer = Print::new(root.global_args.quiet);
+    let error_format = root.global_args.error_format;
     if let Err(e) = root.run().await {
-        printer.errorln(format!("error: {e}"));
+        if error_format == ErrorFormat::Json {
+            exit_with_json_error(&e);
+        }
+        if printer.plain {
+            // The message is already prefixed with `error:`
+            printer.errorln(e);
+        } else {
+            printer.errorln(format!("error: {e}"));
+        }
         std::process::exit(1);
     }
 }
 
+// Write the error to stderr as a JSON object for `--error-format json`, and exit with the code of
+// the command group it comes from.
+fn exit_with_json_error(e: &commands::Error) -> ! {
+    eprintln!(
+        "{}",
+        json!({ "error": { "type": e.kind(), "message": e.to_string().trim_end() } })
+    );
+    std::process::exit(e.exit_code());
+}
+
 // Load ~/.config/stellar/config.toml defaults as env vars.
 fn set_env_from_config() {
     if let Ok(config) = Config::new() {
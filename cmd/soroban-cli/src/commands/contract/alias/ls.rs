@@ -3,15 +3,22 @@ use std::fmt::Debug;
 use std::{fs, process};
 
 use clap::{command, Parser};
+use serde::Serialize;
 
 use crate::commands::config::network;
-use crate::config::{alias, locator};
+use crate::config::{alias, locator, locator::global_config_path};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     #[command(flatten)]
     pub config_locator: locator::Args,
+
+    /// Output the aliases as a JSON array of objects with their `name`, `contract_id`,
+    /// `network_passphrase`, `network`, the name of a configured network with that passphrase if
+    /// any, and `scope`, `local` or `global`
+    #[arg(long)]
+    pub json: bool,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -30,6 +37,19 @@ pub enum Error {
 
     #[error(transparent)]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// An alias as output by `--json`, one per network it is defined for.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct Alias {
+    pub name: String,
+    pub contract_id: String,
+    pub network_passphrase: String,
+    pub network: Option<String>,
+    pub scope: &'static str,
 }
 
 #[derive(Debug, Clone)]
@@ -78,6 +98,11 @@ impl Cmd {
             }
         }
 
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&self.aliases(&map)?)?);
+            return Ok(());
+        }
+
         for network_passphrase in map.keys() {
             if let Some(list) = map.clone().get_mut(network_passphrase) {
                 println!("ℹ️ Aliases available for network '{network_passphrase}'");
@@ -101,4 +126,35 @@ impl Cmd {
 
         Ok(())
     }
+
+    /// The aliases of `map`, sorted by network passphrase and name, with the name of a
+    /// configured network for each passphrase and the scope of the config they are in.
+    fn aliases(&self, map: &HashMap<String, Vec<AliasEntry>>) -> Result<Vec<Alias>, Error> {
+        let scope = if self.config_locator.config_dir()? == global_config_path()? {
+            "global"
+        } else {
+            "local"
+        };
+        let networks = self.config_locator.list_networks_long()?;
+        let mut aliases = map
+            .iter()
+            .flat_map(|(network_passphrase, list)| {
+                let network = networks
+                    .iter()
+                    .find(|(_, network, _)| network.network_passphrase == *network_passphrase)
+                    .map(|(name, _, _)| name.clone());
+                list.iter().map(move |entry| Alias {
+                    name: entry.alias.clone(),
+                    contract_id: entry.contract.clone(),
+                    network_passphrase: network_passphrase.clone(),
+                    network: network.clone(),
+                    scope,
+                })
+            })
+            .collect::<Vec<_>>();
+        aliases.sort_by(|a, b| {
+            (&a.network_passphrase, &a.name).cmp(&(&b.network_passphrase, &b.name))
+        });
+        Ok(aliases)
+    }
 }
@@ -3,9 +3,9 @@ use std::convert::TryInto;
 use std::env;
 use std::ffi::OsString;
 use std::fmt::Debug;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use clap::value_parser;
+use clap::{parser::ValueSource, value_parser};
 use ed25519_dalek::SigningKey;
 use heck::ToKebabCase;
 
@@ -18,6 +18,8 @@ use crate::config::{
     self,
     sc_address::{self, UnresolvedScAddress},
 };
+use crate::output::{self, Output};
+use crate::spec_help;
 use soroban_spec_tools::Spec;
 
 #[derive(thiserror::Error, Debug)]
@@ -27,11 +29,8 @@ pub enum Error {
         arg: String,
         error: soroban_spec_tools::Error,
     },
-    #[error("cannot print result {result:?}: {error}")]
-    CannotPrintResult {
-        result: ScVal,
-        error: soroban_spec_tools::Error,
-    },
+    #[error(transparent)]
+    Output(#[from] output::Error),
     #[error("function {0} was not found in the contract")]
     FunctionNotFoundInContractSpec(String),
     #[error("function name {0} is too long")]
@@ -44,8 +43,14 @@ pub enum Error {
     StrVal(#[from] soroban_spec_tools::Error),
     #[error("Missing argument {0}")]
     MissingArgument(String),
-    #[error("")]
+    #[error("cannot read argument file {0:?}")]
     MissingFileArg(PathBuf),
+    #[error("`--arg-file` {0}: expected the form `<arg_name>=<path>`")]
+    InvalidArgFile(String),
+    #[error("`--arg-file` names argument {0}, which function {1} doesn't have")]
+    UnknownArgFile(String, String),
+    #[error("argument {0} is provided more than once")]
+    DuplicateArg(String),
     #[error(transparent)]
     ScAddress(#[from] sc_address::Error),
     #[error(transparent)]
@@ -90,6 +95,7 @@ pub fn build_host_function_parameters(
     };
 
     let func = spec.find_function(function)?;
+    let mut arg_files = arg_files(matches_, func)?;
     // create parsed_args in same order as the inputs to func
     let mut signers: Vec<SigningKey> = vec![];
     let parsed_args = func
@@ -97,13 +103,26 @@ pub fn build_host_function_parameters(
         .iter()
         .map(|i| {
             let name = i.name.to_utf8_string()?;
-            if let Some(mut val) = matches_.get_raw(&name) {
+            let arg_file = arg_files.remove(&name);
+            // Default values, e.g. of booleans, don't prevent reading the
+            // argument from a file.
+            let is_default = matches_.value_source(&name) == Some(ValueSource::DefaultValue);
+            if let Some(mut val) = matches_
+                .get_raw(&name)
+                .filter(|_| !(is_default && arg_file.is_some()))
+            {
+                if arg_file.is_some() {
+                    return Err(Error::DuplicateArg(name));
+                }
                 let mut s = val
                     .next()
                     .unwrap()
                     .to_string_lossy()
                     .trim_matches('"')
                     .to_string();
+                if let Some(path) = s.strip_prefix('@').filter(|_| reads_at_path(&i.type_)) {
+                    return read_arg_file(&spec, name, Path::new(path), &i.type_);
+                }
                 if matches!(i.type_, ScSpecTypeDef::Address) {
                     let addr = resolve_address(&s, config)?;
                     let signer = resolve_signer(&s, config);
@@ -114,29 +133,16 @@ pub fn build_host_function_parameters(
                 }
                 spec.from_string(&s, &i.type_)
                     .map_err(|error| Error::CannotParseArg { arg: name, error })
+            } else if let Some(arg_path) = matches_
+                .get_one::<PathBuf>(&fmt_arg_file_name(&name))
+                .or(arg_file.as_ref())
+            {
+                if matches_.contains_id(&fmt_arg_file_name(&name)) && arg_file.is_some() {
+                    return Err(Error::DuplicateArg(name));
+                }
+                read_arg_file(&spec, name, arg_path, &i.type_)
             } else if matches!(i.type_, ScSpecTypeDef::Option(_)) {
                 Ok(ScVal::Void)
-            } else if let Some(arg_path) = matches_.get_one::<PathBuf>(&fmt_arg_file_name(&name)) {
-                if matches!(i.type_, ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_)) {
-                    Ok(ScVal::try_from(
-                        &std::fs::read(arg_path)
-                            .map_err(|_| Error::MissingFileArg(arg_path.clone()))?,
-                    )
-                    .map_err(|()| Error::CannotParseArg {
-                        arg: name.clone(),
-                        error: soroban_spec_tools::Error::Unknown,
-                    })?)
-                } else {
-                    let file_contents = std::fs::read_to_string(arg_path)
-                        .map_err(|_| Error::MissingFileArg(arg_path.clone()))?;
-                    tracing::debug!(
-                        "file {arg_path:?}, has contents:\n{file_contents}\nAnd type {:#?}\n{}",
-                        i.type_,
-                        file_contents.len()
-                    );
-                    spec.from_string(&file_contents, &i.type_)
-                        .map_err(|error| Error::CannotParseArg { arg: name, error })
-                }
             } else {
                 Err(Error::MissingArgument(name))
             }
@@ -166,17 +172,90 @@ pub fn build_host_function_parameters(
     Ok((function.clone(), spec, invoke_args, signers))
 }
 
+/// Name of the flag that reads an argument from a file, e.g. `--arg-file strukt=strukt.json`.
+const ARG_FILE: &str = "arg-file";
+
+// The files passed with `--arg-file <arg_name>=<path>`, by argument name.
+fn arg_files(
+    matches: &clap::ArgMatches,
+    func: &ScSpecFunctionV0,
+) -> Result<HashMap<String, PathBuf>, Error> {
+    let mut files = HashMap::new();
+    let Ok(Some(values)) = matches.try_get_many::<String>(ARG_FILE) else {
+        return Ok(files);
+    };
+    for value in values {
+        let (name, path) = value
+            .split_once('=')
+            .filter(|(name, path)| !name.is_empty() && !path.is_empty())
+            .ok_or_else(|| Error::InvalidArgFile(value.clone()))?;
+        let input = func.inputs.iter().find(|i| {
+            let input_name = i.name.to_utf8_string_lossy();
+            input_name == name || input_name.to_kebab_case() == name
+        });
+        let Some(input) = input else {
+            return Err(Error::UnknownArgFile(
+                name.to_string(),
+                func.name.to_utf8_string_lossy(),
+            ));
+        };
+        if files
+            .insert(input.name.to_utf8_string_lossy(), PathBuf::from(path))
+            .is_some()
+        {
+            return Err(Error::DuplicateArg(name.to_string()));
+        }
+    }
+    Ok(files)
+}
+
+// Whether an inline value of the form `@path` is read from the file at `path`.
+// Strings and symbols are taken as is, as they can start with `@`, and
+// addresses never do. An option is read like the type it wraps.
+fn reads_at_path(type_: &ScSpecTypeDef) -> bool {
+    match type_ {
+        ScSpecTypeDef::Option(option) => reads_at_path(&option.value_type),
+        ScSpecTypeDef::String | ScSpecTypeDef::Symbol | ScSpecTypeDef::Address => false,
+        _ => true,
+    }
+}
+
+// Read an argument from a file, as raw bytes for `Bytes` and `BytesN`, or
+// options of them, and as JSON, like inline arguments, for every other type.
+fn read_arg_file(
+    spec: &Spec,
+    name: String,
+    path: &Path,
+    type_: &ScSpecTypeDef,
+) -> Result<ScVal, Error> {
+    let value_type = match type_ {
+        ScSpecTypeDef::Option(option) => &*option.value_type,
+        type_ => type_,
+    };
+    if matches!(value_type, ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_)) {
+        let bytes = std::fs::read(path).map_err(|_| Error::MissingFileArg(path.to_path_buf()))?;
+        ScVal::try_from(&bytes).map_err(|()| Error::CannotParseArg {
+            arg: name,
+            error: soroban_spec_tools::Error::Unknown,
+        })
+    } else {
+        let file_contents =
+            std::fs::read_to_string(path).map_err(|_| Error::MissingFileArg(path.to_path_buf()))?;
+        tracing::debug!(
+            "file {path:?}, has contents:\n{file_contents}\nAnd type {type_:#?}\n{}",
+            file_contents.len()
+        );
+        spec.from_string(&file_contents, type_)
+            .map_err(|error| Error::CannotParseArg { arg: name, error })
+    }
+}
+
 fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
     let func = spec
         .find_function(name)
         .map_err(|_| Error::FunctionNotFoundInContractSpec(name.to_string()))?;
+    let args = spec_help::function_args(spec, name)?;
 
-    // Parse the function arguments
-    let inputs_map = &func
-        .inputs
-        .iter()
-        .map(|i| (i.name.to_utf8_string().unwrap(), i.type_.clone()))
-        .collect::<HashMap<String, ScSpecTypeDef>>();
     let name: &'static str = Box::leak(name.to_string().into_boxed_str());
     let mut cmd = clap::Command::new(name)
         .no_binary_name(true)
@@ -190,16 +269,23 @@ fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
     let long_doc: &'static str = Box::leak(arg_file_help(doc).into_boxed_str());
 
     cmd = cmd.about(Some(doc)).long_about(long_doc);
-    for (name, type_) in inputs_map {
-        let mut arg = clap::Arg::new(name);
-        let file_arg_name = fmt_arg_file_name(name);
+    for spec_help::ArgHelp {
+        name,
+        type_,
+        value_name,
+        doc,
+        ..
+    } in args
+    {
+        let mut arg = clap::Arg::new(&name);
+        let file_arg_name = fmt_arg_file_name(&name);
         let mut file_arg = clap::Arg::new(&file_arg_name);
         arg = arg
-            .long(name)
+            .long(&name)
             .alias(name.to_kebab_case())
             .num_args(1)
             .value_parser(clap::builder::NonEmptyStringValueParser::new())
-            .long_help(spec.doc(name, type_)?);
+            .long_help(doc);
 
         file_arg = file_arg
             .long(&file_arg_name)
@@ -207,9 +293,9 @@ fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
             .num_args(1)
             .hide(true)
             .value_parser(value_parser!(PathBuf))
-            .conflicts_with(name);
+            .conflicts_with(&name);
 
-        if let Some(value_name) = spec.arg_value_name(type_, 0) {
+        if let Some(value_name) = value_name {
             let value_name: &'static str = Box::leak(value_name.into_boxed_str());
             arg = arg.value_name(value_name);
         }
@@ -231,6 +317,17 @@ fn build_custom_cmd(name: &str, spec: &Spec) -> Result<clap::Command, Error> {
         cmd = cmd.arg(arg);
         cmd = cmd.arg(file_arg);
     }
+    // A contract argument named like the flag takes precedence over it.
+    if cmd.get_arguments().all(|a| a.get_long() != Some(ARG_FILE)) {
+        cmd = cmd.arg(
+            clap::Arg::new(ARG_FILE)
+                .long(ARG_FILE)
+                .value_name("ARG_NAME=PATH")
+                .action(clap::ArgAction::Append)
+                .num_args(1)
+                .help("Read an argument from a file, e.g. `--arg-file strukt=strukt.json`"),
+        );
+    }
     Ok(cmd)
 }
 
@@ -243,26 +340,24 @@ fn arg_file_help(docs: &str) -> String {
         r#"{docs}
 Usage Notes:
 Each arg has a corresponding --<arg_name>-file-path which is a path to a file containing the corresponding JSON argument.
+Args can also be read from files with --arg-file <arg_name>=<path>, or by passing @<path> as their value, except for String, Symbol and Address args.
 Note: The only types which aren't JSON are Bytes and BytesN, which are raw bytes"#
     )
 }
 
+/// Render the result `res` of `function` in the `output` format. JSON results of functions
+/// without a return type are empty.
 pub fn output_to_string(
     spec: &Spec,
     res: &ScVal,
     function: &str,
+    output: Output,
 ) -> Result<TxnResult<String>, Error> {
-    let mut res_str = String::new();
-    if let Some(output) = spec.find_function(function)?.outputs.first() {
-        res_str = spec
-            .xdr_to_json(res, output)
-            .map_err(|e| Error::CannotPrintResult {
-                result: res.clone(),
-                error: e,
-            })?
-            .to_string();
+    let type_ = spec.find_function(function)?.outputs.first();
+    if type_.is_none() && output != Output::Xdr {
+        return Ok(TxnResult::Res(String::new()));
     }
-    Ok(TxnResult::Res(res_str))
+    Ok(TxnResult::Res(output::render(res, spec, type_, output)?))
 }
 
 fn resolve_address(addr_or_alias: &str, config: &config::Args) -> Result<String, Error> {
@@ -289,3 +384,29 @@ fn resolve_signer(addr_or_alias: &str, config: &config::Args) -> Option<SigningK
         .ok()
         .map(|pk| SigningKey::from_bytes(&pk.0))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::{ScSpecTypeOption, ScString};
+
+    #[test]
+    fn optional_string_starting_with_at_is_taken_as_is() {
+        let type_ = ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+            value_type: Box::new(ScSpecTypeDef::String),
+        }));
+        assert!(!reads_at_path(&type_));
+        assert_eq!(
+            Spec::default().from_string("@alice", &type_).unwrap(),
+            ScVal::String(ScString("@alice".try_into().unwrap()))
+        );
+    }
+
+    #[test]
+    fn optional_bytes_starting_with_at_are_read_from_the_file() {
+        let type_ = ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+            value_type: Box::new(ScSpecTypeDef::Bytes),
+        }));
+        assert!(reads_at_path(&type_));
+    }
+}
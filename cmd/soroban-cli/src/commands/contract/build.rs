@@ -1,6 +1,7 @@
-use cargo_metadata::{Metadata, MetadataCommand, Package};
+use cargo_metadata::{camino::Utf8PathBuf, Metadata, MetadataCommand, Package};
 use clap::Parser;
 use itertools::Itertools;
+use sha2::{Digest, Sha256};
 use std::{
     borrow::Cow,
     collections::HashSet,
@@ -25,6 +26,9 @@ use crate::{commands::global, print::Print};
 /// In workspaces builds all crates unless a package name is specified, or the
 /// command is executed from the sub-directory of a workspace crate.
 ///
+/// To build an explicit list of crates instead, without discovering the members
+/// of a workspace, list their manifests in a file passed with --manifests-file.
+///
 /// To view the commands that will be executed, without executing them, use the
 /// --print-commands-only option.
 #[derive(Parser, Debug, Clone)]
@@ -32,6 +36,12 @@ pub struct Cmd {
     /// Path to Cargo.toml
     #[arg(long)]
     pub manifest_path: Option<std::path::PathBuf>,
+    /// File listing the paths to the Cargo.toml of each crate to build, one per line
+    ///
+    /// Relative paths are relative to the directory of the file. Empty lines and
+    /// lines starting with `#` are ignored. Crates are built in the order listed.
+    #[arg(long, conflicts_with_all = ["manifest_path", "package"])]
+    pub manifests_file: Option<std::path::PathBuf>,
     /// Package to build
     ///
     /// If omitted, all packages that build for crate-type cdylib are built.
@@ -68,6 +78,34 @@ pub struct Cmd {
     /// Add key-value to contract meta (adds the meta to the `contractmetav0` custom section)
     #[arg(long, num_args=1, value_parser=parse_meta_arg, action=clap::ArgAction::Append, help_heading = "Metadata")]
     pub meta: Vec<(String, String)>,
+    /// File of key-values to add to contract meta, one `key=value` per line
+    ///
+    /// Empty lines and lines starting with `#` are ignored. Values set with
+    /// --meta override the values of the same keys in the file.
+    #[arg(long, help_heading = "Metadata")]
+    pub meta_file: Option<PathBuf>,
+    /// Fail unless the sha256 hash of the built wasm, as hex, is this hash
+    ///
+    /// For a rebuild to reproduce a wasm, it must be built from the same source
+    /// with the same Rust toolchain version, the same wasm32 target, the same
+    /// dependency versions (commit the Cargo.lock), and the same profile,
+    /// features and meta. Only one package can be built when verifying.
+    #[arg(
+        long,
+        value_name = "SHA256",
+        value_parser = parse_sha256,
+        conflicts_with = "print_commands_only",
+        help_heading = "Other"
+    )]
+    pub verify_reproducible: Option<String>,
+}
+
+fn parse_sha256(s: &str) -> Result<String, Error> {
+    if s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(s.to_ascii_lowercase())
+    } else {
+        Err(Error::InvalidSha256(s.to_string()))
+    }
 }
 
 fn parse_meta_arg(s: &str) -> Result<(String, String), Error> {
@@ -78,9 +116,35 @@ fn parse_meta_arg(s: &str) -> Result<(String, String), Error> {
         .next_tuple()
         .ok_or_else(|| Error::MetaArg("must be in the form 'key=value'".to_string()))?;
 
+    if key.is_empty() {
+        return Err(Error::MetaArg("key must not be empty".to_string()));
+    }
+
     Ok((key.to_string(), value.to_string()))
 }
 
+/// Parse the `key=value` lines of a meta file, skipping empty lines and comments.
+fn parse_meta_file(contents: &str) -> Result<Vec<(String, String)>, Error> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            parse_meta_arg(line).map_err(|e| Error::MetaFileLine(line_number, Box::new(e)))
+        })
+        .collect()
+}
+
+/// Merge the meta entries of a meta file with those of --meta, the latter overriding the
+/// former on the same key.
+fn merge_meta(file: Vec<(String, String)>, args: &[(String, String)]) -> Vec<(String, String)> {
+    file.into_iter()
+        .filter(|(key, _)| !args.iter().any(|(k, _)| k == key))
+        .chain(args.iter().cloned())
+        .collect()
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
@@ -107,6 +171,28 @@ pub enum Error {
     WritingWasmFile(io::Error),
     #[error("invalid meta entry: {0}")]
     MetaArg(String),
+    #[error("reading meta file {}: {1}", .0.display())]
+    ReadingMetaFile(PathBuf, io::Error),
+    #[error("meta file {} is not valid UTF-8", .0.display())]
+    MetaFileNotUtf8(PathBuf),
+    #[error("line {0} of meta file: {1}")]
+    MetaFileLine(usize, Box<Error>),
+    #[error("expected a sha256 hash as 64 hex characters, got {0:?}")]
+    InvalidSha256(String),
+    #[error("--verify-reproducible requires building exactly one package, found {0}")]
+    VerifyingPackages(usize),
+    #[error(
+        "wasm hash {actual} does not match the expected hash {expected}, check that the source, \
+        Rust toolchain, wasm32 target, Cargo.lock, profile, features and meta are the same as \
+        for the reference build"
+    )]
+    NotReproducible { expected: String, actual: String },
+    #[error("reading manifests file {}: {1}", .0.display())]
+    ReadingManifestsFile(PathBuf, io::Error),
+    #[error("manifest {} does not exist", .0.display())]
+    ManifestNotFound(PathBuf),
+    #[error("manifest {} is not the manifest of a package", .0.display())]
+    NotAPackageManifest(PathBuf),
 }
 
 const WASM_TARGET: &str = "wasm32-unknown-unknown";
@@ -118,19 +204,33 @@ impl Cmd {
 
         let working_dir = env::current_dir().map_err(Error::GettingCurrentDir)?;
 
-        let metadata = self.metadata()?;
-        let packages = self.packages(&metadata)?;
-        let target_dir = &metadata.target_directory;
+        let meta = self.meta()?;
 
-        if let Some(package) = &self.package {
-            if packages.is_empty() {
-                return Err(Error::PackageNotFound {
-                    package: package.clone(),
-                });
+        let packages = if let Some(manifests_file) = &self.manifests_file {
+            listed_packages(manifests_file)?
+        } else {
+            let metadata = self.metadata()?;
+            let packages = self.packages(&metadata)?;
+
+            if let Some(package) = &self.package {
+                if packages.is_empty() {
+                    return Err(Error::PackageNotFound {
+                        package: package.clone(),
+                    });
+                }
             }
+
+            packages
+                .into_iter()
+                .map(|p| (p, metadata.target_directory.clone()))
+                .collect()
+        };
+
+        if self.verify_reproducible.is_some() && packages.len() != 1 {
+            return Err(Error::VerifyingPackages(packages.len()));
         }
 
-        for p in packages {
+        for (p, target_dir) in packages {
             let mut cmd = Command::new("cargo");
             cmd.stdout(Stdio::piped());
             cmd.arg("rustc");
@@ -192,17 +292,21 @@ impl Cmd {
                 }
 
                 let file = format!("{}.wasm", p.name.replace('-', "_"));
-                let target_file_path = Path::new(target_dir)
+                let target_file_path = Path::new(&target_dir)
                     .join(WASM_TARGET)
                     .join(&self.profile)
                     .join(&file);
 
-                self.handle_contract_metadata_args(&target_file_path)?;
+                Self::handle_contract_metadata_args(&target_file_path, &meta)?;
 
                 if let Some(out_dir) = &self.out_dir {
                     fs::create_dir_all(out_dir).map_err(Error::CreatingOutDir)?;
                     let out_file_path = Path::new(out_dir).join(&file);
-                    fs::copy(target_file_path, out_file_path).map_err(Error::CopyingWasmFile)?;
+                    fs::copy(&target_file_path, out_file_path).map_err(Error::CopyingWasmFile)?;
+                }
+
+                if let Some(expected) = &self.verify_reproducible {
+                    verify_reproducible(&print, &target_file_path, expected)?;
                 }
             }
         }
@@ -277,14 +381,29 @@ impl Cmd {
         cmd.exec()
     }
 
-    fn handle_contract_metadata_args(&self, target_file_path: &PathBuf) -> Result<(), Error> {
-        if self.meta.is_empty() {
+    /// The meta entries of --meta-file merged with those of --meta.
+    fn meta(&self) -> Result<Vec<(String, String)>, Error> {
+        let Some(meta_file) = &self.meta_file else {
+            return Ok(self.meta.clone());
+        };
+        let bytes =
+            fs::read(meta_file).map_err(|e| Error::ReadingMetaFile(meta_file.clone(), e))?;
+        let contents =
+            String::from_utf8(bytes).map_err(|_| Error::MetaFileNotUtf8(meta_file.clone()))?;
+        Ok(merge_meta(parse_meta_file(&contents)?, &self.meta))
+    }
+
+    fn handle_contract_metadata_args(
+        target_file_path: &PathBuf,
+        meta: &[(String, String)],
+    ) -> Result<(), Error> {
+        if meta.is_empty() {
             return Ok(());
         }
 
         let mut wasm_bytes = fs::read(target_file_path).map_err(Error::ReadingWasmFile)?;
 
-        for (k, v) in self.meta.clone() {
+        for (k, v) in meta {
             let key: StringM = k
                 .clone()
                 .try_into()
@@ -306,6 +425,56 @@ impl Cmd {
     }
 }
 
+/// Check that the sha256 hash of the wasm at `wasm_path` is `expected`.
+fn verify_reproducible(print: &Print, wasm_path: &Path, expected: &str) -> Result<(), Error> {
+    let wasm = fs::read(wasm_path).map_err(Error::ReadingWasmFile)?;
+    let actual = hex::encode(Sha256::digest(wasm));
+    if actual != expected {
+        return Err(Error::NotReproducible {
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    print.checkln(format!("Wasm hash {actual} matches the expected hash"));
+    Ok(())
+}
+
+/// The packages of the manifests listed in `manifests_file`, each with the target
+/// directory of its workspace.
+fn listed_packages(manifests_file: &Path) -> Result<Vec<(Package, Utf8PathBuf)>, Error> {
+    let contents = fs::read_to_string(manifests_file)
+        .map_err(|e| Error::ReadingManifestsFile(manifests_file.to_path_buf(), e))?;
+    let base_dir = manifests_file.parent().unwrap_or(Path::new(""));
+    parse_manifests_file(&contents)
+        .map(|manifest_path| {
+            let manifest_path =
+                path::absolute(base_dir.join(manifest_path)).map_err(Error::AbsolutePath)?;
+            if !manifest_path.is_file() {
+                return Err(Error::ManifestNotFound(manifest_path));
+            }
+            let metadata = MetadataCommand::new()
+                .no_deps()
+                .manifest_path(&manifest_path)
+                .exec()?;
+            let package = metadata
+                .packages
+                .into_iter()
+                .find(|p| p.manifest_path == manifest_path)
+                .ok_or(Error::NotAPackageManifest(manifest_path))?;
+            Ok((package, metadata.target_directory))
+        })
+        .collect()
+}
+
+/// The manifest paths listed in the contents of a manifests file, skipping empty
+/// lines and comments.
+fn parse_manifests_file(contents: &str) -> impl Iterator<Item = &str> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
 /// Configure cargo/rustc to replace absolute paths in panic messages / debuginfo
 /// with relative paths.
 ///
@@ -403,3 +572,56 @@ fn get_rustflags() -> Option<Vec<String>> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, val: &str) -> (String, String) {
+        (key.to_string(), val.to_string())
+    }
+
+    #[test]
+    fn meta_file() {
+        let contents = "# comment\nname = my contract\n\nurl=https://x.y/?a=b\n";
+        assert_eq!(
+            parse_meta_file(contents).unwrap(),
+            [
+                entry("name", "my contract"),
+                entry("url", "https://x.y/?a=b")
+            ]
+        );
+    }
+
+    #[test]
+    fn meta_file_invalid_lines() {
+        assert!(matches!(
+            parse_meta_file("a=b\nnot a pair"),
+            Err(Error::MetaFileLine(2, _))
+        ));
+        assert!(matches!(
+            parse_meta_file("\n=value"),
+            Err(Error::MetaFileLine(2, _))
+        ));
+    }
+
+    #[test]
+    fn sha256() {
+        let hash = "AB".repeat(32);
+        assert_eq!(parse_sha256(&hash).unwrap(), "ab".repeat(32));
+        assert!(matches!(parse_sha256("ab"), Err(Error::InvalidSha256(_))));
+        assert!(matches!(
+            parse_sha256(&"zz".repeat(32)),
+            Err(Error::InvalidSha256(_))
+        ));
+    }
+
+    #[test]
+    fn meta_args_override_meta_file() {
+        let file = vec![entry("a", "1"), entry("b", "2")];
+        assert_eq!(
+            merge_meta(file, &[entry("b", "3"), entry("c", "4")]),
+            [entry("a", "1"), entry("b", "3"), entry("c", "4")]
+        );
+    }
+}
@@ -19,7 +19,7 @@ use crate::{
     },
     config::{self, data, network},
     rpc::Error as SorobanRpcError,
-    tx::builder,
+    tx::builder::{self, memo},
     utils::contract_id_hash_from_asset,
 };
 
@@ -68,6 +68,9 @@ pub struct Cmd {
     #[command(flatten)]
     pub fee: crate::fee::Args,
 
+    #[command(flatten)]
+    pub memo: memo::Args,
+
     /// The alias that will be used to save the assets's id.
     /// Whenever used, `--alias` will always overwrite the existing contract id
     /// configuration without asking for confirmation.
@@ -124,39 +127,46 @@ impl NetworkRunnable for Cmd {
         let asset = self.asset.resolve(&config.locator)?;
 
         let network = config.get_network()?;
-        let client = network.rpc_client()?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
+        let client = network
+            .checked_rpc_client(config.network.rpc_options())
             .await?;
+        network
+            .with_rpc_timeout(client.verify_network_passphrase(Some(&network.network_passphrase)))
+            .await??;
         let source_account = config.source_account()?;
         // Get the account sequence number
         // TODO: use symbols for the method names (both here and in serve)
-        let account_details = client
-            .get_account(&source_account.clone().to_string())
-            .await?;
+        let account_details = network
+            .with_rpc_timeout(client.get_account(&source_account.clone().to_string()))
+            .await??;
         let sequence: i64 = account_details.seq_num.into();
         let network_passphrase = &network.network_passphrase;
         let contract_id = contract_id_hash_from_asset(&asset, network_passphrase);
-        let tx = build_wrap_token_tx(
+        let tx = self.memo.add_to(build_wrap_token_tx(
             asset,
             &contract_id,
             sequence + 1,
             self.fee.fee,
             network_passphrase,
             source_account,
-        )?;
+        )?);
         if self.fee.build_only {
             return Ok(TxnResult::Txn(Box::new(tx)));
         }
-        let txn = simulate_and_assemble_transaction(&client, &tx).await?;
+        let txn = network
+            .with_rpc_timeout(simulate_and_assemble_transaction(&client, &tx))
+            .await??;
         let txn = self.fee.apply_to_assembled_txn(txn).transaction().clone();
         if self.fee.sim_only {
             return Ok(TxnResult::Txn(Box::new(txn)));
         }
-        let get_txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(txn).await?)
-            .await?
-            .try_into()?;
+        let signed_txn = self.config.sign_with_local_key(txn).await?;
+        crate::log::rpc::request("sendTransaction", &signed_txn);
+        let txn_resp = network
+            .with_rpc_timeout(client.send_transaction_polling(&signed_txn))
+            .await??;
+        crate::log::rpc::transaction_response(&txn_resp);
+        let get_txn_resp = txn_resp.try_into()?;
         if args.map_or(true, |a| !a.no_cache) {
             data::write(get_txn_resp, &network.rpc_uri()?)?;
         }
@@ -27,6 +27,7 @@ use crate::{
     config::{self, data, locator, network},
     print::Print,
     rpc,
+    tx::builder::memo,
     utils::{self, rpc::get_remote_wasm_from_hash},
     wasm,
 };
@@ -57,6 +58,8 @@ pub struct Cmd {
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
+    #[command(flatten)]
+    pub memo: memo::Args,
     #[arg(long, short = 'i', default_value = "false")]
     /// Whether to ignore safety checks when deploying contracts
     pub ignore_checks: bool,
@@ -174,7 +177,8 @@ impl NetworkRunnable for Cmd {
         global_args: Option<&global::Args>,
         config: Option<&config::Args>,
     ) -> Result<TxnResult<stellar_strkey::Contract>, Error> {
-        let print = Print::new(global_args.map_or(false, |a| a.quiet));
+        let print = Print::new(global_args.map_or(false, |a| a.quiet))
+            .with_explorer_url(global_args.and_then(|a| a.explorer_url.clone()));
         let config = config.unwrap_or(&self.config);
         let wasm_hash = if let Some(wasm) = &self.wasm {
             let hash = if self.fee.build_only || self.fee.sim_only {
@@ -184,6 +188,7 @@ impl NetworkRunnable for Cmd {
                     wasm: wasm::Args { wasm: wasm.clone() },
                     config: config.clone(),
                     fee: self.fee.clone(),
+                    memo: self.memo.clone(),
                     ignore_checks: self.ignore_checks,
                 }
                 .run_against_rpc_server(global_args, Some(config))
@@ -219,10 +224,12 @@ impl NetworkRunnable for Cmd {
             None => rand::thread_rng().gen::<[u8; 32]>(),
         };
 
-        let client = network.rpc_client()?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
+        let client = network
+            .checked_rpc_client(config.network.rpc_options())
             .await?;
+        network
+            .with_rpc_timeout(client.verify_network_passphrase(Some(&network.network_passphrase)))
+            .await??;
 
         let MuxedAccount::Ed25519(bytes) = config.source_account()? else {
             return Err(Error::OnlyEd25519AccountsAllowed);
@@ -237,7 +244,9 @@ impl NetworkRunnable for Cmd {
         let raw_wasm = if let Some(wasm) = self.wasm.as_ref() {
             wasm::Args { wasm: wasm.clone() }.read()?
         } else {
-            get_remote_wasm_from_hash(&client, &wasm_hash).await?
+            network
+                .with_rpc_timeout(get_remote_wasm_from_hash(&client, &wasm_hash))
+                .await??
         };
         let entries = soroban_spec_tools::contract::Spec::new(&raw_wasm)?.spec;
         let res = soroban_spec_tools::Spec::new(entries.clone());
@@ -261,17 +270,16 @@ impl NetworkRunnable for Cmd {
             None
         };
 
-        // Get the account sequence number
-        let account_details = client.get_account(&source_account.to_string()).await?;
-        let sequence: i64 = account_details.seq_num.into();
-        let txn = Box::new(build_create_contract_tx(
+        let sequence = network.next_sequence_number(source_account.clone()).await?;
+        let txn = build_create_contract_tx(
             wasm_hash,
-            sequence + 1,
+            sequence.0,
             self.fee.fee,
             source_account,
             contract_id_preimage,
             constructor_params.as_ref(),
-        )?);
+        )?;
+        let txn = Box::new(self.memo.add_to(txn));
 
         if self.fee.build_only {
             print.checkln("Transaction built!");
@@ -280,7 +288,9 @@ impl NetworkRunnable for Cmd {
 
         print.infoln("Simulating deploy transaction…");
 
-        let txn = simulate_and_assemble_transaction(&client, &txn).await?;
+        let txn = network
+            .with_rpc_timeout(simulate_and_assemble_transaction(&client, &txn))
+            .await??;
         let txn = Box::new(self.fee.apply_to_assembled_txn(txn).transaction().clone());
 
         if self.fee.sim_only {
@@ -292,16 +302,20 @@ impl NetworkRunnable for Cmd {
         let signed_txn = &config.sign_with_local_key(*txn).await?;
         print.globeln("Submitting deploy transaction…");
 
-        let get_txn_resp = client
-            .send_transaction_polling(signed_txn)
-            .await?
-            .try_into()?;
+        crate::log::rpc::request("sendTransaction", signed_txn);
+        let txn_resp = network
+            .with_rpc_timeout(client.send_transaction_polling(signed_txn))
+            .await??;
+        crate::log::rpc::transaction_response(&txn_resp);
+        let get_txn_resp = txn_resp.try_into()?;
 
         if global_args.map_or(true, |a| !a.no_cache) {
             data::write(get_txn_resp, &network.rpc_uri()?)?;
         }
 
-        if let Some(url) = utils::explorer_url_for_contract(&network, &contract_id) {
+        if let Some(url) =
+            utils::explorer_url_for_contract(&network, print.explorer_url.as_ref(), &contract_id)
+        {
             print.linkln(url);
         }
 
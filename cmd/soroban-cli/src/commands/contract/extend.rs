@@ -134,14 +134,16 @@ impl NetworkRunnable for Cmd {
         let network = config.get_network()?;
         tracing::trace!(?network);
         let keys = self.key.parse_keys(&config.locator, &network)?;
-        let client = network.rpc_client()?;
+        let client = network
+            .checked_rpc_client(config.network.rpc_options())
+            .await?;
         let source_account = config.source_account()?;
         let extend_to = self.ledgers_to_extend();
 
         // Get the account sequence number
-        let account_details = client
-            .get_account(&source_account.clone().to_string())
-            .await?;
+        let account_details = network
+            .with_rpc_timeout(client.get_account(&source_account.clone().to_string()))
+            .await??;
         let sequence: i64 = account_details.seq_num.into();
 
         let tx = Box::new(Transaction {
@@ -175,13 +177,17 @@ impl NetworkRunnable for Cmd {
         if self.fee.build_only {
             return Ok(TxnResult::Txn(tx));
         }
-        let tx = simulate_and_assemble_transaction(&client, &tx)
-            .await?
+        let tx = network
+            .with_rpc_timeout(simulate_and_assemble_transaction(&client, &tx))
+            .await??
             .transaction()
             .clone();
-        let res = client
-            .send_transaction_polling(&config.sign_with_local_key(tx).await?)
-            .await?;
+        let signed_tx = config.sign_with_local_key(tx).await?;
+        crate::log::rpc::request("sendTransaction", &signed_tx);
+        let res = network
+            .with_rpc_timeout(client.send_transaction_polling(&signed_tx))
+            .await??;
+        crate::log::rpc::transaction_response(&res);
         if args.map_or(true, |a| !a.no_cache) {
             data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
         }
@@ -206,7 +212,9 @@ impl NetworkRunnable for Cmd {
         }
 
         if operations[0].changes.is_empty() {
-            let entry = client.get_full_ledger_entries(&keys).await?;
+            let entry = network
+                .with_rpc_timeout(client.get_full_ledger_entries(&keys))
+                .await??;
             let extension = entry.entries[0].live_until_ledger_seq;
             if entry.latest_ledger + i64::from(extend_to) < i64::from(extension) {
                 return Ok(TxnResult::Res(extension));
@@ -111,11 +111,14 @@ impl NetworkRunnable for Cmd {
         config: Option<&config::Args>,
     ) -> Result<Vec<u8>, Error> {
         let network = config.map_or_else(|| self.network(), |c| Ok(c.get_network()?))?;
+        let options =
+            config.map_or_else(|| self.network.rpc_options(), |c| c.network.rpc_options());
         Ok(wasm::fetch_from_contract(
             &self
                 .contract_id
                 .resolve_contract_id(&self.locator, &network.network_passphrase)?,
             &network,
+            options,
         )
         .await?)
     }
@@ -1,3 +1,5 @@
+use crate::commands::global;
+
 pub mod asset;
 pub mod wasm;
 
@@ -18,10 +20,10 @@ pub enum Error {
 }
 
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match &self {
             Cmd::Asset(asset) => asset.run()?,
-            Cmd::Wasm(wasm) => wasm.run()?,
+            Cmd::Wasm(wasm) => wasm.run(global_args)?,
         }
         Ok(())
     }
@@ -1,14 +1,15 @@
 use clap::{arg, command, Parser};
 
 use crate::config;
-
 use crate::tx::builder;
-use crate::utils::contract_id_hash_from_asset;
+use crate::xdr::ContractIdPreimage;
+
+use super::wasm::{self, get_contract_id};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
-    /// ID of the Stellar classic asset to wrap, e.g. "USDC:G...5"
+    /// ID of the Stellar classic asset to wrap, e.g. "USDC:G...5", or "native"/"XLM" for lumens
     #[arg(long)]
     pub asset: builder::Asset,
 
@@ -23,6 +24,8 @@ pub enum Error {
     Xdr(#[from] crate::xdr::Error),
     #[error(transparent)]
     Asset(#[from] builder::asset::Error),
+    #[error(transparent)]
+    Wasm(#[from] wasm::Error),
 }
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
@@ -32,10 +35,7 @@ impl Cmd {
 
     pub fn contract_address(&self) -> Result<stellar_strkey::Contract, Error> {
         let network = self.config.get_network()?;
-        let contract_id = contract_id_hash_from_asset(
-            &self.asset.resolve(&self.config.locator)?,
-            &network.network_passphrase,
-        );
-        Ok(stellar_strkey::Contract(contract_id.0))
+        let preimage = ContractIdPreimage::Asset(self.asset.resolve(&self.config.locator)?);
+        Ok(get_contract_id(preimage, &network.network_passphrase)?)
     }
 }
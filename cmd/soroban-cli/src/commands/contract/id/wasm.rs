@@ -5,14 +5,19 @@ use crate::xdr::{
 use clap::{arg, command, Parser};
 use sha2::{Digest, Sha256};
 
-use crate::config;
+use crate::{commands::global, config, print::Print};
 
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
     /// ID of the Soroban contract
-    #[arg(long)]
-    pub salt: String,
+    #[arg(long, required_unless_present = "salt_from")]
+    pub salt: Option<String>,
+
+    /// Derive the salt from a human readable label as `sha256(label)`, so that the same label
+    /// always yields the same contract id
+    #[arg(long, conflicts_with = "salt")]
+    pub salt_from: Option<String>,
 
     #[command(flatten)]
     pub config: config::Args,
@@ -29,15 +34,18 @@ pub enum Error {
     OnlyEd25519AccountsAllowed,
 }
 impl Cmd {
-    pub fn run(&self) -> Result<(), Error> {
-        let salt: [u8; 32] = soroban_spec_tools::utils::padded_hex_from_str(&self.salt, 32)
-            .map_err(|_| Error::CannotParseSalt(self.salt.clone()))?
-            .try_into()
-            .map_err(|_| Error::CannotParseSalt(self.salt.clone()))?;
-        let source_account = match self.config.source_account()? {
-            xdr::MuxedAccount::Ed25519(uint256) => stellar_strkey::ed25519::PublicKey(uint256.0),
-            xdr::MuxedAccount::MuxedEd25519(_) => return Err(Error::OnlyEd25519AccountsAllowed),
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let salt = self.salt()?;
+        if let Some(label) = &self.salt_from {
+            Print::new(global_args.quiet).infoln(format!(
+                "Using salt {} derived from {label:?}",
+                hex::encode(salt)
+            ));
+        }
+        let xdr::MuxedAccount::Ed25519(key) = self.config.source_account()? else {
+            return Err(Error::OnlyEd25519AccountsAllowed);
         };
+        let source_account = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(key)));
         let contract_id_preimage = contract_preimage(&source_account, salt);
         let contract_id = get_contract_id(
             contract_id_preimage.clone(),
@@ -46,15 +54,27 @@ impl Cmd {
         println!("{contract_id}");
         Ok(())
     }
+
+    fn salt(&self) -> Result<[u8; 32], Error> {
+        if let Some(label) = &self.salt_from {
+            return Ok(salt_from_label(label));
+        }
+        let salt = self.salt.as_deref().unwrap_or_default();
+        soroban_spec_tools::utils::padded_hex_from_str(salt, 32)
+            .map_err(|_| Error::CannotParseSalt(salt.to_string()))?
+            .try_into()
+            .map_err(|_| Error::CannotParseSalt(salt.to_string()))
+    }
+}
+
+/// Salt for `--salt-from`: the sha256 digest of `label`, which is exactly 32 bytes.
+pub fn salt_from_label(label: &str) -> [u8; 32] {
+    Sha256::digest(label.as_bytes()).into()
 }
 
-pub fn contract_preimage(
-    key: &stellar_strkey::ed25519::PublicKey,
-    salt: [u8; 32],
-) -> ContractIdPreimage {
-    let source_account = AccountId(PublicKey::PublicKeyTypeEd25519(key.0.into()));
+pub fn contract_preimage(address: &ScAddress, salt: [u8; 32]) -> ContractIdPreimage {
     ContractIdPreimage::Address(ContractIdPreimageFromAddress {
-        address: ScAddress::Account(source_account),
+        address: address.clone(),
         salt: Uint256(salt),
     })
 }
@@ -73,3 +93,51 @@ pub fn get_contract_id(
         Sha256::digest(preimage_xdr).into(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contract_id_from_account_and_salt() {
+        let key = stellar_strkey::ed25519::PublicKey::from_string(
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+        )
+        .unwrap();
+        let address =
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key.0))));
+        let contract_id = get_contract_id(
+            contract_preimage(&address, [1; 32]),
+            "Test SDF Network ; September 2015",
+        )
+        .unwrap();
+        assert_eq!(
+            contract_id.to_string(),
+            "CD3RZVKIPK3HBLDJD4TOQNBFA6JZMI6DQ2HDYM22ZAYGPAH7DOADPYUD"
+        );
+    }
+
+    #[test]
+    fn contract_id_from_account_and_salt_label() {
+        let key = stellar_strkey::ed25519::PublicKey::from_string(
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+        )
+        .unwrap();
+        let address =
+            ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(key.0))));
+        let salt = salt_from_label("my-token-v1");
+        assert_eq!(
+            hex::encode(salt),
+            "2240ecbffabcf96b3402f985d0126755d05e72ccf94c47df56e28a0a7f4cdb61"
+        );
+        let contract_id = get_contract_id(
+            contract_preimage(&address, salt),
+            "Test SDF Network ; September 2015",
+        )
+        .unwrap();
+        assert_eq!(
+            contract_id.to_string(),
+            "CACXPK72A62VUFUL2LH4X22TROMILRRNVJA5SQPG4TWKFYDIUS3ME72L"
+        );
+    }
+}
@@ -1,11 +1,15 @@
 use std::fmt::Debug;
 
-use crate::commands::global;
+use crate::{commands::global, print::Print};
 
 pub mod env_meta;
+pub mod example_args;
 pub mod interface;
+pub mod ls;
 pub mod meta;
 pub mod shared;
+pub mod spec;
+pub mod validate;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
@@ -49,6 +53,43 @@ pub enum Cmd {
     ///
     /// Outputs no data when no data is present in the contract.
     EnvMeta(env_meta::Cmd),
+
+    /// Output the full spec of a contract as JSON.
+    ///
+    /// The spec lists every function with its argument names and types, as
+    /// well as the user defined types and error enums of the contract. It is
+    /// intended to be consumed by code generation tools.
+    ///
+    /// The data outputted by this command is a stream of `SCSpecEntry` XDR values.
+    /// See the type definitions in [stellar-xdr](https://github.com/stellar/stellar-xdr).
+    /// [See also XDR data format](https://developers.stellar.org/docs/learn/encyclopedia/data-format/xdr).
+    Spec(spec::Cmd),
+
+    /// Output a JSON object with a placeholder value for each argument of a function.
+    ///
+    /// Each argument is mapped to a value of its type to be filled in, e.g.
+    /// `0` for numbers, `""` for strings and symbols, `[]` for vectors, and
+    /// the first case of enums. Once filled in, the values can be passed to
+    /// `contract invoke` with `--arg-file`.
+    ExampleArgs(example_args::Cmd),
+
+    /// Output the names of the functions of a contract, one per line and sorted.
+    ///
+    /// Functions with reserved names starting with `__`, such as
+    /// `__constructor`, are left out as they can't be invoked. Fails when
+    /// the contract has no functions.
+    Ls(ls::Cmd),
+
+    /// Check that a wasm file is a contract that can be deployed and invoked.
+    ///
+    /// Checks that the wasm is a wasm32 module, that it has the
+    /// `contractenvmetav0` and `contractspecv0` custom sections added by the
+    /// soroban-sdk, and that it exports at least one of the functions in its
+    /// spec, which is not the case when it isn't built as a `cdylib`.
+    ///
+    /// The same checks run before `contract upload` and `contract deploy`
+    /// unless `--ignore-checks` is passed.
+    Validate(validate::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -59,6 +100,16 @@ pub enum Error {
     Meta(#[from] meta::Error),
     #[error(transparent)]
     EnvMeta(#[from] env_meta::Error),
+    #[error(transparent)]
+    Spec(#[from] spec::Error),
+    #[error(transparent)]
+    ExampleArgs(#[from] example_args::Error),
+    #[error(transparent)]
+    Ls(#[from] ls::Error),
+    #[error(transparent)]
+    Validate(#[from] validate::Error),
+    #[error(transparent)]
+    Out(#[from] shared::Error),
 }
 
 impl Cmd {
@@ -67,8 +118,33 @@ impl Cmd {
             Cmd::Interface(interface) => interface.run(global_args).await?,
             Cmd::Meta(meta) => meta.run(global_args).await?,
             Cmd::EnvMeta(env_meta) => env_meta.run(global_args).await?,
+            Cmd::Spec(spec) => spec.run(global_args).await?,
+            Cmd::ExampleArgs(example_args) => example_args.run(global_args).await?,
+            Cmd::Ls(ls) => ls.run(global_args).await?,
+            Cmd::Validate(validate) => validate.run(global_args).await?,
+        };
+        let written = match self.out() {
+            Some((out, file_name)) => {
+                out.write(&file_name, &result, &Print::new(global_args.quiet))?
+            }
+            None => false,
         };
-        println!("{result}");
+        if !written {
+            println!("{result}");
+        }
         Ok(())
     }
+
+    /// Where to write the output of the command with `--out-dir`, and the name of the file.
+    fn out(&self) -> Option<(&shared::Out, String)> {
+        match &self {
+            Cmd::Interface(cmd) => {
+                Some((&cmd.out, format!("interface.{}", cmd.output.extension())))
+            }
+            Cmd::Meta(cmd) => Some((&cmd.out, format!("meta.{}", cmd.output.extension()))),
+            Cmd::EnvMeta(cmd) => Some((&cmd.out, format!("env-meta.{}", cmd.output.extension()))),
+            Cmd::Spec(cmd) => Some((&cmd.out, format!("spec.{}", cmd.output.extension()))),
+            Cmd::ExampleArgs(_) | Cmd::Ls(_) | Cmd::Validate(_) => None,
+        }
+    }
 }
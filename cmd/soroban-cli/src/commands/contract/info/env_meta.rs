@@ -24,6 +24,8 @@ pub struct Cmd {
     /// Format of the output
     #[arg(long, default_value = "text")]
     pub output: MetasInfoOutput,
+    #[command(flatten)]
+    pub out: shared::Out,
 }
 
 #[derive(thiserror::Error, Debug)]
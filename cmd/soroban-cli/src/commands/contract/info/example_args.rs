@@ -0,0 +1,34 @@
+use std::fmt::Debug;
+
+use clap::{command, Parser};
+
+use crate::commands::{
+    contract::info::{shared, spec},
+    global,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct Cmd {
+    /// Name of the function to generate the arguments for
+    pub function: String,
+    #[command(flatten)]
+    pub common: shared::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Spec(#[from] spec::Error),
+    #[error(transparent)]
+    SpecTools(#[from] soroban_spec_tools::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<String, Error> {
+        let entries = spec::entries(&self.common, global_args).await?;
+        let args = soroban_spec_tools::Spec::new(entries).example_args(&self.function)?;
+        Ok(serde_json::to_string_pretty(&args)?)
+    }
+}
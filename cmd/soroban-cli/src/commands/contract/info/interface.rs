@@ -4,7 +4,9 @@ use crate::commands::contract::info::interface::Error::NoInterfacePresent;
 use crate::commands::contract::info::shared::{self, fetch, Fetched};
 use crate::commands::global;
 use crate::print::Print;
+use crate::spec_help;
 use clap::{command, Parser};
+use itertools::Itertools;
 use soroban_spec_rust::ToFormattedString;
 use soroban_spec_tools::contract;
 use soroban_spec_tools::contract::Spec;
@@ -16,6 +18,8 @@ pub struct Cmd {
     /// Format of the output
     #[arg(long, default_value = "rust")]
     pub output: InfoOutput,
+    #[command(flatten)]
+    pub out: shared::Out,
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum, Default)]
@@ -29,6 +33,24 @@ pub enum InfoOutput {
     Json,
     /// Formatted (multiline) JSON output of the info entry
     JsonFormatted,
+    /// One line per function with its arguments and return type, with types named as in the
+    /// help of `contract invoke`
+    Signatures,
+    /// The arguments of each function with their types and docs, as shown in the help of
+    /// `contract invoke -- <function> --help`
+    Args,
+}
+
+impl InfoOutput {
+    /// Extension of the file written with `--out-dir`
+    pub fn extension(self) -> &'static str {
+        match self {
+            InfoOutput::Rust => "rs",
+            InfoOutput::XdrBase64 => "xdr",
+            InfoOutput::Json | InfoOutput::JsonFormatted => "json",
+            InfoOutput::Signatures | InfoOutput::Args => "txt",
+        }
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -41,6 +63,8 @@ pub enum Error {
     NoInterfacePresent(),
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    SpecTools(#[from] soroban_spec_tools::Error),
 }
 
 impl Cmd {
@@ -70,6 +94,28 @@ impl Cmd {
             InfoOutput::Rust => soroban_spec_rust::generate_without_file(&spec)
                 .to_formatted_string()
                 .expect("Unexpected spec format error"),
+            InfoOutput::Signatures => {
+                let spec = soroban_spec_tools::Spec::new(spec);
+                let signatures = spec
+                    .find_functions()?
+                    .map(|function| spec.function_signature(function))
+                    .join("\n");
+                signatures
+            }
+            InfoOutput::Args => {
+                let functions = soroban_spec_tools::Spec::new(spec.clone())
+                    .find_functions()?
+                    .map(|function| function.name.to_utf8_string_lossy())
+                    .collect::<Vec<_>>();
+                functions
+                    .iter()
+                    .map(|name| {
+                        let args = spec_help::render_args(spec.clone(), name)?;
+                        Ok(format!("{name}\n{args}"))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .join("\n")
+            }
         };
 
         Ok(res)
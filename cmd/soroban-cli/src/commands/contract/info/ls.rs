@@ -0,0 +1,98 @@
+use std::fmt::Debug;
+
+use clap::{command, Parser};
+
+use crate::{
+    commands::{
+        contract::info::{shared, spec},
+        global,
+    },
+    xdr::{ScSpecEntry, ScSpecFunctionV0},
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub common: shared::Args,
+    /// Output the names as a JSON array instead of one per line
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Spec(#[from] spec::Error),
+    #[error("the contract spec has no functions, e.g. because the contract only holds data")]
+    NoFunctions,
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<String, Error> {
+        let names = function_names(&spec::entries(&self.common, global_args).await?)?;
+        Ok(if self.json {
+            serde_json::to_string(&names)?
+        } else {
+            names.join("\n")
+        })
+    }
+}
+
+/// Sorted names of the functions in `entries` that can be invoked, leaving out the reserved
+/// ones starting with `__`, e.g. `__constructor`.
+pub fn function_names(entries: &[ScSpecEntry]) -> Result<Vec<String>, Error> {
+    let mut names = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::FunctionV0(ScSpecFunctionV0 { name, .. }) => {
+                Some(name.to_utf8_string_lossy())
+            }
+            _ => None,
+        })
+        .filter(|name| !name.starts_with("__"))
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        return Err(Error::NoFunctions);
+    }
+    names.sort();
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xdr::{ScSpecUdtStructV0, VecM};
+
+    use super::*;
+
+    fn function(name: &str) -> ScSpecEntry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: "".try_into().unwrap(),
+            name: name.try_into().unwrap(),
+            inputs: VecM::default(),
+            outputs: VecM::default(),
+        })
+    }
+
+    #[test]
+    fn sorted_without_reserved() {
+        let entries = [
+            function("hello"),
+            function("__constructor"),
+            function("add"),
+        ];
+        assert_eq!(function_names(&entries).unwrap(), ["add", "hello"]);
+    }
+
+    #[test]
+    fn no_functions() {
+        let entries = [ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            doc: "".try_into().unwrap(),
+            lib: "".try_into().unwrap(),
+            name: "Data".try_into().unwrap(),
+            fields: VecM::default(),
+        })];
+        assert!(matches!(function_names(&entries), Err(Error::NoFunctions)));
+    }
+}
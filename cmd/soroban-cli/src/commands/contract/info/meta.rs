@@ -16,6 +16,8 @@ pub struct Cmd {
     /// Format of the output
     #[arg(long, default_value = "text")]
     pub output: MetasInfoOutput,
+    #[command(flatten)]
+    pub out: shared::Out,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -35,11 +37,16 @@ pub enum Error {
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<String, Error> {
         let print = Print::new(global_args.quiet);
-        let Fetched { contract, .. } = fetch(&self.common, &print).await?;
+        let Fetched { contract, source } = fetch(&self.common, &print).await?;
 
         let spec = match contract {
             shared::Contract::Wasm { wasm_bytes } => Spec::new(&wasm_bytes)?,
-            shared::Contract::StellarAssetContract => return Err(NoSACMeta()),
+            shared::Contract::StellarAssetContract => {
+                let Some(asset) = source.stellar_asset_name().await? else {
+                    return Err(NoSACMeta());
+                };
+                return stellar_asset_meta(&asset, self.output);
+            }
         };
 
         let Some(meta_base64) = spec.meta_base64 else {
@@ -76,3 +83,46 @@ impl Cmd {
         Ok(res)
     }
 }
+
+/// Stellar Asset Contracts are built into the network and have no WASM to read meta from, so
+/// describe the contract and the asset it wraps instead.
+fn stellar_asset_meta(asset: &str, output: MetasInfoOutput) -> Result<String, Error> {
+    let meta = serde_json::json!({
+        "type": "stellar_asset_contract",
+        "asset": asset,
+    });
+    Ok(match output {
+        MetasInfoOutput::XdrBase64 => return Err(NoSACMeta()),
+        MetasInfoOutput::Json => serde_json::to_string(&meta)?,
+        MetasInfoOutput::JsonFormatted => serde_json::to_string_pretty(&meta)?,
+        MetasInfoOutput::Text => {
+            format!("Contract meta:\n • type: Stellar Asset Contract\n • asset: {asset}\n")
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASSET: &str = "USDC:GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI";
+
+    #[test]
+    fn stellar_asset_contract_meta() {
+        assert_eq!(
+            stellar_asset_meta(ASSET, MetasInfoOutput::Text).unwrap(),
+            format!("Contract meta:\n • type: Stellar Asset Contract\n • asset: {ASSET}\n")
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&stellar_asset_meta(ASSET, MetasInfoOutput::Json).unwrap())
+                .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "stellar_asset_contract", "asset": ASSET })
+        );
+        assert!(matches!(
+            stellar_asset_meta(ASSET, MetasInfoOutput::XdrBase64),
+            Err(NoSACMeta())
+        ));
+    }
+}
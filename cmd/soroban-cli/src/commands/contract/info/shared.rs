@@ -7,9 +7,10 @@ use crate::{
     config::{
         self, locator,
         network::{self, Network},
+        sc_address, UnresolvedScAddress,
     },
     print::Print,
-    utils::rpc::get_remote_wasm_from_hash,
+    utils::{get_name_from_stellar_asset_contract_storage, rpc::get_remote_wasm_from_hash},
     wasm::{self, Error::ContractIsStellarAsset},
     xdr,
 };
@@ -54,6 +55,37 @@ pub struct Args {
     pub locator: locator::Args,
 }
 
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Out {
+    /// Directory to write the output to instead of stdout, in a file named after the command
+    /// and the output format, e.g. `meta.json`
+    #[arg(long)]
+    pub out_dir: Option<PathBuf>,
+    /// Replace the file in `--out-dir` if it already exists
+    #[arg(long, requires = "out_dir")]
+    pub overwrite: bool,
+}
+
+impl Out {
+    /// Write `content` to `file_name` under `--out-dir`. Returns `false` without writing
+    /// anything when no directory was given.
+    pub fn write(&self, file_name: &str, content: &str, print: &Print) -> Result<bool, Error> {
+        let Some(out_dir) = &self.out_dir else {
+            return Ok(false);
+        };
+        let path = out_dir.join(file_name);
+        if path.exists() && !self.overwrite {
+            return Err(Error::OutFileExists(path));
+        }
+        std::fs::create_dir_all(out_dir).map_err(|e| Error::WriteOutFile(path.clone(), e))?;
+        std::fs::write(&path, format!("{content}\n"))
+            .map_err(|e| Error::WriteOutFile(path.clone(), e))?;
+        print.saveln(format!("Wrote {}", path.display()));
+        Ok(true)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum, Default)]
 pub enum MetasInfoOutput {
     /// Text output of the meta info entry
@@ -67,6 +99,17 @@ pub enum MetasInfoOutput {
     JsonFormatted,
 }
 
+impl MetasInfoOutput {
+    /// Extension of the file written with `--out-dir`
+    pub fn extension(self) -> &'static str {
+        match self {
+            MetasInfoOutput::Text => "txt",
+            MetasInfoOutput::XdrBase64 => "xdr",
+            MetasInfoOutput::Json | MetasInfoOutput::JsonFormatted => "json",
+        }
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
@@ -81,6 +124,12 @@ pub enum Error {
     Rpc(#[from] soroban_rpc::Error),
     #[error(transparent)]
     Locator(#[from] locator::Error),
+    #[error(transparent)]
+    ScAddress(#[from] sc_address::Error),
+    #[error("{0:?} already exists, use --overwrite to replace it")]
+    OutFileExists(PathBuf),
+    #[error("writing {0:?}: {1}")]
+    WriteOutFile(PathBuf, std::io::Error),
 }
 
 pub struct Fetched {
@@ -116,6 +165,34 @@ impl Source {
             }
         }
     }
+
+    /// The asset (`CODE:ISSUER` or `native`) wrapped by a Stellar Asset Contract, read from the
+    /// contract instance storage. Returns `None` if the source is not a deployed contract or the
+    /// asset cannot be determined.
+    pub async fn stellar_asset_name(&self) -> Result<Option<String>, Error> {
+        let Source::Contract {
+            resolved_address,
+            network,
+        } = self
+        else {
+            return Ok(None);
+        };
+        let Ok(contract) = stellar_strkey::Contract::from_string(resolved_address) else {
+            return Ok(None);
+        };
+        let client = network.rpc_client()?;
+        let entry = network
+            .with_rpc_timeout(client.get_contract_data(&contract.0))
+            .await??;
+        let xdr::ScVal::ContractInstance(xdr::ScContractInstance {
+            executable: xdr::ContractExecutable::StellarAsset,
+            storage: Some(storage),
+        }) = &entry.val
+        else {
+            return Ok(None);
+        };
+        Ok(get_name_from_stellar_asset_contract_storage(storage))
+    }
 }
 
 pub async fn fetch(args: &Args, print: &Print) -> Result<Fetched, Error> {
@@ -142,16 +219,20 @@ pub async fn fetch(args: &Args, print: &Print) -> Result<Fetched, Error> {
 
         let hash = xdr::Hash(hash);
 
-        let client = network.rpc_client()?;
-
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
+        let client = network
+            .checked_rpc_client(args.network.rpc_options())
             .await?;
 
+        network
+            .with_rpc_timeout(client.verify_network_passphrase(Some(&network.network_passphrase)))
+            .await??;
+
         print.globeln(format!(
             "Downloading contract spec for wasm hash: {wasm_hash}"
         ));
-        let wasm_bytes = get_remote_wasm_from_hash(&client, &hash).await?;
+        let wasm_bytes = network
+            .with_rpc_timeout(get_remote_wasm_from_hash(&client, &hash))
+            .await??;
         Ok(Fetched {
             contract: Contract::Wasm { wasm_bytes },
             source: Source::Wasm {
@@ -160,11 +241,12 @@ pub async fn fetch(args: &Args, print: &Print) -> Result<Fetched, Error> {
             },
         })
     } else if let Some(contract_id) = &args.contract_id {
-        let contract_id =
-            contract_id.resolve_contract_id(&args.locator, &network.network_passphrase)?;
+        let contract_id = UnresolvedScAddress::from(contract_id)
+            .resolve_contract(&args.locator, &network.network_passphrase)?;
         let derived_address = xdr::ScAddress::Contract(xdr::Hash(contract_id.0)).to_string();
         print.globeln(format!("Downloading contract spec: {derived_address}"));
-        let res = wasm::fetch_from_contract(&contract_id, network).await;
+        let res =
+            wasm::fetch_from_contract(&contract_id, network, args.network.rpc_options()).await;
         if let Some(ContractIsStellarAsset) = res.as_ref().err() {
             return Ok(Fetched {
                 contract: Contract::StellarAssetContract,
@@ -0,0 +1,130 @@
+use std::fmt::Debug;
+
+use clap::{command, Parser};
+use soroban_spec_tools::contract::{self, Spec};
+
+use crate::{
+    commands::{
+        contract::info::{
+            shared::{self, fetch, Fetched},
+            spec::Error::NoSpecPresent,
+        },
+        global,
+    },
+    config::{locator, network, sc_address, UnresolvedScAddress},
+    get_spec::{self, get_remote_contract_spec},
+    print::Print,
+    xdr::{self, Limits, ScSpecEntry, WriteXdr},
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub common: shared::Args,
+    /// Format of the output
+    #[arg(long, default_value = "json-formatted")]
+    pub output: SpecInfoOutput,
+    #[command(flatten)]
+    pub out: shared::Out,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum, Default)]
+pub enum SpecInfoOutput {
+    /// XDR output of the spec entries
+    XdrBase64,
+    /// JSON output of the spec entries (one line, not formatted)
+    Json,
+    /// Formatted (multiline) JSON output of the spec entries
+    #[default]
+    JsonFormatted,
+}
+
+impl SpecInfoOutput {
+    /// Extension of the file written with `--out-dir`
+    pub fn extension(self) -> &'static str {
+        match self {
+            SpecInfoOutput::XdrBase64 => "xdr",
+            SpecInfoOutput::Json | SpecInfoOutput::JsonFormatted => "json",
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] shared::Error),
+    #[error(transparent)]
+    Spec(#[from] contract::Error),
+    #[error(transparent)]
+    GetSpec(#[from] get_spec::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    ScAddress(#[from] sc_address::Error),
+    #[error("no spec present in provided WASM file")]
+    NoSpecPresent(),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<String, Error> {
+        let spec = entries(&self.common, global_args).await?;
+
+        if spec.is_empty() {
+            return Err(NoSpecPresent());
+        }
+
+        let res = match self.output {
+            SpecInfoOutput::XdrBase64 => spec_to_base64(&spec)?,
+            SpecInfoOutput::Json => serde_json::to_string(&spec)?,
+            SpecInfoOutput::JsonFormatted => serde_json::to_string_pretty(&spec)?,
+        };
+
+        Ok(res)
+    }
+}
+
+/// The spec entries of the contract selected by `common`, downloaded with its contract id or
+/// read from its wasm.
+pub async fn entries(
+    common: &shared::Args,
+    global_args: &global::Args,
+) -> Result<Vec<ScSpecEntry>, Error> {
+    let print = Print::new(global_args.quiet);
+
+    Ok(if let Some(contract_id) = &common.contract_id {
+        let network = common.network.get(&common.locator)?;
+        let contract_id = UnresolvedScAddress::from(contract_id)
+            .resolve_contract(&common.locator, &network.network_passphrase)?;
+        print.globeln(format!("Downloading contract spec: {contract_id}"));
+        get_remote_contract_spec(
+            &contract_id.0,
+            &common.locator,
+            &common.network,
+            Some(global_args),
+            None,
+        )
+        .await?
+    } else {
+        let Fetched { contract, .. } = fetch(common, &print).await?;
+        match contract {
+            shared::Contract::Wasm { wasm_bytes } => Spec::new(&wasm_bytes)?.spec,
+            shared::Contract::StellarAssetContract => {
+                Spec::spec_to_base64(&soroban_sdk::token::StellarAssetSpec::spec_xdr())?.1
+            }
+        }
+    })
+}
+
+fn spec_to_base64(spec: &[ScSpecEntry]) -> Result<String, Error> {
+    let mut bytes = Vec::new();
+    for entry in spec {
+        bytes.extend(entry.to_xdr(Limits::none())?);
+    }
+    Ok(Spec::spec_to_base64(&bytes)?.0)
+}
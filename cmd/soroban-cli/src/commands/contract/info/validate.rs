@@ -0,0 +1,51 @@
+use std::fmt::Debug;
+
+use clap::{command, Parser};
+
+use soroban_spec_tools::contract::{self, Spec};
+
+use crate::{
+    commands::{
+        contract::info::shared::{self, fetch, Fetched},
+        global,
+    },
+    print::Print,
+    xdr::ScSpecEntry,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub common: shared::Args,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wasm(#[from] shared::Error),
+    #[error(transparent)]
+    Spec(#[from] contract::Error),
+    #[error("Stellar asset contract is built into the network and has no wasm to validate")]
+    NoSACValidation(),
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<String, Error> {
+        let print = Print::new(global_args.quiet);
+        let Fetched { contract, .. } = fetch(&self.common, &print).await?;
+
+        let spec = match contract {
+            shared::Contract::Wasm { wasm_bytes } => Spec::validate(&wasm_bytes)?,
+            shared::Contract::StellarAssetContract => return Err(Error::NoSACValidation()),
+        };
+
+        let functions = spec
+            .spec
+            .iter()
+            .filter(|entry| matches!(entry, ScSpecEntry::FunctionV0(_)))
+            .count();
+        Ok(format!(
+            "Valid contract wasm with {functions} contract functions"
+        ))
+    }
+}
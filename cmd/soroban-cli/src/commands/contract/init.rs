@@ -1,13 +1,15 @@
 use std::borrow::Cow;
 use std::{
-    fs::{create_dir_all, metadata, write, Metadata},
+    fs::{copy, create_dir_all, metadata, read_dir, read_to_string, write, Metadata},
     io,
     path::{Path, PathBuf},
+    process::Command,
     str,
 };
 
 use clap::Parser;
 use rust_embed::RustEmbed;
+use toml_edit::{DocumentMut, InlineTable, Item, Value};
 
 use crate::{commands::global, error_on_use_of_removed_arg, print, utils};
 
@@ -17,6 +19,23 @@ const FRONTEND_EXAMPLE_REMOVAL_NOTICE: &str = "Using frontend template via cli i
 supported. You can search for frontend templates using github tags, \
 such as `soroban-template` or `soroban-frontend-template`";
 
+// Paths in a cloned contract template that are not copied into the new contract.
+const TEMPLATE_EXCLUDED_PATHS: &[&str] = &[".git", ".github", "target", "Cargo.lock"];
+
+// Keys of a dependency that specify where it comes from, which are replaced by
+// `workspace = true`. Other keys such as `features` are kept.
+const DEPENDENCY_SOURCE_KEYS: &[&str] =
+    &["version", "path", "git", "branch", "tag", "rev", "registry"];
+
+// Rust keywords, which cargo doesn't accept as package names.
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
 pub struct Cmd {
@@ -25,7 +44,11 @@ pub struct Cmd {
     #[arg(
         long,
         default_value = "hello-world",
-        long_help = "An optional flag to specify a new contract's name."
+        value_parser = crate_name_validator,
+        long_help = "An optional flag to specify a new contract's name. It names the contract's \
+        directory under `contracts/` and its package in `Cargo.toml`, and must be a valid crate \
+        name: letters, digits, `-` and `_`, starting with a letter or `_`. The project directory \
+        is not renamed."
     )]
     pub name: String,
 
@@ -48,8 +71,32 @@ pub struct Cmd {
     )]
     pub frontend_template: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "GIT_URL",
+        long_help = "Git URL of a contract template to scaffold the contract from, instead of \
+        the built-in template. The repository root must be a contract crate."
+    )]
+    pub template: Option<String>,
+
     #[arg(long, long_help = "Overwrite all existing files.")]
     pub overwrite: bool,
+
+    #[arg(
+        long,
+        long_help = "After writing the project, run `cargo metadata` in it to check that the \
+        workspace and its dependencies resolve, failing if they don't. Requires network access \
+        unless the dependencies are already in the cargo cache."
+    )]
+    pub check: bool,
+
+    #[arg(
+        long,
+        long_help = "After writing the project, initialize a git repository in it and commit the \
+        generated files. Skipped with a warning if the project is already inside a git \
+        repository."
+    )]
+    pub git: bool,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -72,6 +119,21 @@ pub enum Error {
     #[error("provided project path exists and is not a cargo workspace root directory. Hint: run init on an empty or non-existing directory"
     )]
     PathExistsNotCargoProject,
+
+    #[error("failed to clone template {0}: git exited with {1}")]
+    GitClone(String, std::process::ExitStatus),
+
+    #[error("failed to run `git {0}` in {1:?}: git exited with {2}")]
+    Git(String, PathBuf, std::process::ExitStatus),
+
+    #[error("parsing contract Cargo.toml: {0}")]
+    ParseCargoToml(#[from] toml_edit::TomlError),
+
+    #[error("workspace at {0:?} does not resolve: {1}")]
+    Check(PathBuf, cargo_metadata::Error),
+
+    #[error("invalid contract name {0:?}: {1}")]
+    InvalidName(String, &'static str),
 }
 
 impl Cmd {
@@ -118,15 +180,142 @@ impl Runner {
             .infoln(format!("Initializing contract at {contract_path:?}"));
 
         Self::create_dir_all(contract_path.as_path())?;
-        self.copy_template_files(
-            contract_path.as_path(),
-            &mut ContractTemplateFiles::iter(),
-            ContractTemplateFiles::get,
-        )?;
+        if let Some(template) = &self.args.template {
+            let template_dir = tempfile::tempdir()
+                .map_err(|e| Error::Io("creating temporary directory".to_string(), e))?;
+            self.clone_repo(template, template_dir.path())?;
+            self.copy_contract_files(template_dir.path(), &contract_path, true)?;
+        } else {
+            self.copy_template_files(
+                contract_path.as_path(),
+                &mut ContractTemplateFiles::iter(),
+                ContractTemplateFiles::get,
+            )?;
+        }
+
+        if self.args.check {
+            self.check(&project_path)?;
+        }
+
+        if self.args.git {
+            self.git_init(&project_path)?;
+        }
 
         Ok(())
     }
 
+    // Initialize a git repository in the project and commit the generated
+    // files, unless the project is already part of a repository.
+    fn git_init(&self, project_path: &Path) -> Result<(), Error> {
+        let inside_repo = Command::new("git")
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .current_dir(project_path)
+            .output()
+            .map_err(|e| Error::Io("running git rev-parse".to_string(), e))?
+            .status
+            .success();
+        if inside_repo {
+            self.print.warnln(format!(
+                "Skipped initializing a git repository as {project_path:?} is already inside one"
+            ));
+            return Ok(());
+        }
+
+        self.print
+            .infoln(format!("Initializing git repository at {project_path:?}"));
+        for args in [
+            &["init", "--quiet"][..],
+            &["add", "--all"][..],
+            &["commit", "--quiet", "--message", "Initial commit"][..],
+        ] {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(project_path)
+                .status()
+                .map_err(|e| Error::Io(format!("running git {}", args[0]), e))?;
+            if !status.success() {
+                return Err(Error::Git(
+                    args.join(" "),
+                    project_path.to_path_buf(),
+                    status,
+                ));
+            }
+        }
+        self.print.checkln("Committed the generated files");
+        Ok(())
+    }
+
+    // Check that the workspace manifests are consistent and that its
+    // dependencies resolve, e.g. that every dependency inherited from the
+    // workspace is declared in it.
+    fn check(&self, project_path: &Path) -> Result<(), Error> {
+        self.print
+            .infoln(format!("Checking workspace at {project_path:?}"));
+        cargo_metadata::MetadataCommand::new()
+            .current_dir(project_path)
+            .exec()
+            .map_err(|e| Error::Check(project_path.to_path_buf(), e))?;
+        self.print.checkln("Workspace resolves");
+        Ok(())
+    }
+
+    fn clone_repo(&self, url: &str, to: &Path) -> Result<(), Error> {
+        self.print.globeln(format!("Cloning template {url}"));
+        let status = Command::new("git")
+            .args(["clone", "--depth", "1", "--quiet", url])
+            .arg(to)
+            .status()
+            .map_err(|e| Error::Io("running git clone".to_string(), e))?;
+        if !status.success() {
+            return Err(Error::GitClone(url.to_string(), status));
+        }
+        Ok(())
+    }
+
+    // Copy the files of a cloned contract template into the contract directory,
+    // skipping the excluded paths and rewriting its root Cargo.toml to be a
+    // member of the workspace.
+    fn copy_contract_files(&self, from: &Path, to: &Path, is_root: bool) -> Result<(), Error> {
+        let entries =
+            read_dir(from).map_err(|e| Error::Io(format!("reading directory: {from:?}"), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| Error::Io(format!("reading directory: {from:?}"), e))?;
+            let file_name = entry.file_name();
+            if TEMPLATE_EXCLUDED_PATHS.iter().any(|p| file_name == *p) {
+                continue;
+            }
+            let from = entry.path();
+            let to = to.join(&file_name);
+            if from.is_dir() {
+                Self::create_dir_all(&to)?;
+                self.copy_contract_files(&from, &to, false)?;
+                continue;
+            }
+
+            let exists = Self::file_exists(&to);
+            if exists && !self.args.overwrite {
+                self.print
+                    .infoln(format!("Skipped creating {to:?} as it already exists"));
+                continue;
+            }
+
+            if exists {
+                self.print
+                    .plusln(format!("Writing {to:?} (overwriting existing file)"));
+            } else {
+                self.print.plusln(format!("Writing {to:?}"));
+            }
+            if is_root && file_name == "Cargo.toml" {
+                let contents = read_to_string(&from)
+                    .map_err(|e| Error::Io(format!("reading file: {from:?}"), e))?;
+                Self::write(&to, &edit_contract_cargo_file(&contents, &self.args.name)?)?;
+            } else {
+                copy(&from, &to).map_err(|e| Error::Io(format!("copying file: {from:?}"), e))?;
+            }
+        }
+        Ok(())
+    }
+
     fn copy_template_files(
         &self,
         root_path: &Path,
@@ -198,6 +387,57 @@ impl Runner {
     }
 }
 
+// Check that `name` can be used as the package name of the contract.
+fn crate_name_validator(name: &str) -> Result<String, Error> {
+    let invalid = |reason| Err(Error::InvalidName(name.to_string(), reason));
+    let mut chars = name.chars();
+    match chars.next() {
+        None => return invalid("it is empty"),
+        Some(c) if !(c.is_ascii_alphabetic() || c == '_') => {
+            return invalid("it must start with a letter or `_`")
+        }
+        _ => {}
+    }
+    if !chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return invalid("it can only contain letters, digits, `-` and `_`");
+    }
+    if RUST_KEYWORDS.contains(&name) {
+        return invalid("it is a Rust keyword");
+    }
+    Ok(name.to_string())
+}
+
+// Make a template contract's Cargo.toml a member of the workspace: name the
+// package after the contract, drop profiles (they're defined by the workspace),
+// and switch soroban-sdk to the workspace dependency.
+fn edit_contract_cargo_file(contents: &str, name: &str) -> Result<String, Error> {
+    let mut doc: DocumentMut = contents.parse()?;
+    doc["package"]["name"] = toml_edit::value(name);
+    doc.remove("profile");
+
+    for section in ["dependencies", "dev-dependencies"] {
+        let Some(dep) = doc
+            .get_mut(section)
+            .and_then(|deps| deps.get_mut("soroban-sdk"))
+        else {
+            continue;
+        };
+        // A plain version string, e.g. `soroban-sdk = "22.0.0"`
+        if dep.is_str() {
+            *dep = Item::Value(Value::InlineTable(InlineTable::new()));
+        }
+        let Some(table) = dep.as_table_like_mut() else {
+            continue;
+        };
+        for key in DEPENDENCY_SOURCE_KEYS {
+            table.remove(key);
+        }
+        table.insert("workspace", toml_edit::value(true));
+    }
+
+    Ok(doc.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -219,7 +459,10 @@ mod tests {
                 name: "hello_world".to_string(),
                 with_example: None,
                 frontend_template: None,
+                template: None,
                 overwrite: false,
+                check: false,
+                git: false,
             },
             print: print::Print::new(false),
         };
@@ -239,7 +482,10 @@ mod tests {
                 name: "contract2".to_string(),
                 with_example: None,
                 frontend_template: None,
+                template: None,
                 overwrite: false,
+                check: false,
+                git: false,
             },
             print: print::Print::new(false),
         };
@@ -254,7 +500,99 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[test]
+    fn test_edit_contract_cargo_file() {
+        let contents = r#"[package]
+name = "my-template"
+version = "0.1.0"
+edition = "2021"
+
+[lib]
+crate-type = ["lib", "cdylib"]
+
+[dependencies]
+soroban-sdk = "22.0.0"
+
+[dev-dependencies]
+soroban-sdk = { version = "22.0.0", features = ["testutils"] }
+
+[profile.release]
+opt-level = "z"
+"#;
+        let doc: toml_edit::DocumentMut = edit_contract_cargo_file(contents, "my_contract")
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(doc["package"]["name"].as_str(), Some("my_contract"));
+        assert!(doc.get("profile").is_none());
+        assert_eq!(
+            doc["dependencies"]["soroban-sdk"]["workspace"].as_bool(),
+            Some(true)
+        );
+        assert!(doc["dependencies"]["soroban-sdk"].get("version").is_none());
+        assert_eq!(
+            doc["dev-dependencies"]["soroban-sdk"]["features"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|v| v.as_str().unwrap())
+                .collect::<Vec<_>>(),
+            ["testutils"]
+        );
+    }
+
+    #[test]
+    fn test_edit_contract_cargo_file_preserves_dependency_features() {
+        let contents = r#"[package]
+name = "my-template"
+version = "0.1.0"
+
+[dependencies]
+soroban-sdk = { version = "22.0.0", default-features = false, features = ["alloc"] }
+
+[dev-dependencies.soroban-sdk]
+version = "22.0.0"
+features = ["testutils"]
+"#;
+        let doc: toml_edit::DocumentMut = edit_contract_cargo_file(contents, "my_contract")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let features = |section: &str| {
+            doc[section]["soroban-sdk"]["features"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|v| v.as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let dep = &doc["dependencies"]["soroban-sdk"];
+        assert_eq!(dep["workspace"].as_bool(), Some(true));
+        assert_eq!(dep["default-features"].as_bool(), Some(false));
+        assert!(dep.get("version").is_none());
+        assert_eq!(features("dependencies"), ["alloc"]);
+
+        let dev_dep = &doc["dev-dependencies"]["soroban-sdk"];
+        assert_eq!(dev_dep["workspace"].as_bool(), Some(true));
+        assert!(dev_dep.get("version").is_none());
+        assert_eq!(features("dev-dependencies"), ["testutils"]);
+    }
+
     // test helpers
+    #[test]
+    fn test_crate_name_validator() {
+        for name in ["hello_world", "hello-world", "_private", "token2"] {
+            assert_eq!(crate_name_validator(name).unwrap(), name);
+        }
+        for name in ["", "2fa", "-dash", "hello world", "héllo", "fn", "self"] {
+            assert!(
+                matches!(crate_name_validator(name), Err(Error::InvalidName(..))),
+                "{name:?} should be invalid"
+            );
+        }
+    }
+
     fn assert_base_template_files_exist(project_dir: &Path) {
         let expected_paths = ["contracts", "Cargo.toml", "README.md"];
         for path in &expected_paths {
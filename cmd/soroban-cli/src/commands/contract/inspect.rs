@@ -1,12 +1,11 @@
 use crate::xdr;
 use clap::{command, Parser};
+use sha2::{Digest, Sha256};
 use soroban_spec_tools::contract;
 use std::{fmt::Debug, path::PathBuf};
 use tracing::debug;
 
 use super::SpecOutput;
-use crate::commands::global::Args;
-use crate::print::Print;
 use crate::{config::locator, wasm};
 
 #[derive(Parser, Debug, Clone)]
@@ -35,12 +34,9 @@ pub enum Error {
 }
 
 impl Cmd {
-    pub fn run(&self, global_args: &Args) -> Result<(), Error> {
-        Print::new(global_args.quiet).warnln(
-            "`contract inspect` has been deprecated in favor of `contract info`. \
-            Please use `contract info` instead.",
-        );
-        let wasm = self.wasm.parse()?;
+    pub fn run(&self) -> Result<(), Error> {
+        let bytes = self.wasm.read()?;
+        let wasm = contract::Spec::new(&bytes)?;
         debug!("File: {}", self.wasm.wasm.to_string_lossy());
         let output = match self.output {
             SpecOutput::XdrBase64 => wasm
@@ -48,7 +44,12 @@ impl Cmd {
                 .clone()
                 .ok_or_else(|| Error::MissingSpec(self.wasm.wasm.clone()))?,
             SpecOutput::XdrBase64Array => wasm.spec_as_json_array()?,
-            SpecOutput::Docs => wasm.to_string(),
+            SpecOutput::Docs => format!(
+                "File: {}\nSize: {} bytes\nSHA256: {}\n\n{wasm}",
+                self.wasm.wasm.display(),
+                bytes.len(),
+                hex::encode(Sha256::digest(&bytes)),
+            ),
         };
         println!("{output}");
         Ok(())
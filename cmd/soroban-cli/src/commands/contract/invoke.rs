@@ -13,6 +13,7 @@ use soroban_spec::read::FromWasmError;
 use super::super::events;
 use super::arg_parsing;
 use crate::assembled::Assembled;
+use crate::failure_snapshot::OnFail;
 use crate::{
     assembled::simulate_and_assemble_transaction,
     commands::{
@@ -23,7 +24,9 @@ use crate::{
     },
     config::{self, data, locator, network},
     get_spec::{self, get_remote_contract_spec},
+    output::Output,
     print, rpc,
+    tx::builder::memo,
     xdr::{
         self, AccountEntry, AccountEntryExt, AccountId, ContractEvent, ContractEventType,
         DiagnosticEvent, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
@@ -55,9 +58,14 @@ pub struct Cmd {
     pub config: config::Args,
     #[command(flatten)]
     pub fee: crate::fee::Args,
+    #[command(flatten)]
+    pub memo: memo::Args,
     /// Whether or not to send a transaction
     #[arg(long, value_enum, default_value_t, env = "STELLAR_SEND")]
     pub send: Send,
+    /// Format of the result printed to stdout
+    #[arg(long, value_enum, default_value_t)]
+    pub output: Output,
 }
 
 impl FromStr for Cmd {
@@ -184,19 +192,28 @@ impl Cmd {
         &self,
         host_function_params: &InvokeContractArgs,
         account_details: &AccountEntry,
+        network: &network::Network,
         rpc_client: &Client,
+        on_fail: &OnFail<'_>,
     ) -> Result<Assembled, Error> {
         let sequence: i64 = account_details.seq_num.0;
         let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) =
             account_details.account_id.clone();
 
-        let tx = build_invoke_contract_tx(
+        let tx = self.memo.add_to(build_invoke_contract_tx(
             host_function_params.clone(),
             sequence + 1,
             self.fee.fee,
             account_id,
-        )?;
-        Ok(simulate_and_assemble_transaction(rpc_client, &tx).await?)
+        )?);
+        Ok(on_fail
+            .check(
+                network
+                    .with_rpc_timeout(simulate_and_assemble_transaction(rpc_client, &tx))
+                    .await?,
+                &tx,
+            )
+            .await?)
     }
 }
 
@@ -223,7 +240,9 @@ impl NetworkRunnable for Cmd {
             // For testing wasm arg parsing
             let _ = build_host_function_parameters(&contract_id, &self.slop, spec_entries, config)?;
         }
-        let client = network.rpc_client()?;
+        let client = network
+            .checked_rpc_client(config.network.rpc_options())
+            .await?;
 
         let spec_entries = get_remote_contract_spec(
             &contract_id.0,
@@ -238,19 +257,28 @@ impl NetworkRunnable for Cmd {
         let (function, spec, host_function_params, signers) =
             build_host_function_parameters(&contract_id, &self.slop, &spec_entries, config)?;
 
+        let on_fail = OnFail::new(global_args, &network, &print);
         let assembled = self
-            .simulate(&host_function_params, &default_account_entry(), &client)
+            .simulate(
+                &host_function_params,
+                &default_account_entry(),
+                &network,
+                &client,
+                &on_fail,
+            )
             .await?;
         let should_send = self.should_send_tx(&assembled.sim_res)?;
 
         let account_details = if should_send == ShouldSend::Yes {
-            client
-                .verify_network_passphrase(Some(&network.network_passphrase))
-                .await?;
-
-            client
-                .get_account(&config.source_account()?.to_string())
-                .await?
+            network
+                .with_rpc_timeout(
+                    client.verify_network_passphrase(Some(&network.network_passphrase)),
+                )
+                .await??;
+
+            network
+                .with_rpc_timeout(client.get_account(&config.source_account()?.to_string()))
+                .await??
         } else {
             if should_send == ShouldSend::DefaultNo {
                 print.infoln(
@@ -261,21 +289,33 @@ impl NetworkRunnable for Cmd {
             let (return_value, events) = (sim_res.results()?, sim_res.events()?);
             crate::log::event::all(&events);
             crate::log::event::contract(&events, &print);
-            return Ok(output_to_string(&spec, &return_value[0].xdr, &function)?);
+            return Ok(output_to_string(
+                &spec,
+                &return_value[0].xdr,
+                &function,
+                self.output,
+            )?);
         };
         let sequence: i64 = account_details.seq_num.into();
         let AccountId(PublicKey::PublicKeyTypeEd25519(account_id)) = account_details.account_id;
 
-        let tx = Box::new(build_invoke_contract_tx(
+        let tx = Box::new(self.memo.add_to(build_invoke_contract_tx(
             host_function_params.clone(),
             sequence + 1,
             self.fee.fee,
             account_id,
-        )?);
+        )?));
         if self.fee.build_only {
             return Ok(TxnResult::Txn(tx));
         }
-        let txn = simulate_and_assemble_transaction(&client, &tx).await?;
+        let txn = on_fail
+            .check(
+                network
+                    .with_rpc_timeout(simulate_and_assemble_transaction(&client, &tx))
+                    .await?,
+                &tx,
+            )
+            .await?;
         let assembled = self.fee.apply_to_assembled_txn(txn);
         let mut txn = Box::new(assembled.transaction().clone());
         if self.fee.sim_only {
@@ -290,9 +330,17 @@ impl NetworkRunnable for Cmd {
         if let Some(tx) = config.sign_soroban_authorizations(&txn, &signers).await? {
             txn = Box::new(tx);
         }
-        let res = client
-            .send_transaction_polling(&config.sign_with_local_key(*txn).await?)
+        let signed_txn = config.sign_with_local_key((*txn).clone()).await?;
+        crate::log::rpc::request("sendTransaction", &signed_txn);
+        let res = on_fail
+            .check(
+                network
+                    .with_rpc_timeout(client.send_transaction_polling(&signed_txn))
+                    .await?,
+                &txn,
+            )
             .await?;
+        crate::log::rpc::transaction_response(&res);
         if !no_cache {
             data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
         }
@@ -305,7 +353,12 @@ impl NetworkRunnable for Cmd {
 
         crate::log::event::all(&events);
         crate::log::event::contract(&events, &print);
-        Ok(output_to_string(&spec, &return_value, &function)?)
+        Ok(output_to_string(
+            &spec,
+            &return_value,
+            &function,
+            self.output,
+        )?)
     }
 }
 
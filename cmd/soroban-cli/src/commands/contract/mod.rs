@@ -61,7 +61,7 @@ pub enum Cmd {
     /// be overwritten unless `--overwrite` is passed.
     Init(init::Cmd),
 
-    /// (Deprecated in favor of `contract info` subcommand) Inspect a WASM file listing contract functions, meta, etc
+    /// Inspect a WASM file, reporting its size and hash, env meta, contract meta and spec
     #[command(display_order = 100)]
     Inspect(inspect::Cmd),
 
@@ -155,10 +155,10 @@ impl Cmd {
             Cmd::Extend(extend) => extend.run().await?,
             Cmd::Alias(alias) => alias.run(global_args)?,
             Cmd::Deploy(deploy) => deploy.run(global_args).await?,
-            Cmd::Id(id) => id.run()?,
+            Cmd::Id(id) => id.run(global_args)?,
             Cmd::Info(info) => info.run(global_args).await?,
             Cmd::Init(init) => init.run(global_args)?,
-            Cmd::Inspect(inspect) => inspect.run(global_args)?,
+            Cmd::Inspect(inspect) => inspect.run()?,
             Cmd::Install(install) => {
                 print.warnln("`stellar contract install` has been deprecated in favor of `stellar contract upload`");
                 install.run(global_args).await?;
@@ -181,8 +181,12 @@ impl NetworkRunnable for Cmd {
         let network = self.config.network.get(&locator)?;
 
         tracing::trace!(?network);
-        let client = network.rpc_client()?;
+        let client = network
+            .checked_rpc_client(self.config.network.rpc_options())
+            .await?;
         let keys = self.key.parse_keys(&locator, &network)?;
-        Ok(client.get_full_ledger_entries(&keys).await?)
+        Ok(network
+            .with_rpc_timeout(client.get_full_ledger_entries(&keys))
+            .await??)
     }
 }
@@ -133,13 +133,15 @@ impl NetworkRunnable for Cmd {
         let network = config.get_network()?;
         tracing::trace!(?network);
         let entry_keys = self.key.parse_keys(&config.locator, &network)?;
-        let client = network.rpc_client()?;
+        let client = network
+            .checked_rpc_client(config.network.rpc_options())
+            .await?;
         let source_account = config.source_account()?;
 
         // Get the account sequence number
-        let account_details = client
-            .get_account(&source_account.clone().to_string())
-            .await?;
+        let account_details = network
+            .with_rpc_timeout(client.get_account(&source_account.clone().to_string()))
+            .await??;
         let sequence: i64 = account_details.seq_num.into();
 
         let tx = Box::new(Transaction {
@@ -172,9 +174,12 @@ impl NetworkRunnable for Cmd {
         if self.fee.build_only {
             return Ok(TxnResult::Txn(tx));
         }
-        let res = client
-            .send_transaction_polling(&config.sign_with_local_key(*tx).await?)
-            .await?;
+        let signed_tx = config.sign_with_local_key(*tx).await?;
+        crate::log::rpc::request("sendTransaction", &signed_tx);
+        let res = network
+            .with_rpc_timeout(client.send_transaction_polling(&signed_tx))
+            .await??;
+        crate::log::rpc::transaction_response(&res);
         if args.map_or(true, |a| !a.no_cache) {
             data::write(res.clone().try_into()?, &network.rpc_uri()?)?;
         }
@@ -4,8 +4,9 @@ use std::num::ParseIntError;
 
 use crate::xdr::{
     self, ContractCodeEntryExt, Error as XdrError, Hash, HostFunction, InvokeHostFunctionOp,
-    LedgerEntryData, Limits, OperationBody, ReadXdr, ScMetaEntry, ScMetaV0, Transaction,
-    TransactionResult, TransactionResultResult, VecM, WriteXdr,
+    LedgerEntryData, Limits, OperationBody, ReadXdr, ScEnvMetaEntry,
+    ScEnvMetaEntryInterfaceVersion, ScMetaEntry, ScMetaV0, Transaction, TransactionResult,
+    TransactionResultResult, VecM, WriteXdr,
 };
 use clap::{command, Parser};
 
@@ -21,7 +22,7 @@ use crate::{
     key,
     print::Print,
     rpc,
-    tx::builder::{self, TxExt},
+    tx::builder::{self, memo, TxExt},
     utils, wasm,
 };
 
@@ -36,6 +37,8 @@ pub struct Cmd {
     #[command(flatten)]
     pub fee: crate::fee::Args,
     #[command(flatten)]
+    pub memo: memo::Args,
+    #[command(flatten)]
     pub wasm: wasm::Args,
     #[arg(long, short = 'i', default_value = "false")]
     /// Whether to ignore safety checks when deploying contracts
@@ -108,11 +111,18 @@ impl NetworkRunnable for Cmd {
         let config = config.unwrap_or(&self.config);
         let contract = self.wasm.read()?;
         let network = config.get_network()?;
-        let client = network.rpc_client()?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
+        let client = network
+            .checked_rpc_client(config.network.rpc_options())
             .await?;
-        let wasm_spec = &self.wasm.parse().map_err(|e| Error::CannotParseWasm {
+        network
+            .with_rpc_timeout(client.verify_network_passphrase(Some(&network.network_passphrase)))
+            .await??;
+        let wasm_spec = if self.ignore_checks {
+            self.wasm.parse()
+        } else {
+            self.wasm.validate()
+        };
+        let wasm_spec = &wasm_spec.map_err(|e| Error::CannotParseWasm {
             wasm: self.wasm.wasm.clone(),
             error: e,
         })?;
@@ -134,16 +144,30 @@ impl NetworkRunnable for Cmd {
             }
         }
 
+        if let Some(protocol) = get_contract_env_meta_protocol(wasm_spec) {
+            match network.with_rpc_timeout(client.get_network()).await {
+                Ok(Ok(info)) if protocol > info.protocol_version => print.warnln(format!(
+                    "{path} was built for protocol {protocol}, which is newer than the network's protocol {}, so it may fail to upload or be invoked",
+                    info.protocol_version,
+                    path = self.wasm.wasm.display(),
+                )),
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => tracing::debug!("skipping protocol version check: {e}"),
+                Err(e) => tracing::debug!("skipping protocol version check: {e}"),
+            }
+        }
+
         // Get the account sequence number
         let source_account = config.source_account()?;
 
-        let account_details = client
-            .get_account(&source_account.clone().to_string())
-            .await?;
+        let account_details = network
+            .with_rpc_timeout(client.get_account(&source_account.clone().to_string()))
+            .await??;
         let sequence: i64 = account_details.seq_num.into();
 
         let (tx_without_preflight, hash) =
             build_install_contract_code_tx(&contract, sequence + 1, self.fee.fee, &source_account)?;
+        let tx_without_preflight = self.memo.add_to(tx_without_preflight);
 
         if self.fee.build_only {
             return Ok(TxnResult::Txn(Box::new(tx_without_preflight)));
@@ -155,7 +179,9 @@ impl NetworkRunnable for Cmd {
         if !self.fee.sim_only {
             let code_key =
                 xdr::LedgerKey::ContractCode(xdr::LedgerKeyContractCode { hash: hash.clone() });
-            let contract_data = client.get_ledger_entries(&[code_key]).await?;
+            let contract_data = network
+                .with_rpc_timeout(client.get_ledger_entries(&[code_key]))
+                .await??;
 
             // Skip install if the contract is already installed, and the contract has an extension version that isn't V0.
             // In protocol 21 extension V1 was added that stores additional information about a contract making execution
@@ -185,7 +211,12 @@ impl NetworkRunnable for Cmd {
 
         print.infoln("Simulating install transaction…");
 
-        let txn = simulate_and_assemble_transaction(&client, &tx_without_preflight).await?;
+        let txn = network
+            .with_rpc_timeout(simulate_and_assemble_transaction(
+                &client,
+                &tx_without_preflight,
+            ))
+            .await??;
         let txn = Box::new(self.fee.apply_to_assembled_txn(txn).transaction().clone());
 
         if self.fee.sim_only {
@@ -195,7 +226,11 @@ impl NetworkRunnable for Cmd {
         let signed_txn = &self.config.sign_with_local_key(*txn).await?;
 
         print.globeln("Submitting install transaction…");
-        let txn_resp = client.send_transaction_polling(signed_txn).await?;
+        crate::log::rpc::request("sendTransaction", signed_txn);
+        let txn_resp = network
+            .with_rpc_timeout(client.send_transaction_polling(signed_txn))
+            .await??;
+        crate::log::rpc::transaction_response(&txn_resp);
 
         if args.map_or(true, |a| !a.no_cache) {
             data::write(txn_resp.clone().try_into().unwrap(), &network.rpc_uri()?)?;
@@ -256,6 +291,20 @@ fn get_contract_meta_sdk_version(wasm_spec: &soroban_spec_tools::contract::Spec)
     None
 }
 
+// Protocol version the contract was built for, from its env meta.
+fn get_contract_env_meta_protocol(wasm_spec: &soroban_spec_tools::contract::Spec) -> Option<u32> {
+    wasm_spec
+        .env_meta
+        .iter()
+        .map(|entry| match entry {
+            ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion(ScEnvMetaEntryInterfaceVersion {
+                protocol,
+                ..
+            }) => *protocol,
+        })
+        .next()
+}
+
 pub(crate) fn build_install_contract_code_tx(
     source_code: &[u8],
     sequence: i64,
@@ -9,9 +9,14 @@ use crate::{
     rpc,
 };
 
+pub mod decode;
+
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cmd {
+    #[command(subcommand)]
+    subcommand: Option<Subcommand>,
     #[allow(clippy::doc_markdown)]
     /// The first ledger sequence number in the range to pull events
     /// https://developers.stellar.org/docs/learn/encyclopedia/network-configuration/ledger-headers#ledger-sequence
@@ -73,6 +78,13 @@ pub struct Cmd {
     network: network::Args,
 }
 
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum Subcommand {
+    /// Decode the topics and data of contract events from their base64 XDR into JSON, typed with
+    /// the spec of the contract when one is given
+    Decode(decode::Cmd),
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("cursor is not valid")]
@@ -121,6 +133,8 @@ pub enum Error {
     Locator(#[from] locator::Error),
     #[error(transparent)]
     Config(#[from] config::Error),
+    #[error(transparent)]
+    Decode(#[from] decode::Error),
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, clap::ValueEnum)]
@@ -135,6 +149,9 @@ pub enum OutputFormat {
 
 impl Cmd {
     pub async fn run(&mut self) -> Result<(), Error> {
+        if let Some(Subcommand::Decode(cmd)) = &self.subcommand {
+            return Ok(cmd.run().await?);
+        }
         // Validate that topics are made up of segments.
         for topic in &self.topic_filters {
             for (i, segment) in topic.split(',').enumerate() {
@@ -209,10 +226,14 @@ impl NetworkRunnable for Cmd {
             self.network.get(&self.locator)
         }?;
 
-        let client = network.rpc_client()?;
-        client
-            .verify_network_passphrase(Some(&network.network_passphrase))
+        let client = network
+            .checked_rpc_client(
+                config.map_or_else(|| self.network.rpc_options(), |c| c.network.rpc_options()),
+            )
             .await?;
+        network
+            .with_rpc_timeout(client.verify_network_passphrase(Some(&network.network_passphrase)))
+            .await??;
 
         let contract_ids: Vec<String> = self
             .contract_ids
@@ -224,15 +245,15 @@ impl NetworkRunnable for Cmd {
             })
             .collect::<Result<Vec<_>, Error>>()?;
 
-        Ok(client
-            .get_events(
+        Ok(network
+            .with_rpc_timeout(client.get_events(
                 start,
                 Some(self.event_type),
                 &contract_ids,
                 &self.topic_filters,
                 Some(self.count),
-            )
-            .await
+            ))
+            .await?
             .map_err(Error::Rpc)?)
     }
 }
@@ -0,0 +1,228 @@
+use std::{
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use clap::{arg, Parser};
+use serde_json::{json, Value};
+use soroban_spec_tools::{contract as contract_spec, Spec};
+
+use crate::{
+    commands::tx::xdr::{self as xdr_args, DecodeLimits, LimitArgs},
+    config::{self, locator, network, UnresolvedScAddress},
+    get_spec::{self, get_remote_contract_spec_for_address},
+    output,
+    xdr::{self, ContractEvent, ContractEventBody, ContractEventV0, DiagnosticEvent},
+};
+
+/// Decode the topics and data of contract events from their base64 XDR into JSON, typed with the
+/// spec of the contract when one is given
+#[derive(Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Base64 `ContractEvent` or `DiagnosticEvent` XDR. Read from stdin when omitted, one event
+    /// per line or a JSON array of events
+    pub events: Vec<String>,
+    /// Wasm file of the contract that emitted the events, to decode values as its types
+    #[arg(long, conflicts_with = "contract_id")]
+    pub wasm: Option<PathBuf>,
+    /// Contract ID/alias on a network whose spec is fetched to decode values as its types
+    #[arg(long, visible_alias = "id")]
+    pub contract_id: Option<config::UnresolvedContract>,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+    #[command(flatten)]
+    pub limits: LimitArgs,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("reading events from stdin: {0}")]
+    Stdin(io::Error),
+    #[error("reading {0:?}: {1}")]
+    ReadWasm(PathBuf, io::Error),
+    #[error("parsing the JSON array of events: {0}")]
+    EventsJson(serde_json::Error),
+    #[error("{0} is not a base64 ContractEvent or DiagnosticEvent")]
+    InvalidEvent(String),
+    #[error(transparent)]
+    XdrArgs(#[from] xdr_args::Error),
+    #[error(transparent)]
+    ContractSpec(#[from] contract_spec::Error),
+    #[error(transparent)]
+    GetSpec(#[from] get_spec::Error),
+    #[error(transparent)]
+    Output(#[from] output::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let spec = self.spec().await?;
+        for event in self.events()? {
+            let decoded = decode(&event, &spec, self.limits.xdr_limit)?;
+            println!("{}", serde_json::to_string_pretty(&decoded)?);
+        }
+        Ok(())
+    }
+
+    fn events(&self) -> Result<Vec<String>, Error> {
+        if !self.events.is_empty() {
+            return Ok(self.events.clone());
+        }
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(Error::Stdin)?;
+        parse_events(&input)
+    }
+
+    async fn spec(&self) -> Result<Spec, Error> {
+        Ok(if let Some(wasm) = &self.wasm {
+            let bytes = std::fs::read(wasm).map_err(|e| Error::ReadWasm(wasm.clone(), e))?;
+            Spec::new(contract_spec::Spec::new(&bytes)?.spec)
+        } else if let Some(contract_id) = &self.contract_id {
+            Spec::new(
+                get_remote_contract_spec_for_address(
+                    &UnresolvedScAddress::from(contract_id),
+                    &self.locator,
+                    &self.network,
+                    None,
+                    None,
+                )
+                .await?,
+            )
+        } else {
+            Spec(None)
+        })
+    }
+}
+
+/// Split `input` into base64 events, given as a JSON array or one per line.
+pub fn parse_events(input: &str) -> Result<Vec<String>, Error> {
+    let input = input.trim();
+    if input.starts_with('[') {
+        return serde_json::from_str(input).map_err(Error::EventsJson);
+    }
+    Ok(input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Decode a base64 `DiagnosticEvent` or `ContractEvent` within `limits`, converting its topics
+/// and data to JSON as the types of `spec` they have the shape of.
+pub fn decode(base64: &str, spec: &Spec, limits: DecodeLimits) -> Result<Value, Error> {
+    let (in_successful_contract_call, event) = match limits.from_base64(base64) {
+        Ok(DiagnosticEvent {
+            in_successful_contract_call,
+            event,
+        }) => (Some(in_successful_contract_call), event),
+        Err(diagnostic_err) => (
+            None,
+            limits.from_base64(base64).map_err(|contract_err| {
+                match (contract_err, diagnostic_err) {
+                    (e @ xdr_args::Error::LimitExceeded(_), _)
+                    | (_, e @ xdr_args::Error::LimitExceeded(_)) => Error::XdrArgs(e),
+                    _ => Error::InvalidEvent(base64.to_string()),
+                }
+            })?,
+        ),
+    };
+    let ContractEvent {
+        contract_id,
+        type_,
+        body: ContractEventBody::V0(ContractEventV0 { topics, data }),
+        ..
+    } = event;
+    let mut decoded = json!({
+        "type": type_.name().to_lowercase(),
+        "contract_id": contract_id.map(|xdr::Hash(id)| stellar_strkey::Contract(id).to_string()),
+        "topics": topics
+            .iter()
+            .map(|topic| output::to_json(topic, spec, None))
+            .collect::<Result<Vec<_>, _>>()?,
+        "data": output::to_json(&data, spec, None)?,
+    });
+    if let Some(in_successful_contract_call) = in_successful_contract_call {
+        decoded["in_successful_contract_call"] = json!(in_successful_contract_call);
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xdr::{ContractEventType, ExtensionPoint, Hash, Limits, ScSymbol, ScVal, WriteXdr};
+
+    use super::*;
+
+    fn event() -> ContractEvent {
+        ContractEvent {
+            ext: ExtensionPoint::V0,
+            contract_id: Some(Hash([0; 32])),
+            type_: ContractEventType::Contract,
+            body: ContractEventBody::V0(ContractEventV0 {
+                topics: vec![ScVal::Symbol(ScSymbol("transfer".try_into().unwrap()))]
+                    .try_into()
+                    .unwrap(),
+                data: ScVal::U32(7),
+            }),
+        }
+    }
+
+    #[test]
+    fn contract_event() {
+        let base64 = event().to_xdr_base64(Limits::none()).unwrap();
+        assert_eq!(
+            decode(&base64, &Spec(None), DecodeLimits::default()).unwrap(),
+            json!({
+                "type": "contract",
+                "contract_id": "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4",
+                "topics": ["transfer"],
+                "data": 7,
+            })
+        );
+    }
+
+    #[test]
+    fn diagnostic_event() {
+        let base64 = DiagnosticEvent {
+            in_successful_contract_call: true,
+            event: event(),
+        }
+        .to_xdr_base64(Limits::none())
+        .unwrap();
+        let decoded = decode(&base64, &Spec(None), DecodeLimits::default()).unwrap();
+        assert_eq!(decoded["in_successful_contract_call"], json!(true));
+        assert_eq!(decoded["topics"], json!(["transfer"]));
+    }
+
+    #[test]
+    fn invalid_event() {
+        assert!(matches!(
+            decode("AAAA", &Spec(None), DecodeLimits::default()),
+            Err(Error::InvalidEvent(_))
+        ));
+    }
+
+    #[test]
+    fn event_exceeding_limits() {
+        let base64 = event().to_xdr_base64(Limits::none()).unwrap();
+        let limits = DecodeLimits { depth: 500, len: 8 };
+        assert!(matches!(
+            decode(&base64, &Spec(None), limits),
+            Err(Error::XdrArgs(xdr_args::Error::LimitExceeded(_)))
+        ));
+    }
+
+    #[test]
+    fn events_from_lines_or_json() {
+        assert_eq!(parse_events("AAA\n\n BBB \n").unwrap(), ["AAA", "BBB"]);
+        assert_eq!(parse_events(r#"["AAA", "BBB"]"#).unwrap(), ["AAA", "BBB"]);
+    }
+}
@@ -2,9 +2,11 @@ use clap::{
     arg,
     builder::styling::{AnsiColor, Effects, Styles},
 };
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
+use url::Url;
 
 use super::{config, HEADING_GLOBAL};
+use crate::print::LogFormat;
 
 const USAGE_STYLES: Styles = Styles::styled()
     .header(AnsiColor::Green.on_default().effects(Effects::BOLD))
@@ -39,6 +41,12 @@ pub struct Args {
     #[arg(long, visible_alias = "vv", global = true, help_heading = HEADING_GLOBAL)]
     pub very_verbose: bool,
 
+    /// Log the base64 XDR of the transactions sent with `simulateTransaction` and
+    /// `sendTransaction` and of the RPC server's responses, including `getTransaction`, at DEBUG
+    /// level, e.g. to attach them to a bug report. The values of `--rpc-header` are redacted
+    #[arg(long, global = true, help_heading = HEADING_GLOBAL)]
+    pub log_rpc_xdr: bool,
+
     /// List installed plugins. E.g. `stellar-hello`
     #[arg(long)]
     pub list: bool,
@@ -46,6 +54,120 @@ pub struct Args {
     /// Do not cache your simulations and transactions
     #[arg(long, env = "STELLAR_NO_CACHE", global = true, help_heading = HEADING_GLOBAL)]
     pub no_cache: bool,
+
+    /// Base URL of a block explorer to link transactions and contracts to, e.g. for a private
+    /// network. Defaults to stellar.expert for testnet and mainnet.
+    #[arg(long, env = "STELLAR_EXPLORER_URL", global = true, help_heading = HEADING_GLOBAL)]
+    pub explorer_url: Option<Url>,
+
+    /// Do not use emoji in the messages written to stderr, prefix them with their level instead.
+    /// This is also the case when the `NO_COLOR` environment variable is set or stderr is not a
+    /// terminal
+    #[arg(long, global = true, help_heading = HEADING_GLOBAL)]
+    pub no_color: bool,
+
+    /// Format of the messages written to stderr
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        env = "STELLAR_LOG_FORMAT",
+        global = true,
+        help_heading = HEADING_GLOBAL
+    )]
+    pub log_format: LogFormat,
+
+    /// Number of times to retry requests to the RPC server or friendbot that fail with a
+    /// transient error, e.g. while the RPC server is starting up
+    #[arg(
+        long,
+        default_value_t = 4,
+        env = "STELLAR_RPC_RETRIES",
+        global = true,
+        help_heading = HEADING_GLOBAL
+    )]
+    pub rpc_retries: u32,
+
+    /// Delay in milliseconds before the first retry of a request to the RPC server or
+    /// friendbot, doubled after every retry
+    #[arg(
+        long,
+        default_value_t = 500,
+        env = "STELLAR_RPC_RETRY_BACKOFF",
+        global = true,
+        help_heading = HEADING_GLOBAL
+    )]
+    pub rpc_retry_backoff: u64,
+
+    /// Seconds to wait for a response to a request to the RPC server, including connecting to
+    /// it, before failing. Waiting for a sent transaction to be included in a ledger counts as a
+    /// single request
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = config::network::DEFAULT_RPC_TIMEOUT,
+        env = "STELLAR_RPC_TIMEOUT",
+        global = true,
+        help_heading = HEADING_GLOBAL
+    )]
+    pub rpc_timeout: u64,
+
+    /// When a simulation or a contract invocation fails, write the ledger entries the transaction
+    /// uses, fetched from the RPC server, to this file as a ledger snapshot, to reproduce the
+    /// failure offline with `tx simulate --snapshot`. Contract storage other than the contract
+    /// instance is only included when the transaction has a footprint, which it doesn't when its
+    /// simulation failed
+    #[arg(
+        long,
+        value_name = "FILE",
+        env = "STELLAR_DUMP_SNAPSHOT_ON_FAIL",
+        global = true,
+        help_heading = HEADING_GLOBAL
+    )]
+    pub dump_snapshot_on_fail: Option<PathBuf>,
+
+    /// Format of the error written to stderr when the command fails
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        env = "STELLAR_ERROR_FORMAT",
+        global = true,
+        help_heading = HEADING_GLOBAL
+    )]
+    pub error_format: ErrorFormat,
+}
+
+/// Format of the error written to stderr when a command fails.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorFormat {
+    /// The error message, prefixed with `error:`
+    #[default]
+    Text,
+    /// A JSON object, e.g. `{"error":{"type":"keys","message":"..."}}`, with an exit code specific to the command group that failed
+    Json,
+}
+
+impl ErrorFormat {
+    /// The format given with `--error-format` in `args` or with `STELLAR_ERROR_FORMAT`. Used
+    /// for errors parsing the arguments, which happen before [`Args`] is available.
+    pub fn from_raw_args(args: impl IntoIterator<Item = String>) -> Self {
+        let mut args = args.into_iter();
+        let mut value = None;
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                break;
+            } else if arg == "--error-format" {
+                value = args.next();
+            } else if let Some(v) = arg.strip_prefix("--error-format=") {
+                value = Some(v.to_string());
+            }
+        }
+        value
+            .or_else(|| std::env::var("STELLAR_ERROR_FORMAT").ok())
+            .and_then(|v| <Self as clap::ValueEnum>::from_str(&v, true).ok())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -64,6 +186,17 @@ pub enum Error {
 }
 
 impl Args {
+    pub fn rpc_retry(&self) -> config::network::Retry {
+        config::network::Retry {
+            max_retries: self.rpc_retries,
+            initial_backoff: Duration::from_millis(self.rpc_retry_backoff),
+        }
+    }
+
+    pub fn rpc_timeout(&self) -> Duration {
+        Duration::from_secs(self.rpc_timeout)
+    }
+
     pub fn log_level(&self) -> Option<tracing::Level> {
         if self.quiet {
             None
@@ -76,3 +209,36 @@ impl Args {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn error_format_from_raw_args() {
+        assert_eq!(
+            ErrorFormat::from_raw_args(args(&["stellar", "--error-format", "json", "keys"])),
+            ErrorFormat::Json
+        );
+        assert_eq!(
+            ErrorFormat::from_raw_args(args(&["stellar", "keys", "--error-format=json", "-x"])),
+            ErrorFormat::Json
+        );
+        assert_eq!(
+            ErrorFormat::from_raw_args(args(&[
+                "stellar",
+                "--error-format=json",
+                "--error-format=text"
+            ])),
+            ErrorFormat::Text
+        );
+        assert_eq!(
+            ErrorFormat::from_raw_args(args(&["stellar", "--", "--error-format=json"])),
+            ErrorFormat::from_raw_args(args(&["stellar"]))
+        );
+    }
+}
@@ -1,15 +1,32 @@
 use clap::command;
+use futures::StreamExt;
+use url::Url;
 
-use crate::{commands::global, config::network, print::Print};
+use crate::{
+    commands::global,
+    config::{
+        locator,
+        network::{self, Funded, Network, Retry},
+        UnresolvedMuxedAccount,
+    },
+    print::Print,
+};
 
 use super::public_key;
 
+/// Number of friendbot requests in flight with `--all`.
+const MAX_CONCURRENT_FUNDING: usize = 5;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Address(#[from] public_key::Error),
     #[error(transparent)]
     Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error("funding failed for {0} of {1} identities")]
+    FundingFailed(usize, usize),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -17,21 +34,95 @@ pub enum Error {
 pub struct Cmd {
     #[command(flatten)]
     pub network: network::Args,
-    /// Address to fund
+
+    /// Name of identity to fund
+    #[arg(required_unless_present = "all")]
+    pub name: Option<UnresolvedMuxedAccount>,
+
+    /// Fund every identity, reporting the ones that fail or are already funded instead of
+    /// stopping at the first failure
+    #[arg(long, conflicts_with = "name")]
+    pub all: bool,
+
+    /// If identity is a seed phrase use this hd path, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
     #[command(flatten)]
-    pub address: public_key::Cmd,
+    pub locator: locator::Args,
+
+    /// Friendbot URL to use instead of the one of the network, e.g. when a local stack exposes
+    /// friendbot on a different path or port. The address is appended as the `addr` query parameter
+    #[arg(long, env = "STELLAR_FRIENDBOT_URL")]
+    pub friendbot_url: Option<Url>,
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         let print = Print::new(global_args.quiet);
-        let addr = self.address.public_key()?;
-        let network = self.network.get(&self.address.locator)?;
-        network.fund_address(&addr).await?;
+        let network = self.network.get(&self.locator)?;
+        let retry = global_args.rpc_retry();
+        let Some(name) = &self.name else {
+            return self.fund_all(&network, &print, retry).await;
+        };
+        let addr = self.public_key(name)?;
+        network
+            .fund_address_with_retry(&addr, self.friendbot_url.as_ref(), retry)
+            .await?;
         print.checkln(format!(
-            "Account {:?} funded on {:?}",
-            self.address.name, network.network_passphrase
+            "Account {name:?} funded on {:?}",
+            network.network_passphrase
         ));
         Ok(())
     }
+
+    async fn fund_all(&self, network: &Network, print: &Print, retry: Retry) -> Result<(), Error> {
+        let names = self.locator.list_identities()?;
+        let mut results = futures::stream::iter(&names)
+            .map(|name| async move {
+                let name_account = UnresolvedMuxedAccount::AliasOrSecret(name.clone());
+                let result = match self.public_key(&name_account) {
+                    Ok(addr) => network
+                        .fund_address_with_retry(&addr, self.friendbot_url.as_ref(), retry)
+                        .await
+                        .map_err(Error::from),
+                    Err(e) => Err(e),
+                };
+                (name, result)
+            })
+            .buffer_unordered(MAX_CONCURRENT_FUNDING)
+            .collect::<Vec<_>>()
+            .await;
+        results.sort_by_key(|(name, _)| *name);
+
+        let mut failed = 0;
+        for (name, result) in results {
+            match result {
+                Ok(Funded::Funded) => print.checkln(format!("{name}: funded")),
+                Ok(Funded::AlreadyFunded) => {
+                    print.infoln(format!("{name}: already funded, skipped"));
+                }
+                Err(e) => {
+                    failed += 1;
+                    print.errorln(format!("{name}: {e}"));
+                }
+            }
+        }
+        if failed > 0 {
+            return Err(Error::FundingFailed(failed, names.len()));
+        }
+        Ok(())
+    }
+
+    fn public_key(
+        &self,
+        name: &UnresolvedMuxedAccount,
+    ) -> Result<stellar_strkey::ed25519::PublicKey, Error> {
+        Ok(public_key::Cmd {
+            name: name.clone(),
+            hd_path: self.hd_path,
+            locator: self.locator.clone(),
+        }
+        .public_key()?)
+    }
 }
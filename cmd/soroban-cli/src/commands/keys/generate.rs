@@ -1,8 +1,10 @@
 use clap::{arg, command};
 use sep5::SeedPhrase;
+use url::Url;
 
 use super::super::config::{
-    locator, network,
+    locator,
+    network::{self, Funded, Retry},
     secret::{self, Secret},
 };
 
@@ -65,10 +67,17 @@ pub struct Cmd {
     #[command(flatten)]
     pub network: network::Args,
 
-    /// Fund generated key pair
-    #[arg(long, default_value = "false")]
+    /// Fund generated key pair with friendbot right after saving it, reporting its public key
+    /// and whether funding succeeded. Skipped with a warning on networks without friendbot,
+    /// e.g. mainnet
+    #[arg(long, default_value = "false", conflicts_with = "no_fund")]
     pub fund: bool,
 
+    /// Friendbot URL to use instead of the one of the network, e.g. when a local stack exposes
+    /// friendbot on a different path or port. The address is appended as the `addr` query parameter
+    #[arg(long, env = "STELLAR_FRIENDBOT_URL")]
+    pub friendbot_url: Option<Url>,
+
     /// Overwrite existing identity if it already exists.
     #[arg(long)]
     pub overwrite: bool,
@@ -98,16 +107,21 @@ impl Cmd {
         let path = self.config_locator.write_identity(&self.name, &secret)?;
         print.checkln(format!("Key saved with alias {} in {path:?}", self.name));
 
-        if !self.no_fund {
-            let addr = secret.public_key(self.hd_path)?;
+        if self.fund {
+            self.fund(&print, &secret, global_args.rpc_retry()).await?;
+        } else if !self.no_fund {
+            let addr = secret.public_key(self.saved_hd_path())?;
             let network = self.network.get(&self.config_locator)?;
-            network
-                .fund_address(&addr)
+            if let Err(e) = network
+                .fund_address_with_retry(
+                    &addr,
+                    self.friendbot_url.as_ref(),
+                    global_args.rpc_retry(),
+                )
                 .await
-                .map_err(|e| {
-                    tracing::warn!("fund_address failed: {e}");
-                })
-                .unwrap_or_default();
+            {
+                tracing::warn!("fund_address failed: {e}");
+            }
             print.checkln(format!(
                 "Account {:?} funded on {:?}",
                 self.name, network.network_passphrase
@@ -117,6 +131,31 @@ impl Cmd {
         Ok(())
     }
 
+    async fn fund(&self, print: &Print, secret: &Secret, retry: Retry) -> Result<(), Error> {
+        let addr = secret.public_key(self.saved_hd_path())?;
+        print.infoln(format!("Public key of {} is {addr}", self.name));
+        let network = self.network.get(&self.config_locator)?;
+        match network
+            .fund_address_with_retry(&addr, self.friendbot_url.as_ref(), retry)
+            .await
+        {
+            Ok(Funded::Funded) => print.checkln(format!(
+                "Account {:?} funded on {:?}",
+                self.name, network.network_passphrase
+            )),
+            Ok(Funded::AlreadyFunded) => print.infoln(format!(
+                "Account {:?} is already funded on {:?}",
+                self.name, network.network_passphrase
+            )),
+            Err(network::Error::FriendbotUnavailable) => print.warnln(format!(
+                "Friendbot is not available on {:?}, skipped funding {addr}",
+                network.network_passphrase
+            )),
+            Err(e) => return Err(e.into()),
+        }
+        Ok(())
+    }
+
     fn secret(&self, print: &Print) -> Result<Secret, Error> {
         let seed_phrase = self.seed_phrase()?;
         if self.secure_store {
@@ -129,6 +168,16 @@ impl Cmd {
         }
     }
 
+    /// The `hd_path` of the key in the saved secret, which is a secret key already derived at
+    /// `hd_path` when saved with `--as-secret`.
+    fn saved_hd_path(&self) -> Option<usize> {
+        if self.as_secret {
+            None
+        } else {
+            self.hd_path
+        }
+    }
+
     fn seed_phrase(&self) -> Result<SeedPhrase, Error> {
         Ok(if self.default_seed {
             secret::test_seed_phrase()
@@ -147,6 +196,7 @@ mod tests {
         let temp_dir = tempfile::tempdir().unwrap();
         let locator = super::locator::Args {
             global: false,
+            local: false,
             config_dir: Some(temp_dir.path().to_path_buf()),
         };
 
@@ -161,6 +211,7 @@ mod tests {
             default_seed: false,
             network: super::network::Args::default(),
             fund: false,
+            friendbot_url: None,
             overwrite: false,
         };
 
@@ -197,6 +248,58 @@ mod tests {
         assert!(matches!(identity, Key::Secret(Secret::SecretKey { .. })));
     }
 
+    #[tokio::test]
+    async fn test_hd_path_defaults_to_0() {
+        let (test_locator, mut cmd) = set_up_test();
+        cmd.default_seed = true;
+        cmd.as_secret = true;
+        cmd.run(&global_args()).await.unwrap();
+        let without_hd_path = test_locator.read_identity("test_name").unwrap();
+
+        cmd.hd_path = Some(0);
+        cmd.overwrite = true;
+        cmd.run(&global_args()).await.unwrap();
+        let with_hd_path_0 = test_locator.read_identity("test_name").unwrap();
+
+        assert_eq!(
+            without_hd_path.private_key(None).unwrap().to_string(),
+            with_hd_path_0.private_key(None).unwrap().to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_storing_secret_key_at_hd_path() {
+        let (test_locator, mut cmd) = set_up_test();
+        cmd.default_seed = true;
+        cmd.as_secret = true;
+        cmd.hd_path = Some(1);
+        cmd.run(&global_args()).await.unwrap();
+
+        let identity = test_locator.read_identity("test_name").unwrap();
+        let seed_phrase = Secret::from(super::secret::test_seed_phrase().unwrap());
+        assert_eq!(
+            identity.private_key(None).unwrap().to_string(),
+            seed_phrase.private_key(Some(1)).unwrap().to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fund_skips_networks_without_friendbot() {
+        let (test_locator, mut cmd) = set_up_test();
+        cmd.no_fund = false;
+        cmd.fund = true;
+        cmd.network = super::network::Args {
+            rpc_url: Some("http://localhost:8000".to_string()),
+            network_passphrase: Some(super::network::passphrase::MAINNET.to_string()),
+            ..Default::default()
+        };
+        let global_args = global_args();
+
+        let result = cmd.run(&global_args).await;
+        assert!(result.is_ok());
+        assert!(test_locator.read_identity("test_name").is_ok());
+    }
+
     #[tokio::test]
     async fn test_storing_secret_in_secure_store() {
         set_default_credential_builder(mock::default_credential_builder());
@@ -0,0 +1,116 @@
+use std::io::{self, Read};
+
+use clap::{arg, command};
+
+use crate::{
+    commands::global,
+    config::{
+        address::KeyName,
+        locator,
+        secret::{self, Secret},
+    },
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+
+    #[error(transparent)]
+    Secret(#[from] secret::Error),
+
+    #[error("An identity with the name '{0}' already exists, use --overwrite to replace it")]
+    IdentityAlreadyExists(String),
+
+    #[error("reading the secret from stdin: {0}")]
+    Stdin(io::Error),
+
+    #[error("--secret-key must be a secret key (S...)")]
+    NotASecretKey,
+
+    #[error("--seed-phrase must be a 12 or 24 word seed phrase")]
+    NotASeedPhrase,
+
+    #[error("only secret keys (S...) and seed phrases can be imported")]
+    UnsupportedSecret,
+
+    #[error("--hd-path can only be used when importing a seed phrase")]
+    HdPathWithoutSeedPhrase,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Name of identity
+    pub name: KeyName,
+
+    /// Secret key (S...) to import. Read from stdin if neither this nor `--seed-phrase` is given
+    #[arg(long, conflicts_with = "seed_phrase")]
+    pub secret_key: Option<String>,
+
+    /// 12 or 24 word seed phrase to import. Read from stdin if neither this nor `--secret-key` is
+    /// given
+    #[arg(long)]
+    pub seed_phrase: Option<String>,
+
+    /// Note the hd path of the account to use with an imported seed phrase, saved in the identity
+    /// file for reference. Commands using the identity still need `--hd-path`
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    /// Overwrite existing identity if it already exists.
+    #[arg(long)]
+    pub overwrite: bool,
+
+    #[command(flatten)]
+    pub config_locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+
+        if self.config_locator.read_identity(&self.name).is_ok() {
+            if !self.overwrite {
+                return Err(Error::IdentityAlreadyExists(self.name.to_string()));
+            }
+            print.exclaimln(format!("Overwriting identity '{}'", self.name));
+        }
+
+        let secret = self.secret()?;
+        if self.hd_path.is_some() && !matches!(secret, Secret::SeedPhrase { .. }) {
+            return Err(Error::HdPathWithoutSeedPhrase);
+        }
+        let path =
+            self.config_locator
+                .write_identity_with_hd_path(&self.name, &secret, self.hd_path)?;
+        print.checkln(format!("Key saved with alias {} in {path:?}", self.name));
+
+        Ok(())
+    }
+
+    /// The secret from the flags or stdin. Errors never include the secret.
+    fn secret(&self) -> Result<Secret, Error> {
+        if let Some(secret_key) = &self.secret_key {
+            return match secret_key.trim().parse()? {
+                secret @ Secret::SecretKey { .. } => Ok(secret),
+                _ => Err(Error::NotASecretKey),
+            };
+        }
+        if let Some(seed_phrase) = &self.seed_phrase {
+            return match seed_phrase.trim().parse()? {
+                secret @ Secret::SeedPhrase { .. } => Ok(secret),
+                _ => Err(Error::NotASeedPhrase),
+            };
+        }
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(Error::Stdin)?;
+        match input.trim().parse()? {
+            secret @ (Secret::SecretKey { .. } | Secret::SeedPhrase { .. }) => Ok(secret),
+            Secret::SecureStore { .. } => Err(Error::UnsupportedSecret),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use clap::command;
+use serde::Serialize;
 
 use super::super::config::locator;
 
@@ -6,6 +7,8 @@ use super::super::config::locator;
 pub enum Error {
     #[error(transparent)]
     Config(#[from] locator::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -14,12 +17,33 @@ pub struct Cmd {
     #[command(flatten)]
     pub config_locator: locator::Args,
 
+    /// Get more info about the identities, including whether they are local or global and their
+    /// address
     #[arg(long, short = 'l')]
     pub long: bool,
+
+    /// Output the identities as a JSON array of objects with their `name`, `public_key` and
+    /// `scope`, `local` or `global`
+    #[arg(long, conflicts_with = "long")]
+    pub json: bool,
+}
+
+/// An identity as output by `--json`.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct Identity {
+    pub name: String,
+    /// Address of the identity, `null` if it can't be derived, e.g. from a secure store entry
+    /// that is unavailable
+    pub public_key: Option<String>,
+    pub scope: &'static str,
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<(), Error> {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&self.ls_json()?)?);
+            return Ok(());
+        }
         let res = if self.long { self.ls_l() } else { self.ls() }?.join("\n");
         println!("{res}");
         Ok(())
@@ -35,7 +59,25 @@ impl Cmd {
             .config_locator
             .list_identities_long()?
             .into_iter()
-            .map(|(name, location)| format!("{location}\nName: {name}\n"))
+            .map(|(name, key, location)| {
+                let address = key
+                    .address(None)
+                    .unwrap_or_else(|e| format!("unavailable ({e})"));
+                format!("{location}\nName: {name}\nAddress: {address}\n")
+            })
             .collect::<Vec<String>>())
     }
+
+    pub fn ls_json(&self) -> Result<Vec<Identity>, Error> {
+        Ok(self
+            .config_locator
+            .list_identities_long()?
+            .into_iter()
+            .map(|(name, key, location)| Identity {
+                name,
+                public_key: key.address(None).ok(),
+                scope: location.scope(),
+            })
+            .collect())
+    }
 }
@@ -5,10 +5,14 @@ pub mod add;
 pub mod default;
 pub mod fund;
 pub mod generate;
+pub mod import;
 pub mod ls;
 pub mod public_key;
+pub mod rename;
 pub mod rm;
 pub mod secret;
+pub mod show;
+pub mod sign_data;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
@@ -17,7 +21,7 @@ pub enum Cmd {
 
     /// Given an identity return its address (public key)
     #[command(visible_alias = "address")]
-    PublicKey(public_key::Cmd),
+    PublicKey(public_key::AddressCmd),
 
     /// Fund an identity on a test network
     Fund(fund::Cmd),
@@ -26,15 +30,31 @@ pub enum Cmd {
     /// The seed phrase can be stored in a config file (default) or in an OS-specific secure store.
     Generate(generate::Cmd),
 
+    /// Import an existing secret key or seed phrase as an identity, read from stdin unless
+    /// `--secret-key` or `--seed-phrase` is given
+    Import(import::Cmd),
+
     /// List identities
     Ls(ls::Cmd),
 
-    /// Remove an identity
+    /// Remove an identity, asking for confirmation unless `--yes` is given
     Rm(rm::Cmd),
 
+    /// Rename an identity
+    Rename(rename::Cmd),
+
     /// Output an identity's secret key
     Secret(secret::Cmd),
 
+    /// Output an identity's address, and with `--reveal` its secret key.
+    /// `keys show` used to output the secret key, use `keys secret` for that
+    Show(show::Cmd),
+
+    /// Sign arbitrary data with an identity and output the base64 encoded signature.
+    /// This is a raw Ed25519 signature over the exact bytes given, not prefixed with the network id
+    /// like a transaction signature, so it cannot be used to authorize a transaction.
+    SignData(sign_data::Cmd),
+
     /// Set the default identity that will be used on all commands.
     /// This allows you to skip `--source-account` or setting a environment
     /// variable, while reusing this value in all commands that require it.
@@ -56,14 +76,26 @@ pub enum Error {
     #[error(transparent)]
     Generate(#[from] generate::Error),
 
+    #[error(transparent)]
+    Import(#[from] import::Error),
+
     #[error(transparent)]
     Rm(#[from] rm::Error),
 
+    #[error(transparent)]
+    Rename(#[from] rename::Error),
+
     #[error(transparent)]
     Ls(#[from] ls::Error),
 
     #[error(transparent)]
-    Show(#[from] secret::Error),
+    Secret(#[from] secret::Error),
+
+    #[error(transparent)]
+    Show(#[from] show::Error),
+
+    #[error(transparent)]
+    SignData(#[from] sign_data::Error),
 
     #[error(transparent)]
     Default(#[from] default::Error),
@@ -73,12 +105,16 @@ impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
             Cmd::Add(cmd) => cmd.run(global_args)?,
-            Cmd::PublicKey(cmd) => cmd.run()?,
+            Cmd::PublicKey(cmd) => cmd.run(global_args)?,
             Cmd::Fund(cmd) => cmd.run(global_args).await?,
             Cmd::Generate(cmd) => cmd.run(global_args).await?,
+            Cmd::Import(cmd) => cmd.run(global_args)?,
             Cmd::Ls(cmd) => cmd.run()?,
             Cmd::Rm(cmd) => cmd.run(global_args)?,
+            Cmd::Rename(cmd) => cmd.run(global_args)?,
             Cmd::Secret(cmd) => cmd.run()?,
+            Cmd::Show(cmd) => cmd.run(global_args)?,
+            Cmd::SignData(cmd) => cmd.run()?,
             Cmd::Default(cmd) => cmd.run(global_args)?,
         };
         Ok(())
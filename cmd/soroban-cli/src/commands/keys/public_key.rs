@@ -1,14 +1,23 @@
 use clap::arg;
 
 use crate::{
-    commands::config::{address, locator},
-    config::UnresolvedMuxedAccount,
+    commands::{
+        config::{address, locator, network},
+        global,
+    },
+    config::{
+        sc_address::{self, UnresolvedScAddress},
+        UnresolvedMuxedAccount,
+    },
+    print::Print,
 };
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Address(#[from] address::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -42,3 +51,67 @@ impl Cmd {
         Ok(stellar_strkey::ed25519::PublicKey(bytes))
     }
 }
+
+/// `keys address`, looking up a single identity or, with `--all`, every identity.
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct AddressCmd {
+    /// Name of identity to lookup
+    #[arg(required_unless_present = "all")]
+    pub name: Option<UnresolvedMuxedAccount>,
+
+    /// Print every identity, and every contract alias of the network if one is given, with its
+    /// address, one `name: address` per line
+    #[arg(long, conflicts_with = "name")]
+    pub all: bool,
+
+    /// If identity is a seed phrase use this hd path, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    #[command(flatten)]
+    pub network: network::Args,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl AddressCmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let Some(name) = &self.name else {
+            return self.run_all(global_args);
+        };
+        Cmd {
+            name: name.clone(),
+            hd_path: self.hd_path,
+            locator: self.locator.clone(),
+        }
+        .run()
+    }
+
+    fn run_all(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        // Contract aliases are per network, without one only identities are listed
+        let network_passphrase = self
+            .network
+            .get(&self.locator)
+            .map(|network| network.network_passphrase)
+            .unwrap_or_default();
+        let mut names = self.locator.list_identities()?;
+        if !network_passphrase.is_empty() {
+            names.extend(self.locator.list_contract_aliases(&network_passphrase)?);
+        }
+        let addresses = names
+            .iter()
+            .map(|name| UnresolvedScAddress::Alias(name.clone()))
+            .collect::<Vec<_>>();
+        let resolved = sc_address::resolve_all(&addresses, &self.locator, &network_passphrase);
+        for (name, address) in names.iter().zip(resolved) {
+            match address {
+                Ok(address) => println!("{name}: {address}"),
+                Err(e) => print.warnln(format!("{name}: {e}")),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use clap::command;
+
+use crate::{
+    commands::global,
+    config::{address::KeyName, locator},
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Identity to rename
+    pub old: String,
+
+    /// New name of the identity
+    pub new: KeyName,
+
+    /// Replace the identity with the new name if it already exists
+    #[arg(long)]
+    pub overwrite: bool,
+
+    #[command(flatten)]
+    pub config: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let path = self
+            .config
+            .rename_identity(&self.old, &self.new, self.overwrite)?;
+        print.checkln(format!(
+            "Renamed identity \"{}\" to \"{}\" at {}",
+            self.old,
+            &*self.new,
+            path.display()
+        ));
+        Ok(())
+    }
+}
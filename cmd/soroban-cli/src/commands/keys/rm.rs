@@ -1,6 +1,8 @@
+use std::io::{self, IsTerminal};
+
 use clap::command;
 
-use crate::commands::global;
+use crate::{commands::global, print::Print};
 
 use super::super::config::locator;
 
@@ -8,6 +10,8 @@ use super::super::config::locator;
 pub enum Error {
     #[error(transparent)]
     Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -16,12 +20,29 @@ pub struct Cmd {
     /// Identity to remove
     pub name: String,
 
+    /// Remove the identity without asking for confirmation
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+
     #[command(flatten)]
     pub config: locator::Args,
 }
 
 impl Cmd {
     pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let path = self.config.identity_path(&self.name)?;
+        if !self.yes
+            && io::stdin().is_terminal()
+            && !print.confirm(&format!(
+                "Remove identity \"{}\" at {}?",
+                self.name,
+                path.display()
+            ))?
+        {
+            print.infoln("Identity not removed");
+            return Ok(());
+        }
         Ok(self.config.remove_identity(&self.name, global_args)?)
     }
 }
@@ -0,0 +1,49 @@
+use clap::arg;
+
+use crate::{
+    commands::global,
+    config::{key, locator},
+    print::Print,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+
+    #[error(transparent)]
+    Key(#[from] key::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Name of identity to look up
+    pub name: String,
+
+    /// If identity is a seed phrase use this hd path, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    /// Also output the secret key on a second line. Secret keys are not shown without it
+    #[arg(long)]
+    pub reveal: bool,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let key = self.locator.read_identity(&self.name)?;
+        println!("{}", key.address(self.hd_path)?);
+        if self.reveal {
+            Print::new(global_args.quiet).warnln(format!(
+                "Revealing the secret key of {}, anyone who sees it can control the account",
+                self.name
+            ));
+            println!("{}", key.private_key(self.hd_path)?);
+        }
+        Ok(())
+    }
+}
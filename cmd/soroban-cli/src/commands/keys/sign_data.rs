@@ -0,0 +1,96 @@
+use std::io::Read;
+
+use base64::{engine::general_purpose::STANDARD as base64, Engine as _};
+use clap::{arg, ValueEnum};
+
+use crate::{
+    config::{key, locator},
+    signer::LocalKey,
+    utils,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] locator::Error),
+
+    #[error(transparent)]
+    Key(#[from] key::Error),
+
+    #[error("reading data from stdin: {0}")]
+    Stdin(std::io::Error),
+
+    #[error("data is not valid {encoding:?}: {error}")]
+    InvalidData { encoding: Encoding, error: String },
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Encoding {
+    /// Hex encoded bytes
+    #[default]
+    Hex,
+    /// Base64 encoded bytes
+    Base64,
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Name of identity to sign with
+    pub name: String,
+
+    /// Data to sign. Read from stdin if not provided
+    #[arg(long)]
+    pub data: Option<String>,
+
+    /// Encoding of the data to sign
+    #[arg(long, value_enum, default_value_t)]
+    pub encoding: Encoding,
+
+    /// If identity is a seed phrase use this hd path, default is 0
+    #[arg(long)]
+    pub hd_path: Option<usize>,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        println!("{}", base64.encode(self.sign()?));
+        Ok(())
+    }
+
+    pub fn sign(&self) -> Result<[u8; 64], Error> {
+        let private_key = self
+            .locator
+            .read_identity(&self.name)?
+            .private_key(self.hd_path)?;
+        let key = LocalKey {
+            key: utils::into_signing_key(&private_key),
+            prompt: false,
+        };
+        Ok(key.sign_payload(&self.data()?))
+    }
+
+    fn data(&self) -> Result<Vec<u8>, Error> {
+        let data = if let Some(data) = &self.data {
+            data.clone()
+        } else {
+            let mut data = String::new();
+            std::io::stdin()
+                .read_to_string(&mut data)
+                .map_err(Error::Stdin)?;
+            data
+        };
+        let data = data.trim();
+        match self.encoding {
+            Encoding::Hex => hex::decode(data).map_err(|e| e.to_string()),
+            Encoding::Base64 => base64.decode(data).map_err(|e| e.to_string()),
+        }
+        .map_err(|error| Error::InvalidData {
+            encoding: self.encoding,
+            error,
+        })
+    }
+}
@@ -226,6 +226,46 @@ pub enum Error {
     Env(#[from] env::Error),
 }
 
+impl Error {
+    /// Name of the command group the error comes from, e.g. `keys`, used with
+    /// `--error-format json`. Errors parsing the arguments are `usage` errors.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Clap(_) => "usage",
+            Error::Plugin(_) => "plugin",
+            Error::Contract(_) => "contract",
+            Error::Events(_) => "events",
+            Error::Keys(_) => "keys",
+            Error::Xdr(_) => "xdr",
+            Error::Network(_) => "network",
+            Error::Container(_) => "container",
+            Error::Snapshot(_) => "snapshot",
+            Error::Tx(_) => "tx",
+            Error::Cache(_) => "cache",
+            Error::Env(_) => "env",
+        }
+    }
+
+    /// Exit code for the command group the error comes from, used with
+    /// `--error-format json`.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Clap(_) => 2,
+            Error::Plugin(_) => 3,
+            Error::Contract(_) => 10,
+            Error::Events(_) => 11,
+            Error::Keys(_) => 12,
+            Error::Xdr(_) => 13,
+            Error::Network(_) => 14,
+            Error::Container(_) => 15,
+            Error::Snapshot(_) => 16,
+            Error::Tx(_) => 17,
+            Error::Cache(_) => 18,
+            Error::Env(_) => 19,
+        }
+    }
+}
+
 #[async_trait]
 pub trait NetworkRunnable {
     type Error;
@@ -1,4 +1,4 @@
-use clap::command;
+use clap::{arg, command};
 
 use crate::{commands::global, print::Print};
 
@@ -16,6 +16,10 @@ pub struct Cmd {
     /// Set the default network name.
     pub name: String,
 
+    /// Identity to use as the source account on this network when `--source` is omitted
+    #[arg(long)]
+    pub identity: Option<String>,
+
     #[command(flatten)]
     pub config_locator: locator::Args,
 }
@@ -23,12 +27,22 @@ pub struct Cmd {
 impl Cmd {
     pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         let printer = Print::new(global_args.quiet);
-        let _ = self.config_locator.read_network(&self.name)?;
+        let mut network = self.config_locator.read_network(&self.name)?;
 
         self.config_locator.write_default_network(&self.name)?;
 
         printer.infoln(format!("The default network is set to `{}`", self.name));
 
+        if let Some(identity) = &self.identity {
+            let _ = self.config_locator.read_identity(identity)?;
+            network.default_identity = Some(identity.clone());
+            self.config_locator.write_network(&self.name, &network)?;
+            printer.infoln(format!(
+                "The default identity of `{}` is set to `{identity}`",
+                self.name
+            ));
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,27 @@
+use clap::Parser;
+
+use crate::commands::global;
+
+pub mod clean;
+
+#[derive(Debug, Parser)]
+pub enum Cmd {
+    /// Delete the buckets downloaded from history archives, or only the least recently used ones
+    /// with `--max-size`
+    Clean(clean::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Clean(#[from] clean::Error),
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        match self {
+            Cmd::Clean(cmd) => cmd.run(global_args)?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use std::{fs, path::Path, time::SystemTime};
+
+use bytesize::ByteSize;
+
+use crate::{commands::global, config::data, print::Print};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Data(#[from] data::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Only delete the least recently used buckets until the cached buckets take at most this
+    /// size, e.g. `20GB`
+    #[arg(long)]
+    pub max_size: Option<ByteSize>,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let dir = data::bucket_dir()?;
+        let max_size = self.max_size.map_or(0, |size| size.as_u64());
+        let (removed, freed) = evict_buckets(&dir, max_size)?;
+        print.checkln(format!("Removed {removed} buckets ({})", ByteSize(freed)));
+        Ok(())
+    }
+}
+
+/// Remove the least recently used buckets in `dir` until the buckets left take
+/// at most `max_size` bytes, returning the number of buckets removed and the
+/// bytes freed. Buckets are named after the hash of their content, so a
+/// removed bucket is downloaded again when needed.
+///
+/// # Errors
+///
+/// Fails if the directory can't be read or a bucket can't be removed.
+pub fn evict_buckets(dir: &Path, max_size: u64) -> Result<(usize, u64), Error> {
+    let mut buckets: Vec<(SystemTime, u64, _)> = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.starts_with("bucket-") || !(name.ends_with(".xdr") || name.ends_with(".xdr.gz")) {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        buckets.push((metadata.modified()?, metadata.len(), entry.path()));
+    }
+    buckets.sort();
+
+    let mut size: u64 = buckets.iter().map(|(_, len, _)| len).sum();
+    let mut removed = 0;
+    let mut freed = 0;
+    for (_, len, path) in buckets {
+        if size <= max_size {
+            break;
+        }
+        fs::remove_file(path)?;
+        size -= len;
+        removed += 1;
+        freed += len;
+    }
+    Ok((removed, freed))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used_buckets() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let now = SystemTime::now();
+        for (name, age) in [
+            ("bucket-a.xdr", 180),
+            ("bucket-b.xdr.gz", 120),
+            ("bucket-c.xdr", 60),
+        ] {
+            let path = dir.path().join(name);
+            fs::write(&path, [0; 10]).unwrap();
+            fs::File::options()
+                .append(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - Duration::from_secs(age))
+                .unwrap();
+        }
+        fs::write(dir.path().join("other.txt"), [0; 100]).unwrap();
+
+        assert_eq!(evict_buckets(dir.path(), 25).unwrap(), (1, 10));
+        assert!(!dir.path().join("bucket-a.xdr").exists());
+        assert!(dir.path().join("bucket-b.xdr.gz").exists());
+        assert_eq!(evict_buckets(dir.path(), 0).unwrap(), (2, 20));
+        assert!(dir.path().join("other.txt").exists());
+    }
+}
@@ -1,18 +1,19 @@
 use async_compression::tokio::bufread::GzipDecoder;
 use bytesize::ByteSize;
 use clap::{arg, Parser, ValueEnum};
+use flate2::read::GzDecoder;
 use futures::StreamExt;
 use humantime::format_duration;
 use itertools::{Either, Itertools};
 use sha2::{Digest, Sha256};
 use soroban_ledger_snapshot::LedgerSnapshot;
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
-    io::{self},
-    path::PathBuf,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
     str::FromStr,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 use stellar_xdr::curr::{
     self as xdr, AccountId, Asset, BucketEntry, ConfigSettingEntry, ConfigSettingId,
@@ -20,21 +21,22 @@ use stellar_xdr::curr::{
     LedgerKeyClaimableBalance, LedgerKeyConfigSetting, LedgerKeyContractCode,
     LedgerKeyContractData, LedgerKeyData, LedgerKeyLiquidityPool, LedgerKeyOffer,
     LedgerKeyTrustLine, LedgerKeyTtl, Limited, Limits, ReadXdr, ScAddress, ScContractInstance,
-    ScVal,
+    ScVal, TtlEntry, WriteXdr,
 };
 use tokio::fs::OpenOptions;
 use tokio::io::BufReader;
 use tokio_util::io::StreamReader;
 use url::Url;
 
+use super::cache;
+use crate::utils::http;
 use crate::{
-    commands::{config::data, global, HEADING_RPC},
-    config::{self, locator, network::passphrase},
+    commands::{config::data, global, version, HEADING_RPC},
+    config::{self, locator, network::passphrase, sc_address, UnresolvedScAddress},
     print,
     tx::builder,
     utils::get_name_from_stellar_asset_contract_storage,
 };
-use crate::{config::address::UnresolvedMuxedAccount, utils::http};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
 pub enum Output {
@@ -64,7 +66,31 @@ fn default_out_path() -> PathBuf {
 /// accounts holding the asset. To include them specify the addresses of
 /// relevant accounts.
 ///
-/// Any invalid contract id passed as `--address` will be ignored.
+/// Addresses passed as `--address` can be G/C-addresses, identity names, or
+/// contract aliases for the snapshot's network.
+///
+/// Contract data and code entries are saved with the live-until ledger of
+/// their TTL entry. The number of entries already expired at the snapshot
+/// ledger is reported.
+///
+/// Progress is written to stderr. With `--quiet` progress is suppressed and a
+/// JSON summary of the snapshot (ledger, entry count, expired entry count,
+/// output path) is written to stdout instead.
+///
+/// With `--since-ledger`, the snapshot previously written to `--out` for that
+/// ledger is updated instead of created from scratch. Only the buckets that
+/// changed between the two ledgers are scanned, and the entries found in them
+/// are merged into the previous snapshot, removing deleted ones. This is best
+/// effort: when the previous snapshot or its manifest can't be read, the
+/// snapshot doesn't match the ledger, the filters differ from the ones in the
+/// manifest, the buckets that changed can't be told apart from the ones that
+/// didn't, or contracts found reference wasms or asset issuers that aren't in
+/// the previous snapshot, a full scan is done instead.
+///
+/// A manifest describing what the snapshot covers (ledger, network, filters,
+/// wasm hashes found, archive URL, and entry counts by type) is written next
+/// to it, e.g. `snapshot.manifest.json` for `snapshot.json`, unless
+/// `--no-manifest` is given or the snapshot is written to stdout with `--out -`.
 ///
 #[derive(Parser, Debug, Clone)]
 #[group(skip)]
@@ -73,18 +99,31 @@ pub struct Cmd {
     /// The ledger sequence number to snapshot. Defaults to latest history archived ledger.
     #[arg(long)]
     ledger: Option<u32>,
+    /// Ledger of the snapshot previously written to `--out`, to update it with
+    /// the entries that changed since that ledger instead of scanning all
+    /// buckets.
+    #[arg(long)]
+    since_ledger: Option<u32>,
     /// Account or contract address/alias to include in the snapshot.
     #[arg(long = "address", help_heading = "Filter Options")]
-    address: Vec<String>,
+    address: Vec<UnresolvedScAddress>,
     /// WASM hashes to include in the snapshot.
     #[arg(long = "wasm-hash", help_heading = "Filter Options")]
     wasm_hashes: Vec<Hash>,
+    /// Also include the TTL entry of each contract data and code entry in the snapshot.
+    #[arg(long, help_heading = "Filter Options")]
+    include_ttl_entries: bool,
     /// Format of the out file.
     #[arg(long)]
     output: Output,
-    /// Out path that the snapshot is written to.
+    /// Out path that the snapshot is written to. Use `-` to write the snapshot to stdout, in
+    /// which case progress isn't written to stderr.
     #[arg(long, default_value=default_out_path().into_os_string())]
     out: PathBuf,
+    /// Don't write the manifest describing what the snapshot covers next to `--out`. No manifest
+    /// is written with `--out -` either.
+    #[arg(long)]
+    no_manifest: bool,
     #[command(flatten)]
     locator: locator::Args,
     #[command(flatten)]
@@ -92,6 +131,22 @@ pub struct Cmd {
     /// Archive URL
     #[arg(long, help_heading = HEADING_RPC, env = "STELLAR_ARCHIVE_URL")]
     archive_url: Option<Url>,
+    /// Number of ledgers between history archive checkpoints, used to suggest checkpoint ledgers
+    /// when `--ledger` isn't one. Defaults to 64, or 8 for the local network.
+    #[arg(long, help_heading = HEADING_RPC, value_parser = clap::value_parser!(u32).range(1..))]
+    checkpoint_frequency: Option<u32>,
+    /// Keep downloaded buckets gzip compressed in the cache, decompressing them each time they're
+    /// read. Uses a fraction of the disk space, at the cost of slower snapshots.
+    #[arg(long)]
+    compress_cache: bool,
+    /// After creating the snapshot, delete the least recently used buckets from the cache until
+    /// the cached buckets take at most this size, e.g. `20GB`.
+    #[arg(long)]
+    max_cache_size: Option<ByteSize>,
+    /// Seconds to wait for the history archive to accept a connection or send data before
+    /// giving up. Connections are reused across all the requests to the archive.
+    #[arg(long, help_heading = HEADING_RPC, default_value_t = 30)]
+    http_timeout: u64,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -127,6 +182,16 @@ pub enum Error {
     #[error("writing ledger snapshot: {0}")]
     WriteLedgerSnapshot(soroban_ledger_snapshot::Error),
     #[error(transparent)]
+    CleanCache(#[from] cache::clean::Error),
+    #[error("hashing ledger key: {0}")]
+    HashLedgerKey(xdr::Error),
+    #[error("json encoding summary: {0}")]
+    JsonEncodingSummary(serde_json::Error),
+    #[error("json encoding manifest: {0}")]
+    JsonEncodingManifest(serde_json::Error),
+    #[error("writing manifest {0:?}: {1}")]
+    WriteManifest(PathBuf, io::Error),
+    #[error(transparent)]
     Join(#[from] tokio::task::JoinError),
     #[error(transparent)]
     Network(#[from] config::network::Error),
@@ -140,22 +205,40 @@ pub enum Error {
     ParseAssetName(String),
     #[error(transparent)]
     Asset(#[from] builder::asset::Error),
+    #[error("request to {0} timed out, use `--http-timeout` to wait longer")]
+    HttpTimeout(Url),
+    #[error("resolving address on network {network_passphrase:?}: {source}")]
+    ResolveAddress {
+        network_passphrase: String,
+        source: sc_address::Error,
+    },
 }
 
 /// Checkpoint frequency is usually 64 ledgers, but in local test nets it'll
 /// often by 8. There's no way to simply detect what frequency to expect ledgers
-/// at, so it is hardcoded at 64, and this value is used only to help the user
+/// at, so it defaults to 64, or 8 for the local network, unless
+/// `--checkpoint-frequency` is set. This value is used only to help the user
 /// select good ledger numbers when they select one that doesn't exist.
 const CHECKPOINT_FREQUENCY: u32 = 64;
+const LOCAL_CHECKPOINT_FREQUENCY: u32 = 8;
 
 impl Cmd {
     #[allow(clippy::too_many_lines)]
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
-        let print = print::Print::new(global_args.quiet);
+        let to_stdout = self.out.as_os_str() == "-";
+        let print = print::Print::new(global_args.quiet || to_stdout);
         let start = Instant::now();
 
         let archive_url = self.archive_url()?;
-        let history = get_history(&print, &archive_url, self.ledger).await?;
+        let client = http::client_with_timeout(Duration::from_secs(self.http_timeout));
+        let history = get_history(
+            &print,
+            &client,
+            &archive_url,
+            self.ledger,
+            self.checkpoint_frequency(),
+        )
+        .await?;
 
         let ledger = history.current_ledger;
         let network_passphrase = &history.network_passphrase;
@@ -165,41 +248,36 @@ impl Cmd {
         print.infoln(format!("Network Passphrase: {network_passphrase}"));
         print.infoln(format!("Network id: {}", hex::encode(network_id)));
 
-        // Prepare a flat list of buckets to read. They'll be ordered by their
-        // level so that they can iterated higher level to lower level.
-        let buckets = history
-            .current_buckets
+        let all_buckets = bucket_list(&history);
+
+        // Search the buckets using the user inputs as the starting inputs.
+        let (account_ids, contract_ids) = self.addresses(network_passphrase)?;
+        let addresses = account_ids
             .iter()
-            .flat_map(|h| [h.curr.clone(), h.snap.clone()])
-            .filter(|b| b != "0000000000000000000000000000000000000000000000000000000000000000")
+            .map(ToString::to_string)
+            .chain(contract_ids.iter().map(ToString::to_string))
+            .sorted()
             .collect::<Vec<_>>();
 
-        // Pre-cache the buckets.
-        for (i, bucket) in buckets.iter().enumerate() {
-            cache_bucket(&print, &archive_url, i, bucket).await?;
-        }
-
-        // The snapshot is what will be written to file at the end. Fields will
-        // be updated while parsing the history archive.
-        let mut snapshot = LedgerSnapshot {
-            // TODO: Update more of the fields.
-            protocol_version: 0,
-            sequence_number: ledger,
-            timestamp: 0,
-            network_id: network_id.into(),
-            base_reserve: 1,
-            min_persistent_entry_ttl: 0,
-            min_temp_entry_ttl: 0,
-            max_entry_ttl: 0,
-            ledger_entries: Vec::new(),
+        // When updating a previous snapshot only the buckets that changed since
+        // it was created are scanned.
+        let mut previous = match self.since_ledger {
+            Some(since_ledger) => {
+                self.previous_snapshot(
+                    &print,
+                    &client,
+                    &archive_url,
+                    since_ledger,
+                    &history,
+                    network_id.into(),
+                    &all_buckets,
+                    &addresses,
+                )
+                .await?
+            }
+            None => None,
         };
 
-        // Track ledger keys seen, so that we can ignore old versions of
-        // entries. Entries can appear in both higher level and lower level
-        // buckets, and to get the latest version of the entry the version in
-        // the higher level bucket should be used.
-        let mut seen = HashSet::new();
-
         #[allow(clippy::items_after_statements)]
         #[derive(Default)]
         struct SearchInputs {
@@ -215,151 +293,277 @@ impl Cmd {
             }
         }
 
-        // Search the buckets using the user inputs as the starting inputs.
-        let (account_ids, contract_ids): (HashSet<AccountId>, HashSet<ScAddress>) = self
-            .address
-            .iter()
-            .cloned()
-            .filter_map(|a| self.resolve_address(&a, network_passphrase))
-            .partition_map(|a| a);
-
-        let mut current = SearchInputs {
-            account_ids,
-            contract_ids,
-            wasm_hashes: self.wasm_hashes.iter().cloned().collect(),
-        };
-        let mut next = SearchInputs::default();
+        // Scan the buckets, starting over with a full scan if updating the
+        // previous snapshot turns out to miss entries.
+        let (mut snapshot, buckets, removed) = 'scan: loop {
+            // Whether only the buckets that changed since the previous snapshot
+            // are scanned, regardless of how many entries it has.
+            let updating = previous.is_some();
+            let (mut snapshot, buckets) = if let Some((previous, changed)) = previous.take() {
+                print.infoln(format!(
+                    "Updating the snapshot of ledger {} with {} of {} buckets",
+                    previous.sequence_number,
+                    changed.len(),
+                    all_buckets.len(),
+                ));
+                (
+                    LedgerSnapshot {
+                        sequence_number: ledger,
+                        ..previous
+                    },
+                    changed,
+                )
+            } else {
+                // The snapshot is what will be written to file at the end. Fields
+                // will be updated while parsing the history archive.
+                let snapshot = LedgerSnapshot {
+                    // TODO: Update more of the fields.
+                    protocol_version: 0,
+                    sequence_number: ledger,
+                    timestamp: 0,
+                    network_id: network_id.into(),
+                    base_reserve: 1,
+                    min_persistent_entry_ttl: 0,
+                    min_temp_entry_ttl: 0,
+                    max_entry_ttl: 0,
+                    ledger_entries: Vec::new(),
+                };
+                (snapshot, all_buckets.clone())
+            };
 
-        loop {
-            if current.is_empty() {
-                break;
-            }
+            // Pre-cache the buckets.
+            cache_buckets(&print, &client, &archive_url, &buckets, self.compress_cache).await?;
 
-            print.infoln(format!(
-                "Searching for {} accounts, {} contracts, {} wasms",
-                current.account_ids.len(),
-                current.contract_ids.len(),
-                current.wasm_hashes.len(),
-            ));
+            // Entries of the previous snapshot, by key, so that the versions found
+            // in the changed buckets replace them. Empty when not updating a
+            // previous snapshot.
+            let previous_entries = snapshot
+                .ledger_entries
+                .iter()
+                .enumerate()
+                .map(|(index, (key, _))| ((**key).clone(), index))
+                .collect::<HashMap<_, _>>();
+            let mut removed = HashSet::new();
+
+            // Track ledger keys seen, so that we can ignore old versions of
+            // entries. Entries can appear in both higher level and lower level
+            // buckets, and to get the latest version of the entry the version in
+            // the higher level bucket should be used.
+            let mut seen = HashSet::new();
+
+            let mut current = SearchInputs {
+                account_ids: account_ids.clone(),
+                contract_ids: contract_ids.clone(),
+                wasm_hashes: self.wasm_hashes.iter().cloned().collect(),
+            };
+            let mut next = SearchInputs::default();
 
-            for (i, bucket) in buckets.iter().enumerate() {
-                // Defined where the bucket will be read from, either from cache on
-                // disk, or streamed from the archive.
-                let cache_path = cache_bucket(&print, &archive_url, i, bucket).await?;
-                let file = std::fs::OpenOptions::new()
-                    .read(true)
-                    .open(&cache_path)
-                    .map_err(Error::ReadOpeningCachedBucket)?;
-
-                let message = format!("Searching bucket {i} {bucket}");
-                print.search(format!("{message}…"));
-
-                if let Ok(metadata) = file.metadata() {
-                    print.clear_line();
-                    print.searchln(format!("{message} ({})", ByteSize(metadata.len())));
+            loop {
+                if current.is_empty() {
+                    break;
                 }
 
-                // Stream the bucket entries from the bucket, identifying
-                // entries that match the filters, and including only the
-                // entries that match in the snapshot.
-                let limited = &mut Limited::new(file, Limits::none());
-                let entries = Frame::<BucketEntry>::read_xdr_iter(limited);
-                let mut count_saved = 0;
-                for entry in entries {
-                    let Frame(entry) = entry.map_err(Error::ReadXdrFrameBucketEntry)?;
-                    let (key, val) = match entry {
-                        BucketEntry::Liveentry(l) | BucketEntry::Initentry(l) => {
-                            let k = data_into_key(&l);
-                            (k, Some(l))
+                print.infoln(format!(
+                    "Searching for {} accounts, {} contracts, {} wasms",
+                    current.account_ids.len(),
+                    current.contract_ids.len(),
+                    current.wasm_hashes.len(),
+                ));
+                for (i, bucket) in buckets.iter().enumerate() {
+                    // Defined where the bucket will be read from, either from cache on
+                    // disk, or streamed from the archive.
+                    let cache_path = cache_bucket(
+                        &print,
+                        &client,
+                        &archive_url,
+                        i,
+                        bucket,
+                        self.compress_cache,
+                    )
+                    .await?;
+                    let file = open_bucket(&cache_path)?;
+
+                    let message = format!("Searching bucket {i} {bucket}");
+                    print.search(format!("{message}…"));
+
+                    if let Ok(metadata) = fs::metadata(&cache_path) {
+                        print.clear_line();
+                        print.searchln(format!("{message} ({})", ByteSize(metadata.len())));
+                    }
+
+                    // Stream the bucket entries from the bucket, identifying
+                    // entries that match the filters, and including only the
+                    // entries that match in the snapshot.
+                    let limited = &mut Limited::new(file, Limits::none());
+                    let entries = Frame::<BucketEntry>::read_xdr_iter(limited);
+                    let mut count_saved = 0;
+                    for entry in entries {
+                        let Frame(entry) = entry.map_err(Error::ReadXdrFrameBucketEntry)?;
+                        let (key, val) = match entry {
+                            BucketEntry::Liveentry(l) | BucketEntry::Initentry(l) => {
+                                let k = data_into_key(&l);
+                                (k, Some(l))
+                            }
+                            BucketEntry::Deadentry(k) => (k, None),
+                            BucketEntry::Metaentry(m) => {
+                                if m.ledger_version > snapshot.protocol_version {
+                                    snapshot.protocol_version = m.ledger_version;
+                                    print.infoln(format!(
+                                        "Protocol version: {}",
+                                        snapshot.protocol_version
+                                    ));
+                                }
+                                continue;
+                            }
+                        };
+                        if seen.contains(&key) {
+                            continue;
                         }
-                        BucketEntry::Deadentry(k) => (k, None),
-                        BucketEntry::Metaentry(m) => {
-                            if m.ledger_version > snapshot.protocol_version {
-                                snapshot.protocol_version = m.ledger_version;
-                                print.infoln(format!(
-                                    "Protocol version: {}",
-                                    snapshot.protocol_version
-                                ));
+                        let keep = match &key {
+                            LedgerKey::Account(k) => current.account_ids.contains(&k.account_id),
+                            LedgerKey::Trustline(k) => current.account_ids.contains(&k.account_id),
+                            LedgerKey::ContractData(k) => {
+                                current.contract_ids.contains(&k.contract)
                             }
+                            LedgerKey::ContractCode(e) => current.wasm_hashes.contains(&e.hash),
+                            _ => false,
+                        } || previous_entries.contains_key(&key);
+                        if !keep {
                             continue;
                         }
-                    };
-                    if seen.contains(&key) {
-                        continue;
-                    }
-                    let keep = match &key {
-                        LedgerKey::Account(k) => current.account_ids.contains(&k.account_id),
-                        LedgerKey::Trustline(k) => current.account_ids.contains(&k.account_id),
-                        LedgerKey::ContractData(k) => current.contract_ids.contains(&k.contract),
-                        LedgerKey::ContractCode(e) => current.wasm_hashes.contains(&e.hash),
-                        _ => false,
-                    };
-                    if !keep {
-                        continue;
-                    }
-                    seen.insert(key.clone());
-                    let Some(val) = val else { continue };
-                    match &val.data {
-                        LedgerEntryData::ContractData(e) => {
-                            // If a contract instance references contract
-                            // executable stored in another ledger entry, add
-                            // that ledger entry to the filter so that Wasm for
-                            // any filtered contract is collected too in the
-                            // second pass.
-                            if keep && e.key == ScVal::LedgerKeyContractInstance {
-                                match &e.val {
-                                    ScVal::ContractInstance(ScContractInstance {
-                                        executable: ContractExecutable::Wasm(hash),
-                                        ..
-                                    }) => {
-                                        if !current.wasm_hashes.contains(hash) {
-                                            next.wasm_hashes.insert(hash.clone());
-                                            print.infoln(format!(
-                                                "Adding wasm {} to search",
-                                                hex::encode(hash)
-                                            ));
-                                        }
-                                    }
-                                    ScVal::ContractInstance(ScContractInstance {
-                                        executable: ContractExecutable::StellarAsset,
-                                        storage: Some(storage),
-                                    }) => {
-                                        if let Some(name) =
-                                            get_name_from_stellar_asset_contract_storage(storage)
-                                        {
-                                            let asset: builder::Asset = name.parse()?;
-                                            if let Some(issuer) = match asset
-                                                .resolve(&global_args.locator)?
-                                            {
-                                                Asset::Native => None,
-                                                Asset::CreditAlphanum4(a4) => Some(a4.issuer),
-                                                Asset::CreditAlphanum12(a12) => Some(a12.issuer),
-                                            } {
+                        seen.insert(key.clone());
+                        let Some(val) = val else {
+                            // The entry was deleted since the previous snapshot.
+                            if let Some(&index) = previous_entries.get(&key) {
+                                removed.insert(index);
+                            }
+                            continue;
+                        };
+                        match &val.data {
+                            LedgerEntryData::ContractData(e) => {
+                                // If a contract instance references contract
+                                // executable stored in another ledger entry, add
+                                // that ledger entry to the filter so that Wasm for
+                                // any filtered contract is collected too in the
+                                // second pass.
+                                if keep && e.key == ScVal::LedgerKeyContractInstance {
+                                    match &e.val {
+                                        ScVal::ContractInstance(ScContractInstance {
+                                            executable: ContractExecutable::Wasm(hash),
+                                            ..
+                                        }) => {
+                                            if !current.wasm_hashes.contains(hash) {
+                                                next.wasm_hashes.insert(hash.clone());
                                                 print.infoln(format!(
-                                                    "Adding asset issuer {issuer} to search"
+                                                    "Adding wasm {} to search",
+                                                    hex::encode(hash)
                                                 ));
-                                                next.account_ids.insert(issuer);
                                             }
                                         }
+                                        ScVal::ContractInstance(ScContractInstance {
+                                            executable: ContractExecutable::StellarAsset,
+                                            storage: Some(storage),
+                                        }) => {
+                                            if let Some(name) =
+                                                get_name_from_stellar_asset_contract_storage(
+                                                    storage,
+                                                )
+                                            {
+                                                let asset: builder::Asset = name.parse()?;
+                                                if let Some(issuer) = match asset
+                                                    .resolve(&global_args.locator)?
+                                                {
+                                                    Asset::Native => None,
+                                                    Asset::CreditAlphanum4(a4) => Some(a4.issuer),
+                                                    Asset::CreditAlphanum12(a12) => {
+                                                        Some(a12.issuer)
+                                                    }
+                                                } {
+                                                    print.infoln(format!(
+                                                        "Adding asset issuer {issuer} to search"
+                                                    ));
+                                                    next.account_ids.insert(issuer);
+                                                }
+                                            }
+                                        }
+                                        _ => {}
                                     }
-                                    _ => {}
                                 }
+                                keep
                             }
-                            keep
+                            _ => false,
+                        };
+                        if let Some(&index) = previous_entries.get(&key) {
+                            // Keep the previous live-until ledger, in case the TTL
+                            // entry didn't change.
+                            *snapshot.ledger_entries[index].1 .0 = val;
+                        } else {
+                            snapshot
+                                .ledger_entries
+                                .push((Box::new(key), (Box::new(val), Some(u32::MAX))));
                         }
-                        _ => false,
-                    };
-                    snapshot
-                        .ledger_entries
-                        .push((Box::new(key), (Box::new(val), Some(u32::MAX))));
-                    count_saved += 1;
+                        count_saved += 1;
+                    }
+                    if count_saved > 0 {
+                        print.infoln(format!("Found {count_saved} entries"));
+                    }
                 }
-                if count_saved > 0 {
-                    print.infoln(format!("Found {count_saved} entries"));
+
+                // The entries of accounts and wasms found in a follow-up pass
+                // can be in buckets that didn't change, which an update
+                // doesn't scan, unless the previous snapshot has them already.
+                if updating
+                    && missing_from_previous(
+                        &previous_entries,
+                        &next.account_ids,
+                        &next.wasm_hashes,
+                    )
+                {
+                    print.warnln(
+                        "Found accounts or wasms that aren't in the previous snapshot, falling back to a full scan",
+                    );
+                    continue 'scan;
                 }
+                current = next;
+                next = SearchInputs::default();
             }
-            current = next;
-            next = SearchInputs::default();
+            break (snapshot, buckets, removed);
+        };
+
+        if !removed.is_empty() {
+            print.infoln(format!("Removing {} deleted entries", removed.len()));
+            let mut index = 0;
+            snapshot.ledger_entries.retain(|_| {
+                index += 1;
+                !removed.contains(&(index - 1))
+            });
+        }
+
+        set_live_until(
+            &print,
+            &client,
+            &archive_url,
+            &buckets,
+            self.compress_cache,
+            self.include_ttl_entries,
+            &mut snapshot,
+        )
+        .await?;
+        let expired = snapshot
+            .ledger_entries
+            .iter()
+            .filter(|(_, (_, live_until))| live_until.is_some_and(|l| l < ledger))
+            .count();
+        if expired > 0 {
+            print.warnln(format!("{expired} entries are expired at ledger {ledger}"));
+        }
+
+        if to_stdout {
+            snapshot
+                .write(io::stdout().lock())
+                .map_err(Error::WriteLedgerSnapshot)?;
+            println!();
+            return Ok(());
         }
 
         // Write the snapshot to file.
@@ -372,12 +576,139 @@ impl Cmd {
             self.out
         ));
 
+        // A snapshot written to stdout has no path for a manifest to go next
+        // to, so none is written.
+        if !self.no_manifest && !to_stdout {
+            let manifest = Manifest::new(
+                &snapshot,
+                network_passphrase,
+                &archive_url,
+                addresses,
+                &self.wasm_hashes,
+            );
+            let path = manifest_path(&self.out);
+            let json =
+                serde_json::to_string_pretty(&manifest).map_err(Error::JsonEncodingManifest)?;
+            fs::write(&path, json).map_err(|e| Error::WriteManifest(path.clone(), e))?;
+            print.saveln(format!("Saved manifest to {path:?}"));
+        }
+
+        if let Some(max_cache_size) = self.max_cache_size {
+            let bucket_dir = data::bucket_dir().map_err(Error::GetBucketDir)?;
+            let (removed, freed) =
+                cache::clean::evict_buckets(&bucket_dir, max_cache_size.as_u64())?;
+            if removed > 0 {
+                print.infoln(format!(
+                    "Removed {removed} buckets ({}) from the cache",
+                    ByteSize(freed)
+                ));
+            }
+        }
+
         let duration = Duration::from_secs(start.elapsed().as_secs());
         print.checkln(format!("Completed in {}", format_duration(duration)));
 
+        if global_args.quiet {
+            let summary = Summary {
+                ledger,
+                entries: snapshot.ledger_entries.len(),
+                expired,
+                out: &self.out,
+            };
+            println!(
+                "{}",
+                serde_json::to_string(&summary).map_err(Error::JsonEncodingSummary)?
+            );
+        }
+
         Ok(())
     }
 
+    /// The snapshot previously written to `--out` for `since_ledger`, and the
+    /// buckets that changed since then, or `None` if the snapshot has to be
+    /// created with a full scan.
+    #[allow(clippy::too_many_arguments)]
+    async fn previous_snapshot(
+        &self,
+        print: &print::Print,
+        client: &reqwest::Client,
+        archive_url: &Url,
+        since_ledger: u32,
+        history: &History,
+        network_id: [u8; 32],
+        buckets: &[String],
+        addresses: &[String],
+    ) -> Result<Option<(LedgerSnapshot, Vec<String>)>, Error> {
+        let fallback = |reason: String| {
+            print.warnln(format!("{reason}, falling back to a full scan"));
+            Ok(None)
+        };
+        if since_ledger >= history.current_ledger {
+            return fallback(format!(
+                "Ledger {since_ledger} isn't before ledger {}",
+                history.current_ledger
+            ));
+        }
+        let previous = match LedgerSnapshot::read_file(&self.out) {
+            Ok(previous) => previous,
+            Err(e) => {
+                return fallback(format!(
+                    "Can't read the previous snapshot {:?}: {e}",
+                    self.out
+                ))
+            }
+        };
+        if previous.sequence_number != since_ledger || previous.network_id != network_id {
+            return fallback(format!(
+                "The previous snapshot {:?} isn't of ledger {since_ledger} of this network",
+                self.out
+            ));
+        }
+        // Entries only matching filters that weren't used for the previous
+        // snapshot could be in any bucket, not only in the changed ones.
+        let path = manifest_path(&self.out);
+        let filters = ManifestFilters::new(addresses.to_vec(), &self.wasm_hashes);
+        match fs::read(&path).map_err(|e| e.to_string()).and_then(|json| {
+            serde_json::from_slice::<PreviousManifest>(&json).map_err(|e| e.to_string())
+        }) {
+            Ok(manifest) if manifest.filters.same_as(&filters) => {}
+            Ok(_) => {
+                return fallback(format!(
+                    "The filters differ from the ones of the previous snapshot in {path:?}"
+                ))
+            }
+            Err(e) => {
+                return fallback(format!(
+                    "Can't read the manifest of the previous snapshot {path:?}: {e}"
+                ))
+            }
+        }
+        let since_history = get_history(
+            print,
+            client,
+            archive_url,
+            Some(since_ledger),
+            self.checkpoint_frequency(),
+        )
+        .await?;
+        let Some(changed) = changed_buckets(&bucket_list(&since_history), buckets) else {
+            return fallback(format!(
+                "The buckets that changed since ledger {since_ledger} can't be determined"
+            ));
+        };
+        Ok(Some((previous, changed)))
+    }
+
+    fn checkpoint_frequency(&self) -> u32 {
+        self.checkpoint_frequency
+            .unwrap_or_else(|| match self.network.get(&self.locator) {
+                Ok(network) if network.network_passphrase == passphrase::LOCAL => {
+                    LOCAL_CHECKPOINT_FREQUENCY
+                }
+                _ => CHECKPOINT_FREQUENCY,
+            })
+    }
+
     fn archive_url(&self) -> Result<Url, Error> {
         // Return the configured archive URL, or if one is not configured, guess
         // at an appropriate archive URL given the network passphrase.
@@ -402,49 +733,183 @@ impl Cmd {
             .ok_or(Error::ArchiveUrlNotConfigured)
     }
 
-    fn resolve_address(
+    // Resolve the addresses, which can be G/C-addresses, key names (as in
+    // `stellar keys address NAME`), or contract aliases, and partition them
+    // into account ids and contract addresses.
+    fn addresses(
         &self,
-        address: &str,
         network_passphrase: &str,
-    ) -> Option<Either<AccountId, ScAddress>> {
-        self.resolve_contract(address, network_passphrase)
-            .map(Either::Right)
-            .or_else(|| self.resolve_account(address).map(Either::Left))
-    }
-
-    // Resolve an account address to an account id. The address can be a
-    // G-address or a key name (as in `stellar keys address NAME`).
-    fn resolve_account(&self, address: &str) -> Option<AccountId> {
-        let address: UnresolvedMuxedAccount = address.parse().ok()?;
-
-        Some(AccountId(xdr::PublicKey::PublicKeyTypeEd25519(
-            match address.resolve_muxed_account(&self.locator, None).ok()? {
-                xdr::MuxedAccount::Ed25519(uint256) => uint256,
-                xdr::MuxedAccount::MuxedEd25519(xdr::MuxedAccountMed25519 { ed25519, .. }) => {
-                    ed25519
+    ) -> Result<(HashSet<AccountId>, HashSet<ScAddress>), Error> {
+        let addresses = self
+            .address
+            .iter()
+            .map(|address| {
+                address
+                    .clone()
+                    .resolve(&self.locator, network_passphrase)
+                    .map_err(|source| Error::ResolveAddress {
+                        network_passphrase: network_passphrase.to_string(),
+                        source,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(addresses
+            .into_iter()
+            .partition_map(|address| match address {
+                ScAddress::Account(account_id) => Either::Left(account_id),
+                contract @ ScAddress::Contract(_) => Either::Right(contract),
+            }))
+    }
+}
+
+/// Flat list of the non-empty buckets of the history, ordered by their level so
+/// that they can be iterated from the higher level to the lower level.
+fn bucket_list(history: &History) -> Vec<String> {
+    history
+        .current_buckets
+        .iter()
+        .flat_map(|h| [h.curr.clone(), h.snap.clone()])
+        .filter(|b| b != "0000000000000000000000000000000000000000000000000000000000000000")
+        .collect()
+}
+
+/// The buckets of `buckets` that aren't in `previous`. Entries in them are
+/// newer than the entries in the unchanged buckets only if they all come
+/// before the unchanged buckets, i.e. if only the higher levels changed,
+/// otherwise `None` is returned.
+fn changed_buckets(previous: &[String], buckets: &[String]) -> Option<Vec<String>> {
+    let previous = previous.iter().collect::<HashSet<_>>();
+    let changed = buckets
+        .iter()
+        .take_while(|b| !previous.contains(b))
+        .cloned()
+        .collect::<Vec<_>>();
+    buckets[changed.len()..]
+        .iter()
+        .all(|b| previous.contains(b))
+        .then_some(changed)
+}
+
+/// Whether any of the accounts or wasms is missing from the entries of the
+/// previous snapshot, which is always the case when it has no entries.
+fn missing_from_previous(
+    previous_entries: &HashMap<LedgerKey, usize>,
+    account_ids: &HashSet<AccountId>,
+    wasm_hashes: &HashSet<Hash>,
+) -> bool {
+    account_ids.iter().any(|account_id| {
+        !previous_entries.contains_key(&LedgerKey::Account(LedgerKeyAccount {
+            account_id: account_id.clone(),
+        }))
+    }) || wasm_hashes.iter().any(|hash| {
+        !previous_entries.contains_key(&LedgerKey::ContractCode(LedgerKeyContractCode {
+            hash: hash.clone(),
+        }))
+    })
+}
+
+/// Contract data and code entries are pushed to the snapshot with a
+/// placeholder live-until ledger of `u32::MAX`. Their real live-until ledger is
+/// in a TTL entry keyed by the hash of the entry's key, which can be in any
+/// bucket, so look the TTLs up in a final pass over all buckets. Entries
+/// without a TTL entry keep the placeholder. With `include_ttl_entries` the TTL
+/// entries found are also added to the snapshot, replacing the ones already in
+/// it.
+async fn set_live_until(
+    print: &print::Print,
+    client: &reqwest::Client,
+    archive_url: &Url,
+    buckets: &[String],
+    compress: bool,
+    include_ttl_entries: bool,
+    snapshot: &mut LedgerSnapshot,
+) -> Result<(), Error> {
+    let mut pending = HashMap::new();
+    let mut ttl_entries = HashMap::new();
+    for (index, (key, _)) in snapshot.ledger_entries.iter().enumerate() {
+        match &**key {
+            LedgerKey::ContractData(_) | LedgerKey::ContractCode(_) => {
+                let key_xdr = key.to_xdr(Limits::none()).map_err(Error::HashLedgerKey)?;
+                pending.insert(Hash(Sha256::digest(key_xdr).into()), index);
+            }
+            LedgerKey::Ttl(LedgerKeyTtl { key_hash }) => {
+                ttl_entries.insert(key_hash.clone(), index);
+            }
+            _ => {}
+        }
+    }
+    if pending.is_empty() {
+        return Ok(());
+    }
+    print.infoln(format!("Searching for {} TTLs", pending.len()));
+
+    for (i, bucket) in buckets.iter().enumerate() {
+        let cache_path = cache_bucket(print, client, archive_url, i, bucket, compress).await?;
+        let file = open_bucket(&cache_path)?;
+        let limited = &mut Limited::new(file, Limits::none());
+        for entry in Frame::<BucketEntry>::read_xdr_iter(limited) {
+            let Frame(entry) = entry.map_err(Error::ReadXdrFrameBucketEntry)?;
+            // The first version of a TTL seen is the latest, as buckets are
+            // ordered from the highest level to the lowest.
+            let (key_hash, ttl) = match entry {
+                BucketEntry::Liveentry(e) | BucketEntry::Initentry(e) => {
+                    let LedgerEntryData::Ttl(TtlEntry {
+                        key_hash,
+                        live_until_ledger_seq,
+                    }) = &e.data
+                    else {
+                        continue;
+                    };
+                    (key_hash.clone(), Some((*live_until_ledger_seq, e)))
                 }
-            },
-        )))
-    }
-    // Resolve a contract address to a contract id. The contract can be a
-    // C-address or a contract alias.
-    fn resolve_contract(&self, address: &str, network_passphrase: &str) -> Option<ScAddress> {
-        address.parse().ok().or_else(|| {
-            Some(ScAddress::Contract(
-                self.locator
-                    .resolve_contract_id(address, network_passphrase)
-                    .ok()?
-                    .0
-                    .into(),
-            ))
-        })
+                BucketEntry::Deadentry(LedgerKey::Ttl(LedgerKeyTtl { key_hash })) => {
+                    (key_hash, None)
+                }
+                _ => continue,
+            };
+            let Some(index) = pending.remove(&key_hash) else {
+                continue;
+            };
+            if let Some((live_until, ttl_entry)) = ttl {
+                snapshot.ledger_entries[index].1 .1 = Some(live_until);
+                if include_ttl_entries {
+                    let entry = (Box::new(ttl_entry), None);
+                    if let Some(&ttl_index) = ttl_entries.get(&key_hash) {
+                        snapshot.ledger_entries[ttl_index].1 = entry;
+                    } else {
+                        let key = LedgerKey::Ttl(LedgerKeyTtl { key_hash });
+                        snapshot.ledger_entries.push((Box::new(key), entry));
+                    }
+                }
+            }
+            if pending.is_empty() {
+                return Ok(());
+            }
+        }
     }
+    Ok(())
+}
+
+/// The checkpoint ledgers before and after `ledger`, or `None` if `ledger` is a
+/// checkpoint ledger. Checkpoint ledgers are the last ledger of each period of
+/// `frequency` ledgers, e.g. 63, 127, … for a frequency of 64.
+fn nearest_checkpoints(ledger: u32, frequency: u32) -> Option<(u32, u32)> {
+    let ledger_offset = ledger.checked_add(1)? % frequency;
+    if ledger_offset == 0 {
+        return None;
+    }
+    Some((
+        ledger.saturating_sub(ledger_offset),
+        ledger.saturating_add(frequency - ledger_offset),
+    ))
 }
 
 async fn get_history(
     print: &print::Print,
+    client: &reqwest::Client,
     archive_url: &Url,
     ledger: Option<u32>,
+    checkpoint_frequency: u32,
 ) -> Result<History, Error> {
     let archive_url = archive_url.to_string();
     let archive_url = archive_url.strip_suffix('/').unwrap_or(&archive_url);
@@ -461,23 +926,19 @@ async fn get_history(
 
     print.globe(format!("Downloading history {history_url}"));
 
-    let response = http::client()
+    let response = client
         .get(history_url.as_str())
         .send()
         .await
-        .map_err(Error::DownloadingHistory)?;
+        .map_err(|e| timeout_or(e, &history_url, Error::DownloadingHistory))?;
 
     if !response.status().is_success() {
         // Check ledger is a checkpoint ledger and available in archives.
         if let Some(ledger) = ledger {
-            let ledger_offset = (ledger + 1) % CHECKPOINT_FREQUENCY;
-
-            if ledger_offset != 0 {
+            if let Some((before, after)) = nearest_checkpoints(ledger, checkpoint_frequency) {
                 print.println("");
                 print.errorln(format!(
-                    "Ledger {ledger} may not be a checkpoint ledger, try {} or {}",
-                    ledger - ledger_offset,
-                    ledger + (CHECKPOINT_FREQUENCY - ledger_offset),
+                    "Ledger {ledger} may not be a checkpoint ledger, try {before} or {after}",
                 ));
             }
         }
@@ -487,7 +948,7 @@ async fn get_history(
     let body = response
         .bytes()
         .await
-        .map_err(Error::ReadHistoryHttpStream)?;
+        .map_err(|e| timeout_or(e, &history_url, Error::ReadHistoryHttpStream))?;
 
     print.clear_line();
     print.globeln(format!("Downloaded history {}", &history_url));
@@ -495,66 +956,394 @@ async fn get_history(
     serde_json::from_slice::<History>(&body).map_err(Error::JsonDecodingHistory)
 }
 
+/// Maximum number of buckets downloaded at the same time when pre-caching.
+const MAX_CONCURRENT_DOWNLOADS: usize = 8;
+
+/// Download the buckets that aren't cached yet concurrently, fetching each
+/// distinct bucket once, and report the combined progress on a single line.
+async fn cache_buckets(
+    print: &print::Print,
+    client: &reqwest::Client,
+    archive_url: &Url,
+    buckets: &[String],
+    compress: bool,
+) -> Result<(), Error> {
+    let mut missing = Vec::new();
+    for bucket in buckets.iter().unique() {
+        let cache_path = bucket_cache_path(bucket, compress)?;
+        if !cache_path.exists() {
+            missing.push((bucket, cache_path));
+        }
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let start = Instant::now();
+    let total = missing.len();
+    let render = renders_progress(print);
+    let message = format!("Downloading {total} buckets");
+    print.globe(format!("{message}…"));
+
+    let mut downloads = futures::stream::iter(missing)
+        .map(|(bucket, cache_path)| async move {
+            download_bucket(client, archive_url, bucket, &cache_path, None).await
+        })
+        .buffer_unordered(MAX_CONCURRENT_DOWNLOADS);
+    let mut done = 0;
+    let mut transferred = 0;
+    while let Some(result) = downloads.next().await {
+        transferred += result.inspect_err(|_| {
+            if render {
+                print.println("");
+            }
+        })?;
+        done += 1;
+        if render {
+            print.clear_line();
+            print.globe(format!(
+                "{message} {done}/{total} ({})",
+                ByteSize(transferred)
+            ));
+        }
+    }
+
+    print.clear_line();
+    print.globeln(format!(
+        "Downloaded {total} buckets ({}) in {}",
+        ByteSize(transferred),
+        format_duration(Duration::from_secs(start.elapsed().as_secs()))
+    ));
+    Ok(())
+}
+
+/// The path a bucket is cached at. A bucket already cached either compressed
+/// or not is used as is, otherwise `compress` selects the path.
+fn bucket_cache_path(bucket: &str, compress: bool) -> Result<PathBuf, Error> {
+    let bucket_dir = data::bucket_dir().map_err(Error::GetBucketDir)?;
+    let plain = bucket_dir.join(format!("bucket-{bucket}.xdr"));
+    let compressed = bucket_dir.join(format!("bucket-{bucket}.xdr.gz"));
+    Ok(if plain.exists() {
+        plain
+    } else if compressed.exists() || compress {
+        compressed
+    } else {
+        plain
+    })
+}
+
+fn is_compressed(cache_path: &Path) -> bool {
+    cache_path.extension().is_some_and(|ext| ext == "gz")
+}
+
+/// Open a cached bucket for reading, decompressing it if it is compressed.
+fn open_bucket(cache_path: &Path) -> Result<Box<dyn io::Read + Send>, Error> {
+    let file = fs::File::open(cache_path).map_err(Error::ReadOpeningCachedBucket)?;
+    // Mark the bucket as recently used, as the least recently used buckets are
+    // the first deleted by `--max-cache-size` and `snapshot cache clean`.
+    if let Ok(touch) = fs::File::options().append(true).open(cache_path) {
+        let _ = touch.set_modified(SystemTime::now());
+    }
+    Ok(if is_compressed(cache_path) {
+        Box::new(GzDecoder::new(io::BufReader::new(file)))
+    } else {
+        Box::new(file)
+    })
+}
+
 async fn cache_bucket(
     print: &print::Print,
+    client: &reqwest::Client,
     archive_url: &Url,
     bucket_index: usize,
     bucket: &str,
+    compress: bool,
 ) -> Result<PathBuf, Error> {
-    let bucket_dir = data::bucket_dir().map_err(Error::GetBucketDir)?;
-    let cache_path = bucket_dir.join(format!("bucket-{bucket}.xdr"));
+    let cache_path = bucket_cache_path(bucket, compress)?;
     if !cache_path.exists() {
-        let bucket_0 = &bucket[0..=1];
-        let bucket_1 = &bucket[2..=3];
-        let bucket_2 = &bucket[4..=5];
-        let bucket_url =
-            format!("{archive_url}/bucket/{bucket_0}/{bucket_1}/{bucket_2}/bucket-{bucket}.xdr.gz");
+        let message = format!("Downloading bucket {bucket_index} {bucket}");
+        print.globe(format!("{message}…"));
+        let mut progress = Progress::new(print, &message, None);
+        if let Err(e) = download_bucket(
+            client,
+            archive_url,
+            bucket,
+            &cache_path,
+            Some(&mut progress),
+        )
+        .await
+        {
+            print.println("");
+            return Err(e);
+        }
+        print.clear_line();
+        print.globeln(format!(
+            "Downloaded bucket {bucket_index} {bucket} ({})",
+            ByteSize(progress.transferred)
+        ));
+    }
+    Ok(cache_path)
+}
 
-        print.globe(format!("Downloading bucket {bucket_index} {bucket}…"));
+/// Download a bucket to `cache_path`, decompressing it unless `cache_path` is
+/// for a compressed bucket, and return the number of bytes transferred. The
+/// bucket is written to a `.dl` file that is renamed
+/// once complete, so that a partial download is never mistaken for a cached
+/// bucket.
+async fn download_bucket(
+    client: &reqwest::Client,
+    archive_url: &Url,
+    bucket: &str,
+    cache_path: &Path,
+    mut progress: Option<&mut Progress<'_>>,
+) -> Result<u64, Error> {
+    let bucket_0 = &bucket[0..=1];
+    let bucket_1 = &bucket[2..=3];
+    let bucket_2 = &bucket[4..=5];
+    let bucket_url =
+        format!("{archive_url}/bucket/{bucket_0}/{bucket_1}/{bucket_2}/bucket-{bucket}.xdr.gz");
+    let bucket_url = Url::from_str(&bucket_url).map_err(Error::ParsingBucketUrl)?;
 
-        let bucket_url = Url::from_str(&bucket_url).map_err(Error::ParsingBucketUrl)?;
+    let response = client
+        .get(bucket_url.as_str())
+        .send()
+        .await
+        .map_err(|e| timeout_or(e, &bucket_url, Error::GettingBucket))?;
 
-        let response = http::client()
-            .get(bucket_url.as_str())
-            .send()
-            .await
-            .map_err(Error::GettingBucket)?;
+    if !response.status().is_success() {
+        return Err(Error::GettingBucketGotStatusCode(response.status()));
+    }
 
-        if !response.status().is_success() {
-            print.println("");
-            return Err(Error::GettingBucketGotStatusCode(response.status()));
+    if let Some(progress) = progress.as_deref_mut() {
+        progress.total = response.content_length();
+    }
+    let mut transferred = 0;
+    let stream = response
+        .bytes_stream()
+        .inspect(|result| {
+            if let Ok(bytes) = result {
+                transferred += bytes.len() as u64;
+                if let Some(progress) = progress.as_deref_mut() {
+                    progress.update(bytes.len());
+                }
+            }
+        })
+        .map(|result| result.map_err(std::io::Error::other));
+    let dl_path = cache_path.with_extension("dl");
+    let mut file = OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(&dl_path)
+        .await
+        .map_err(Error::WriteOpeningCachedBucket)?;
+    // Scoped so that the stream, which counts the bytes transferred, is
+    // dropped before the count is returned.
+    {
+        let mut buf_reader = BufReader::new(StreamReader::new(stream));
+        if is_compressed(cache_path) {
+            tokio::io::copy(&mut buf_reader, &mut file).await
+        } else {
+            tokio::io::copy(&mut GzipDecoder::new(buf_reader), &mut file).await
         }
+        .map_err(|e| {
+            let timed_out = e
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<reqwest::Error>())
+                .is_some_and(reqwest::Error::is_timeout);
+            if timed_out {
+                Error::HttpTimeout(bucket_url.clone())
+            } else {
+                Error::StreamingBucket(e)
+            }
+        })?;
+    }
+    fs::rename(&dl_path, cache_path).map_err(Error::RenameDownloadFile)?;
+    Ok(transferred)
+}
 
-        if let Some(len) = response.content_length() {
-            print.clear_line();
-            print.globe(format!(
-                "Downloaded bucket {bucket_index} {bucket} ({})",
-                ByteSize(len)
-            ));
+/// [`Error::HttpTimeout`] if the request to `url` timed out, otherwise the error
+/// made by `other`.
+fn timeout_or(e: reqwest::Error, url: &Url, other: impl FnOnce(reqwest::Error) -> Error) -> Error {
+    if e.is_timeout() {
+        Error::HttpTimeout(url.clone())
+    } else {
+        other(e)
+    }
+}
+
+/// Progress lines are redrawn in place, which is only possible when writing
+/// text with emoji to a terminal.
+fn renders_progress(print: &print::Print) -> bool {
+    !print.quiet
+        && !print.plain
+        && print.format != print::LogFormat::Json
+        && io::stderr().is_terminal()
+}
+
+/// Tracks the bytes streamed for a download and renders the rate and ETA on a
+/// single line. Rendering is disabled when stderr is not a terminal.
+struct Progress<'a> {
+    print: &'a print::Print,
+    message: &'a str,
+    total: Option<u64>,
+    transferred: u64,
+    enabled: bool,
+    start: Instant,
+    last_render: Option<Instant>,
+}
+
+impl<'a> Progress<'a> {
+    const RENDER_INTERVAL: Duration = Duration::from_millis(100);
+
+    fn new(print: &'a print::Print, message: &'a str, total: Option<u64>) -> Self {
+        Self {
+            print,
+            message,
+            total,
+            transferred: 0,
+            enabled: renders_progress(print),
+            start: Instant::now(),
+            last_render: None,
+        }
+    }
+
+    fn update(&mut self, len: usize) {
+        self.transferred += len as u64;
+        if !self.enabled
+            || self
+                .last_render
+                .is_some_and(|last| last.elapsed() < Self::RENDER_INTERVAL)
+        {
+            return;
         }
+        self.last_render = Some(Instant::now());
 
-        print.println("");
-
-        let stream = response
-            .bytes_stream()
-            .map(|result| result.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)));
-        let stream_reader = StreamReader::new(stream);
-        let buf_reader = BufReader::new(stream_reader);
-        let mut decoder = GzipDecoder::new(buf_reader);
-        let dl_path = cache_path.with_extension("dl");
-        let mut file = OpenOptions::new()
-            .create(true)
-            .truncate(true)
-            .write(true)
-            .open(&dl_path)
-            .await
-            .map_err(Error::WriteOpeningCachedBucket)?;
-        tokio::io::copy(&mut decoder, &mut file)
-            .await
-            .map_err(Error::StreamingBucket)?;
-        fs::rename(&dl_path, &cache_path).map_err(Error::RenameDownloadFile)?;
+        let elapsed = self.start.elapsed().as_secs_f64();
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let rate = if elapsed > 0.0 {
+            (self.transferred as f64 / elapsed) as u64
+        } else {
+            0
+        };
+        let transferred = ByteSize(self.transferred);
+        let line = match self.total {
+            Some(total) if total > 0 => {
+                let percent = self.transferred.min(total) * 100 / total;
+                let eta = total
+                    .saturating_sub(self.transferred)
+                    .checked_div(rate)
+                    .map_or_else(String::new, |secs| {
+                        format!(", ETA {}", format_duration(Duration::from_secs(secs)))
+                    });
+                format!(
+                    "{} {percent}% ({transferred} / {}, {}/s{eta})",
+                    self.message,
+                    ByteSize(total),
+                    ByteSize(rate)
+                )
+            }
+            _ => format!("{} {transferred} ({}/s)", self.message, ByteSize(rate)),
+        };
+        self.print.clear_line();
+        self.print.globe(line);
     }
-    Ok(cache_path)
+}
+
+/// Machine readable summary written to stdout when running with `--quiet`.
+#[derive(Debug, serde::Serialize)]
+struct Summary<'a> {
+    ledger: u32,
+    entries: usize,
+    expired: usize,
+    out: &'a PathBuf,
+}
+
+/// Metadata about what a snapshot covers, written next to it so that the
+/// snapshot can be shared without recipients having to inspect its entries.
+#[derive(Debug, PartialEq, Eq, serde::Serialize)]
+struct Manifest {
+    version: &'static str,
+    ledger: u32,
+    network_passphrase: String,
+    archive_url: String,
+    filters: ManifestFilters,
+    /// Hashes of the contract code entries in the snapshot that weren't given
+    /// with `--wasm-hash`, found from the instances of the contracts included.
+    derived_wasm_hashes: Vec<String>,
+    /// Number of entries of each ledger entry type, e.g. `ContractData`.
+    entries: BTreeMap<&'static str, usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct ManifestFilters {
+    /// The `--address` filters, resolved to G/C-addresses.
+    addresses: Vec<String>,
+    wasm_hashes: Vec<String>,
+}
+
+impl ManifestFilters {
+    fn new(addresses: Vec<String>, wasm_hashes: &[Hash]) -> Self {
+        ManifestFilters {
+            addresses,
+            wasm_hashes: wasm_hashes.iter().map(hex::encode).collect(),
+        }
+    }
+
+    /// Whether the filters select the same entries, regardless of the order
+    /// they were given in.
+    fn same_as(&self, other: &Self) -> bool {
+        let sorted = |v: &[String]| v.iter().sorted().cloned().collect::<Vec<_>>();
+        sorted(&self.addresses) == sorted(&other.addresses)
+            && sorted(&self.wasm_hashes) == sorted(&other.wasm_hashes)
+    }
+}
+
+/// The part of the manifest of a previous snapshot needed to update it.
+#[derive(serde::Deserialize)]
+struct PreviousManifest {
+    filters: ManifestFilters,
+}
+
+impl Manifest {
+    fn new(
+        snapshot: &LedgerSnapshot,
+        network_passphrase: &str,
+        archive_url: &Url,
+        addresses: Vec<String>,
+        wasm_hashes: &[Hash],
+    ) -> Self {
+        let mut entries = BTreeMap::<&'static str, usize>::new();
+        let mut derived_wasm_hashes = Vec::new();
+        for (key, _) in &snapshot.ledger_entries {
+            *entries.entry(key.name()).or_default() += 1;
+            if let LedgerKey::ContractCode(LedgerKeyContractCode { hash }) = &**key {
+                if !wasm_hashes.contains(hash) {
+                    derived_wasm_hashes.push(hex::encode(hash));
+                }
+            }
+        }
+        derived_wasm_hashes.sort();
+        Manifest {
+            version: version::pkg(),
+            ledger: snapshot.sequence_number,
+            network_passphrase: network_passphrase.to_string(),
+            archive_url: archive_url.to_string(),
+            filters: ManifestFilters::new(addresses, wasm_hashes),
+            derived_wasm_hashes,
+            entries,
+        }
+    }
+}
+
+/// Path of the manifest of the snapshot at `out`, e.g.
+/// `snapshot.manifest.json` for `snapshot.json`.
+fn manifest_path(out: &Path) -> PathBuf {
+    out.with_extension("manifest.json")
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize)]
@@ -647,3 +1436,134 @@ fn data_into_key(d: &LedgerEntry) -> LedgerKey {
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_checkpoints_frequency_8() {
+        assert_eq!(nearest_checkpoints(7, 8), None);
+        assert_eq!(nearest_checkpoints(15, 8), None);
+        assert_eq!(nearest_checkpoints(10, 8), Some((7, 15)));
+        assert_eq!(nearest_checkpoints(8, 8), Some((7, 15)));
+        assert_eq!(nearest_checkpoints(14, 8), Some((7, 15)));
+    }
+
+    #[test]
+    fn nearest_checkpoints_frequency_64() {
+        assert_eq!(nearest_checkpoints(63, 64), None);
+        assert_eq!(nearest_checkpoints(100, 64), Some((63, 127)));
+    }
+
+    fn bucket_names(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn changed_buckets_higher_levels() {
+        let previous = bucket_names(&["a", "b", "c", "d"]);
+        let buckets = bucket_names(&["e", "f", "c", "d"]);
+        assert_eq!(
+            changed_buckets(&previous, &buckets),
+            Some(bucket_names(&["e", "f"]))
+        );
+        assert_eq!(changed_buckets(&previous, &previous), Some(vec![]));
+    }
+
+    #[test]
+    fn changed_buckets_without_continuity() {
+        let previous = bucket_names(&["a", "b", "c", "d"]);
+        let buckets = bucket_names(&["e", "b", "f", "d"]);
+        assert_eq!(changed_buckets(&previous, &buckets), None);
+    }
+
+    #[test]
+    fn missing_from_empty_previous_snapshot() {
+        let account_id = AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256([1; 32])));
+        let account_ids = HashSet::from([account_id.clone()]);
+        let wasm_hashes = HashSet::from([Hash([2; 32])]);
+        let previous_entries = HashMap::new();
+        assert!(missing_from_previous(
+            &previous_entries,
+            &account_ids,
+            &HashSet::new()
+        ));
+        assert!(missing_from_previous(
+            &previous_entries,
+            &HashSet::new(),
+            &wasm_hashes
+        ));
+
+        let previous_entries = HashMap::from([
+            (LedgerKey::Account(LedgerKeyAccount { account_id }), 0),
+            (
+                LedgerKey::ContractCode(LedgerKeyContractCode {
+                    hash: Hash([2; 32]),
+                }),
+                1,
+            ),
+        ]);
+        assert!(!missing_from_previous(
+            &previous_entries,
+            &account_ids,
+            &wasm_hashes
+        ));
+    }
+
+    #[test]
+    fn manifest_filters_same_regardless_of_order() {
+        let filters = |addresses: &[&str], wasm_hashes: &[&str]| ManifestFilters {
+            addresses: bucket_names(addresses),
+            wasm_hashes: bucket_names(wasm_hashes),
+        };
+        assert!(filters(&["a", "b"], &["c", "d"]).same_as(&filters(&["b", "a"], &["d", "c"])));
+        assert!(!filters(&["a", "b"], &["c"]).same_as(&filters(&["a"], &["c"])));
+        assert!(!filters(&["a"], &["c"]).same_as(&filters(&["a"], &["c", "d"])));
+    }
+
+    #[test]
+    fn manifest_path_next_to_out() {
+        assert_eq!(
+            manifest_path(Path::new("snapshot.json")),
+            Path::new("snapshot.manifest.json")
+        );
+        assert_eq!(
+            manifest_path(Path::new("dir/snap")),
+            Path::new("dir/snap.manifest.json")
+        );
+    }
+
+    #[test]
+    fn manifest_counts_entries_and_derived_wasms() {
+        let code = |hash: Hash| {
+            let key = LedgerKey::ContractCode(LedgerKeyContractCode { hash: hash.clone() });
+            let entry = LedgerEntry {
+                last_modified_ledger_seq: 0,
+                data: LedgerEntryData::ContractCode(xdr::ContractCodeEntry {
+                    ext: xdr::ContractCodeEntryExt::V0,
+                    hash,
+                    code: xdr::BytesM::default(),
+                }),
+                ext: xdr::LedgerEntryExt::V0,
+            };
+            (Box::new(key), (Box::new(entry), Some(100)))
+        };
+        let snapshot = LedgerSnapshot {
+            sequence_number: 63,
+            ledger_entries: vec![code(Hash([1; 32])), code(Hash([2; 32]))],
+            ..LedgerSnapshot::default()
+        };
+        let manifest = Manifest::new(
+            &snapshot,
+            passphrase::TESTNET,
+            &Url::from_str("https://history.example.org/").unwrap(),
+            vec![],
+            &[Hash([1; 32])],
+        );
+        assert_eq!(manifest.ledger, 63);
+        assert_eq!(manifest.filters.wasm_hashes, [hex::encode([1; 32])]);
+        assert_eq!(manifest.derived_wasm_hashes, [hex::encode([2; 32])]);
+        assert_eq!(manifest.entries, BTreeMap::from([("ContractCode", 2)]));
+    }
+}
@@ -2,24 +2,31 @@ use clap::Parser;
 
 use super::global;
 
+pub mod cache;
 pub mod create;
 
 /// Create and operate on ledger snapshots.
 #[derive(Debug, Parser)]
 pub enum Cmd {
     Create(create::Cmd),
+    /// Manage the buckets cached by `snapshot create`
+    #[command(subcommand)]
+    Cache(cache::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
     Create(#[from] create::Error),
+    #[error(transparent)]
+    Cache(#[from] cache::Error),
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
             Cmd::Create(cmd) => cmd.run(global_args).await?,
+            Cmd::Cache(cmd) => cmd.run(global_args)?,
         };
         Ok(())
     }
@@ -7,7 +7,7 @@ use crate::{
     },
     fee,
     rpc::{self, Client, GetTransactionResponse},
-    tx::builder::{self, asset, TxExt},
+    tx::builder::{self, asset, memo, TxExt},
     xdr::{self, Limits, WriteXdr},
 };
 
@@ -18,6 +18,8 @@ pub struct Args {
     pub fee: fee::Args,
     #[clap(flatten)]
     pub config: config::Args,
+    #[clap(flatten)]
+    pub memo: memo::Args,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -51,12 +53,23 @@ impl Args {
             .config
             .next_sequence_number(source_account.clone().account_id())
             .await?;
+        Ok(self.tx_with_seq_num(source_account, seq_num, body))
+    }
+
+    /// Transaction from `source_account` with the sequence number `seq_num` and the single
+    /// operation `body`, built without fetching anything from the network.
+    pub fn tx_with_seq_num(
+        &self,
+        source_account: xdr::MuxedAccount,
+        seq_num: xdr::SequenceNumber,
+        body: impl Into<xdr::OperationBody>,
+    ) -> xdr::Transaction {
         // Once we have a way to add operations this will be updated to allow for a different source account
         let operation = xdr::Operation {
             source_account: None,
             body: body.into(),
         };
-        Ok(xdr::Transaction::new_tx(
+        self.memo.add_to(xdr::Transaction::new_tx(
             source_account,
             self.fee.fee,
             seq_num,
@@ -66,7 +79,7 @@ impl Args {
 
     pub fn client(&self) -> Result<Client, Error> {
         let network = self.config.get_network()?;
-        Ok(Client::new(&network.rpc_url)?)
+        Ok(network.rpc_client()?)
     }
 
     pub async fn handle(
@@ -94,16 +107,21 @@ impl Args {
         tx: xdr::Transaction,
         args: &global::Args,
     ) -> Result<TxnEnvelopeResult<GetTransactionResponse>, Error> {
-        let network = self.config.get_network()?;
-        let client = Client::new(&network.rpc_url)?;
         if self.fee.build_only {
             return Ok(TxnEnvelopeResult::TxnEnvelope(Box::new(tx.into())));
         }
-
-        let txn_resp = client
-            .send_transaction_polling(&self.config.sign_with_local_key(tx).await?)
+        let network = self.config.get_network()?;
+        let client = network
+            .checked_rpc_client(self.config.network.rpc_options())
             .await?;
 
+        let signed_tx = self.config.sign_with_local_key(tx).await?;
+        crate::log::rpc::request("sendTransaction", &signed_tx);
+        let txn_resp = network
+            .with_rpc_timeout(client.send_transaction_polling(&signed_tx))
+            .await??;
+        crate::log::rpc::transaction_response(&txn_resp);
+
         if !args.no_cache {
             data::write(txn_resp.clone().try_into().unwrap(), &network.rpc_uri()?)?;
         }
@@ -144,7 +162,13 @@ impl Args {
             source_account,
             body: op_body.into(),
         };
-        Ok(super::xdr::add_op(tx_env, op)?)
+        let tx_env = super::xdr::add_op(tx_env, op)?;
+        Ok(match self.memo.memo() {
+            Some(memo) => super::xdr::unwrap_envelope_v1(tx_env)?
+                .add_memo(memo)
+                .into(),
+            None => tx_env,
+        })
     }
 
     pub fn resolve_asset(&self, asset: &builder::Asset) -> Result<xdr::Asset, Error> {
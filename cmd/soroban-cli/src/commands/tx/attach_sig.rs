@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    commands::global,
+    config::{address, locator, network, UnresolvedMuxedAccount},
+    print::Print,
+    signer::{self, append_signatures, is_signed_by, signature_payload_hash},
+    xdr::{self, DecoratedSignature, Limits, MuxedAccount, ReadXdr, WriteXdr},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Address(#[from] address::Error),
+    #[error(transparent)]
+    Signer(#[from] signer::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("reading signature {0}: {1}")]
+    ReadingSignature(PathBuf, std::io::Error),
+    #[error("failed to decode the decorated signature in {0}")]
+    SignatureDecode(PathBuf),
+    #[error(
+        "the signature in {0} is not a valid signature of the transaction by any of the signers"
+    )]
+    InvalidSignature(PathBuf),
+}
+
+/// Append signatures made with `tx sign --signature-only` to a transaction envelope, checking
+/// each against the transaction hash and the signers' keys
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Files with a base64 decorated signature each
+    #[arg(long = "signature", value_name = "FILE", required = true, num_args = 1..)]
+    pub signatures: Vec<PathBuf>,
+    /// Keys that are expected to have made the signatures, as identities or public keys, e.g.
+    /// `--signer alice,GDKW...`. Each signature must be valid for one of them
+    #[arg(long, required = true, num_args = 1.., value_delimiter = ',')]
+    pub signer: Vec<UnresolvedMuxedAccount>,
+    /// File to read the base64 transaction envelope from, instead of stdin
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let tx_env = match &self.input {
+            Some(input) => super::xdr::tx_envelope_from_file(input)?,
+            None => super::xdr::tx_envelope_from_stdin()?,
+        };
+        let network = self.network.get_offline(&self.locator)?;
+        let (tx_hash, existing) = signature_payload_hash(&tx_env, &network)?;
+        let signers = self.signer_keys()?;
+
+        let mut signatures: Vec<DecoratedSignature> = Vec::new();
+        for path in &self.signatures {
+            let signature = read_signature(path)?;
+            if !signers
+                .iter()
+                .any(|key| is_signed_by(&signature, key, &tx_hash))
+            {
+                return Err(Error::InvalidSignature(path.clone()));
+            }
+            if existing.contains(&signature) || signatures.contains(&signature) {
+                print.warnln(format!(
+                    "Skipping {}: the envelope already has this signature",
+                    path.display()
+                ));
+                continue;
+            }
+            signatures.push(signature);
+        }
+
+        let tx_env = append_signatures(&tx_env, signatures)?;
+        println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+        Ok(())
+    }
+
+    fn signer_keys(&self) -> Result<Vec<[u8; 32]>, Error> {
+        self.signer
+            .iter()
+            .map(|signer| {
+                Ok(match signer.resolve_muxed_account(&self.locator, None)? {
+                    MuxedAccount::Ed25519(xdr::Uint256(key)) => key,
+                    MuxedAccount::MuxedEd25519(muxed) => muxed.ed25519.0,
+                })
+            })
+            .collect()
+    }
+}
+
+fn read_signature(path: &Path) -> Result<DecoratedSignature, Error> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::ReadingSignature(path.to_path_buf(), e))?;
+    DecoratedSignature::from_xdr_base64(content.trim(), Limits::none())
+        .map_err(|_| Error::SignatureDecode(path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signer::LocalKey;
+
+    use super::*;
+
+    #[test]
+    fn signature_from_file() {
+        let key = LocalKey {
+            key: ed25519_dalek::SigningKey::from_bytes(&[1; 32]),
+            prompt: false,
+        };
+        let tx_hash = [7; 32];
+        let signature = key.sign_tx_hash(tx_hash).unwrap();
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let path = temp_dir.path().join("sig");
+        std::fs::write(
+            &path,
+            format!("{}\n", signature.to_xdr_base64(Limits::none()).unwrap()),
+        )
+        .unwrap();
+
+        let read = read_signature(&path).unwrap();
+        assert_eq!(read, signature);
+        let public_key = key.key.verifying_key().to_bytes();
+        assert!(is_signed_by(&read, &public_key, &tx_hash));
+        assert!(!is_signed_by(&read, &public_key, &[8; 32]));
+
+        std::fs::write(&path, "not a signature").unwrap();
+        assert!(matches!(
+            read_signature(&path),
+            Err(Error::SignatureDecode(_))
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    commands::{tx, HEADING_RPC},
+    config::{self, address::UnresolvedMuxedAccount, locator, network},
+    fee,
+    tx::builder::memo,
+    xdr::{self, Limits, WriteXdr},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Tx(#[from] tx::args::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error("--seq-num is required to build a transaction without a network, pass it or use --network to fetch the sequence number of the source account")]
+    MissingSeqNum,
+}
+
+/// The options of [`tx::args::Args`] that apply to building a transaction: the config, the fee
+/// and the memo. The transaction is only built, so the options to simulate and send it are left
+/// out.
+#[derive(Debug, clap::Args, Clone)]
+#[group(skip)]
+pub struct Args {
+    /// Sequence number of the transaction, one more than the current sequence number of the
+    /// source account. Fetched from the network when omitted
+    #[arg(long)]
+    pub seq_num: Option<i64>,
+
+    /// fee amount for transaction, in stroops. 1 stroop = 0.0000001 xlm
+    #[arg(long, default_value = "100", env = "STELLAR_FEE", help_heading = HEADING_RPC)]
+    pub fee: u32,
+
+    #[command(flatten)]
+    pub network: network::Args,
+
+    #[arg(long, short = 's', visible_alias = "source", env = "STELLAR_ACCOUNT")]
+    /// Account that the transaction originates from, whose sequence number it uses. Alias
+    /// `source`. Can be an identity (--source alice), a public key (--source GDKW...), a muxed
+    /// account (--source MDA…), a secret key (--source SC36…), or a seed phrase (--source "kite
+    /// urban…"). The transaction isn't signed with it, sign it with `tx sign`.
+    /// If omitted, the default identity of the network set with `network use --identity` is used.
+    pub source_account: Option<UnresolvedMuxedAccount>,
+
+    #[arg(long)]
+    /// If using a seed phrase, which hierarchical deterministic path to use, e.g. `m/44'/148'/{hd_path}`. Example: `--hd-path 1`. Default: `0`
+    pub hd_path: Option<usize>,
+
+    #[command(flatten)]
+    pub locator: locator::Args,
+
+    #[command(flatten)]
+    pub memo: memo::Args,
+}
+
+impl Args {
+    /// The `tx new` args with the same config, fee and memo, to build the operation and the
+    /// transaction the same way.
+    pub fn tx(&self) -> tx::args::Args {
+        tx::args::Args {
+            fee: fee::Args {
+                fee: self.fee,
+                ..Default::default()
+            },
+            config: config::Args {
+                network: self.network.clone(),
+                source_account: self.source_account.clone(),
+                hd_path: self.hd_path,
+                locator: self.locator.clone(),
+            },
+            memo: self.memo.clone(),
+        }
+    }
+
+    /// Print the unsigned envelope of the transaction with the single operation `op_body`, as
+    /// base64.
+    pub async fn print(&self, op_body: impl Into<xdr::OperationBody>) -> Result<(), Error> {
+        let tx = self.tx();
+        let source_account = tx.source_account()?;
+        let seq_num = match self.seq_num {
+            Some(seq_num) => seq_num.into(),
+            None => match tx
+                .config
+                .next_sequence_number(source_account.clone().account_id())
+                .await
+            {
+                Err(config::Error::Network(network::Error::Network)) => {
+                    return Err(Error::MissingSeqNum)
+                }
+                seq_num => seq_num.map_err(tx::args::Error::from)?,
+            },
+        };
+        let tx_env: xdr::TransactionEnvelope =
+            tx.tx_with_seq_num(source_account, seq_num, op_body).into();
+        println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+        Ok(())
+    }
+}
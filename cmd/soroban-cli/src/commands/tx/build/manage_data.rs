@@ -0,0 +1,17 @@
+#[derive(clap::Parser, Debug, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub args: super::args::Args,
+    #[command(flatten)]
+    pub op: super::new::manage_data::Args,
+}
+
+impl From<&Cmd> for super::new::manage_data::Cmd {
+    fn from(Cmd { args, op }: &Cmd) -> Self {
+        Self {
+            tx: args.tx(),
+            op: op.clone(),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use super::help;
+use crate::xdr::OperationBody;
+
+pub(crate) use super::new;
+
+mod account_merge;
+mod args;
+mod bump_sequence;
+mod change_trust;
+mod create_account;
+mod manage_data;
+mod payment;
+mod set_options;
+mod set_trustline_flags;
+
+#[derive(Debug, clap::Parser)]
+#[allow(clippy::doc_markdown)]
+pub enum Cmd {
+    #[command(about = help::ACCOUNT_MERGE)]
+    AccountMerge(account_merge::Cmd),
+    #[command(about = help::BUMP_SEQUENCE)]
+    BumpSequence(bump_sequence::Cmd),
+    #[command(about = help::CHANGE_TRUST)]
+    ChangeTrust(change_trust::Cmd),
+    #[command(about = help::CREATE_ACCOUNT)]
+    CreateAccount(create_account::Cmd),
+    #[command(about = help::MANAGE_DATA)]
+    ManageData(manage_data::Cmd),
+    #[command(about = help::PAYMENT)]
+    Payment(payment::Cmd),
+    #[command(about = help::SET_OPTIONS)]
+    SetOptions(set_options::Cmd),
+    #[command(about = help::SET_TRUSTLINE_FLAGS)]
+    SetTrustlineFlags(set_trustline_flags::Cmd),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Args(#[from] args::Error),
+    #[error(transparent)]
+    New(#[from] new::Error),
+}
+
+impl TryFrom<&Cmd> for OperationBody {
+    type Error = new::Error;
+    fn try_from(cmd: &Cmd) -> Result<Self, Self::Error> {
+        Ok(match &cmd {
+            Cmd::AccountMerge(cmd) => (&new::account_merge::Cmd::from(cmd)).try_into()?,
+            Cmd::BumpSequence(cmd) => (&new::bump_sequence::Cmd::from(cmd)).into(),
+            Cmd::ChangeTrust(cmd) => (&new::change_trust::Cmd::from(cmd)).try_into()?,
+            Cmd::CreateAccount(cmd) => (&new::create_account::Cmd::from(cmd)).try_into()?,
+            Cmd::ManageData(cmd) => (&new::manage_data::Cmd::from(cmd)).into(),
+            Cmd::Payment(cmd) => (&new::payment::Cmd::from(cmd)).try_into()?,
+            Cmd::SetOptions(cmd) => (&new::set_options::Cmd::from(cmd)).try_into()?,
+            Cmd::SetTrustlineFlags(cmd) => {
+                (&new::set_trustline_flags::Cmd::from(cmd)).try_into()?
+            }
+        })
+    }
+}
+
+impl Cmd {
+    pub async fn run(&self) -> Result<(), Error> {
+        let op = OperationBody::try_from(self)?;
+        match self {
+            Cmd::AccountMerge(cmd) => cmd.args.print(op).await,
+            Cmd::BumpSequence(cmd) => cmd.args.print(op).await,
+            Cmd::ChangeTrust(cmd) => cmd.args.print(op).await,
+            Cmd::CreateAccount(cmd) => cmd.args.print(op).await,
+            Cmd::ManageData(cmd) => cmd.args.print(op).await,
+            Cmd::Payment(cmd) => cmd.args.print(op).await,
+            Cmd::SetOptions(cmd) => cmd.args.print(op).await,
+            Cmd::SetTrustlineFlags(cmd) => cmd.args.print(op).await,
+        }?;
+        Ok(())
+    }
+}
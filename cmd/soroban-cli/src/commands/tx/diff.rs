@@ -0,0 +1,167 @@
+use std::{collections::BTreeSet, fmt, path::PathBuf};
+
+use serde_json::Value;
+
+use crate::{commands::global, print::Print, xdr::TransactionEnvelope};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Xdr(#[from] super::xdr::Error),
+    #[error("converting the envelope to JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("two envelopes are required, either as two files or as stdin and `--against`")]
+    MissingEnvelope,
+    #[error("`--against` takes the place of the first file, pass at most one file with it")]
+    TooManyEnvelopes,
+}
+
+/// Compare two transaction envelopes field by field, e.g. the input and output of `tx simulate`.
+/// Changes are printed one per line: `~ path: old -> new`, `+ path: new` or `- path: old`
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// Files with the base64 envelopes to compare, the original one first
+    #[arg(num_args = 0..=2, value_name = "FILE")]
+    pub files: Vec<PathBuf>,
+    /// File with the original envelope to compare against. The changed envelope is then read from
+    /// the file given, or from stdin
+    #[arg(long, value_name = "FILE")]
+    pub against: Option<PathBuf>,
+}
+
+impl Cmd {
+    pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let (old, new) = self.envelopes()?;
+        let changes = diff(&serde_json::to_value(&old)?, &serde_json::to_value(&new)?);
+        if changes.is_empty() {
+            Print::new(global_args.quiet).checkln("The envelopes are identical");
+        }
+        for change in changes {
+            println!("{change}");
+        }
+        Ok(())
+    }
+
+    fn envelopes(&self) -> Result<(TransactionEnvelope, TransactionEnvelope), Error> {
+        let envelope = |path: &PathBuf| super::xdr::tx_envelope_from_file(path);
+        match (&self.against, self.files.as_slice()) {
+            (None, [old, new]) | (Some(old), [new]) => Ok((envelope(old)?, envelope(new)?)),
+            (None, _) => Err(Error::MissingEnvelope),
+            (Some(old), []) => Ok((envelope(old)?, super::xdr::tx_envelope_from_stdin()?)),
+            (Some(_), _) => Err(Error::TooManyEnvelopes),
+        }
+    }
+}
+
+/// A difference between two JSON trees, at a path such as `tx.tx.operations[0].body`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change {
+    Added(String, Value),
+    Removed(String, Value),
+    Changed(String, Value, Value),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added(path, new) => write!(f, "+ {path}: {new}"),
+            Change::Removed(path, old) => write!(f, "- {path}: {old}"),
+            Change::Changed(path, old, new) => write!(f, "~ {path}: {old} -> {new}"),
+        }
+    }
+}
+
+/// Structural diff of two JSON trees. Objects are compared key by key and arrays index by index,
+/// anything else is reported as changed as a whole.
+pub fn diff(old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_at("", old, new, &mut changes);
+    changes
+}
+
+fn diff_at(path: &str, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let key_path = |key: &str| {
+                if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                }
+            };
+            // Keys are visited in sorted order so the output doesn't depend on the map
+            // implementation.
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                diff_entry(key_path(key), old.get(key), new.get(key), changes);
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for i in 0..old.len().max(new.len()) {
+                diff_entry(format!("{path}[{i}]"), old.get(i), new.get(i), changes);
+            }
+        }
+        (old, new) if old != new => {
+            changes.push(Change::Changed(path.to_string(), old.clone(), new.clone()));
+        }
+        _ => {}
+    }
+}
+
+fn diff_entry(path: String, old: Option<&Value>, new: Option<&Value>, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Some(old), Some(new)) => diff_at(&path, old, new, changes),
+        (Some(old), None) => changes.push(Change::Removed(path, old.clone())),
+        (None, Some(new)) => changes.push(Change::Added(path, new.clone())),
+        (None, None) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn identical() {
+        let value = json!({"tx": {"fee": 100, "operations": [{"body": "inflation"}]}});
+        assert_eq!(diff(&value, &value), vec![]);
+    }
+
+    #[test]
+    fn nested_changes() {
+        let old = json!({
+            "tx": {
+                "fee": 100,
+                "operations": [{"body": "inflation"}],
+                "ext": "v0",
+                "memo": "none",
+            }
+        });
+        let new = json!({
+            "tx": {
+                "fee": 12345,
+                "operations": [{"body": "inflation"}, {"body": "inflation"}],
+                "ext": {"v1": {"resource_fee": 100}},
+            }
+        });
+        let changes = diff(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                Change::Changed(
+                    "tx.ext".to_string(),
+                    json!("v0"),
+                    json!({"v1": {"resource_fee": 100}})
+                ),
+                Change::Changed("tx.fee".to_string(), json!(100), json!(12345)),
+                Change::Removed("tx.memo".to_string(), json!("none")),
+                Change::Added("tx.operations[1]".to_string(), json!({"body": "inflation"})),
+            ]
+        );
+        assert_eq!(changes[1].to_string(), "~ tx.fee: 100 -> 12345");
+        assert_eq!(changes[2].to_string(), "- tx.memo: \"none\"");
+    }
+}
@@ -1,6 +1,11 @@
 use hex;
 
-use crate::{commands::global, config::network, utils::transaction_hash};
+use crate::{
+    commands::global,
+    config::network,
+    utils::{fee_bump_transaction_hash, transaction_hash},
+    xdr::{FeeBumpTransactionEnvelope, TransactionEnvelope},
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -23,12 +28,18 @@ pub struct Cmd {
 
 impl Cmd {
     pub fn run(&self, global_args: &global::Args) -> Result<(), Error> {
-        let tx = super::xdr::unwrap_envelope_v1(super::xdr::tx_envelope_from_stdin()?)?;
-        let network = &self.network.get(&global_args.locator)?;
-        println!(
-            "{}",
-            hex::encode(transaction_hash(&tx, &network.network_passphrase)?)
-        );
+        let tx_env = super::xdr::tx_envelope_from_stdin()?;
+        let network = &self.network.get_offline(&global_args.locator)?;
+        let hash = match tx_env {
+            TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, .. }) => {
+                fee_bump_transaction_hash(&tx, &network.network_passphrase)?
+            }
+            tx_env => transaction_hash(
+                &super::xdr::unwrap_envelope_v1(tx_env)?,
+                &network.network_passphrase,
+            )?,
+        };
+        println!("{}", hex::encode(hash));
         Ok(())
     }
 }
@@ -0,0 +1,362 @@
+use std::fmt::Write;
+
+use soroban_env_host::fees::{
+    compute_transaction_resource_fee, FeeConfiguration, TransactionResources,
+};
+use soroban_ledger_snapshot::LedgerSnapshot;
+
+use crate::{
+    commands::{global, tx::simulate::snapshot},
+    config::network,
+    rpc,
+    tx::builder::memo,
+    xdr::{
+        self, ConfigSettingId, FeeBumpTransactionEnvelope, FeeBumpTransactionInnerTx, LedgerEntry,
+        LedgerEntryExt, LedgerKey, LedgerKeyConfigSetting, Limits, Memo, Operation,
+        SorobanTransactionData, TransactionEnvelope, TransactionExt, TransactionV0Envelope,
+        TransactionV1Envelope, WriteXdr,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    TxEnvelopeFromStdin(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Fmt(#[from] std::fmt::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Snapshot(#[from] snapshot::Error),
+}
+
+/// Break down the fee of a transaction envelope from stdin, e.g.
+/// `cat file.txt | stellar tx inspect-fees`. This needs no network connection unless a network
+/// is given, which is used to split the resource fee into its non-refundable and refundable
+/// parts.
+#[derive(Debug, clap::Parser, Clone, Default)]
+#[group(skip)]
+pub struct Cmd {
+    #[command(flatten)]
+    pub network: network::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let tx_env = super::xdr::tx_envelope_from_stdin()?;
+        let fee_config = if self.network.network.is_some() || self.network.rpc_url.is_some() {
+            Some(self.fee_config(global_args).await?)
+        } else {
+            None
+        };
+        print!("{}", report(&tx_env, fee_config.as_ref())?);
+        Ok(())
+    }
+
+    /// The fee configuration of the network, from its config setting entries.
+    async fn fee_config(&self, global_args: &global::Args) -> Result<FeeConfiguration, Error> {
+        let network = self.network.get(&global_args.locator)?;
+        let client = network
+            .checked_rpc_client(self.network.rpc_options())
+            .await?;
+        let keys = ConfigSettingId::variants()
+            .into_iter()
+            .map(|config_setting_id| {
+                LedgerKey::ConfigSetting(LedgerKeyConfigSetting { config_setting_id })
+            })
+            .collect::<Vec<_>>();
+        let response = network
+            .with_rpc_timeout(client.get_full_ledger_entries(&keys))
+            .await??;
+        let snapshot = LedgerSnapshot {
+            ledger_entries: response
+                .entries
+                .into_iter()
+                .map(|entry| {
+                    let ledger_entry = LedgerEntry {
+                        last_modified_ledger_seq: entry.last_modified_ledger,
+                        data: entry.val,
+                        ext: LedgerEntryExt::V0,
+                    };
+                    (Box::new(entry.key), (Box::new(ledger_entry), None))
+                })
+                .collect(),
+            ..LedgerSnapshot::default()
+        };
+        Ok(snapshot::network_config(snapshot)?.fee_configuration)
+    }
+}
+
+/// Human readable breakdown of the fee of the envelope, ending with a new line. With the fee
+/// configuration of the network, the resource fee is split into its parts.
+pub fn report(
+    tx_env: &TransactionEnvelope,
+    fee_config: Option<&FeeConfiguration>,
+) -> Result<String, Error> {
+    let tx_size = u32::try_from(tx_env.to_xdr(Limits::none())?.len()).unwrap_or(u32::MAX);
+    let fee_config = fee_config.map(|config| (config, tx_size));
+    let mut out = String::new();
+    match tx_env {
+        TransactionEnvelope::TxV0(TransactionV0Envelope { tx, .. }) => {
+            report_tx(&mut out, tx.fee, &tx.operations, None, &tx.memo, None)?;
+        }
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => {
+            let soroban_data = match &tx.ext {
+                TransactionExt::V0 => None,
+                TransactionExt::V1(data) => Some(data),
+            };
+            report_tx(
+                &mut out,
+                tx.fee,
+                &tx.operations,
+                soroban_data,
+                &tx.memo,
+                fee_config,
+            )?;
+        }
+        TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, .. }) => {
+            writeln!(
+                out,
+                "Fee bump: {} stroops, paid by {}",
+                tx.fee, tx.fee_source
+            )?;
+            let FeeBumpTransactionInnerTx::Tx(TransactionV1Envelope { tx: inner, .. }) =
+                &tx.inner_tx;
+            let soroban_data = match &inner.ext {
+                TransactionExt::V0 => None,
+                TransactionExt::V1(data) => Some(data),
+            };
+            writeln!(out, "Inner transaction:")?;
+            report_tx(
+                &mut out,
+                inner.fee,
+                &inner.operations,
+                soroban_data,
+                &inner.memo,
+                fee_config,
+            )?;
+        }
+    }
+    Ok(out)
+}
+
+fn report_tx(
+    out: &mut String,
+    fee: u32,
+    operations: &[Operation],
+    soroban_data: Option<&SorobanTransactionData>,
+    memo: &Memo,
+    fee_config: Option<(&FeeConfiguration, u32)>,
+) -> Result<(), Error> {
+    writeln!(out, "Fee: {fee} stroops")?;
+    if let Some(data) = soroban_data {
+        let inclusion_fee = i64::from(fee) - data.resource_fee;
+        writeln!(out, "  Inclusion fee: {inclusion_fee} stroops")?;
+        writeln!(
+            out,
+            "  Resource fee: {} stroops, a maximum from which the unused refundable part is refunded",
+            data.resource_fee
+        )?;
+        if let Some((fee_config, tx_size)) = fee_config {
+            let non_refundable = non_refundable_fee(data, fee_config, tx_size);
+            writeln!(out, "    Non-refundable: {non_refundable} stroops")?;
+            writeln!(
+                out,
+                "    Refundable: {} stroops, for rent and events",
+                (data.resource_fee - non_refundable).max(0)
+            )?;
+        }
+        let resources = &data.resources;
+        writeln!(out, "Resources:")?;
+        writeln!(out, "  Instructions: {}", resources.instructions)?;
+        writeln!(out, "  Read bytes: {}", resources.read_bytes)?;
+        writeln!(out, "  Write bytes: {}", resources.write_bytes)?;
+        writeln!(
+            out,
+            "  Read-only entries: {}",
+            resources.footprint.read_only.len()
+        )?;
+        writeln!(
+            out,
+            "  Read-write entries: {}",
+            resources.footprint.read_write.len()
+        )?;
+        writeln!(out, "Operations: {}", operations.len())?;
+    } else {
+        // The inclusion fee of a classic transaction is shared equally by its operations
+        let per_op = u32::try_from(operations.len())
+            .ok()
+            .filter(|count| *count > 0)
+            .map_or(0, |count| fee / count);
+        writeln!(
+            out,
+            "Operations: {}, {per_op} stroops each",
+            operations.len()
+        )?;
+    }
+    for (i, op) in operations.iter().enumerate() {
+        writeln!(out, "  {i}: {}", op.body.name())?;
+    }
+    if let Some(memo) = memo::display(memo) {
+        writeln!(out, "Memo: {memo}")?;
+    }
+    Ok(())
+}
+
+/// The part of the resource fee that is charged whatever the transaction uses, for the declared
+/// resources and the size of the transaction. The rest of the resource fee pays for rent and
+/// events, and what is not used of it is refunded.
+fn non_refundable_fee(
+    data: &SorobanTransactionData,
+    fee_config: &FeeConfiguration,
+    tx_size: u32,
+) -> i64 {
+    let resources = &data.resources;
+    let read_only = u32::try_from(resources.footprint.read_only.len()).unwrap_or(u32::MAX);
+    let read_write = u32::try_from(resources.footprint.read_write.len()).unwrap_or(u32::MAX);
+    let tx_resources = TransactionResources {
+        instructions: resources.instructions,
+        read_entries: read_only.saturating_add(read_write),
+        write_entries: read_write,
+        read_bytes: resources.read_bytes,
+        write_bytes: resources.write_bytes,
+        contract_events_size_bytes: 0,
+        transaction_size_bytes: tx_size,
+    };
+    compute_transaction_resource_fee(&tx_resources, fee_config).0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xdr::{
+        ExtensionPoint, Hash, LedgerFootprint, MuxedAccount, OperationBody, Preconditions,
+        SequenceNumber, SorobanResources, Transaction, Uint256, VecM,
+    };
+
+    use super::*;
+
+    fn tx(fee: u32, operations: usize, ext: TransactionExt) -> TransactionEnvelope {
+        tx_with_memo(fee, operations, ext, Memo::None)
+    }
+
+    fn tx_with_memo(
+        fee: u32,
+        operations: usize,
+        ext: TransactionExt,
+        memo: Memo,
+    ) -> TransactionEnvelope {
+        let op = Operation {
+            source_account: None,
+            body: OperationBody::Inflation,
+        };
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+                fee,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo,
+                operations: vec![op; operations].try_into().unwrap(),
+                ext,
+            },
+            signatures: VecM::default(),
+        })
+    }
+
+    #[test]
+    fn classic() {
+        assert_eq!(
+            report(&tx(200, 2, TransactionExt::V0), None).unwrap(),
+            "Fee: 200 stroops\n\
+             Operations: 2, 100 stroops each\n\
+             \x20 0: Inflation\n\
+             \x20 1: Inflation\n"
+        );
+    }
+
+    #[test]
+    fn soroban() {
+        let data = SorobanTransactionData {
+            ext: ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: VecM::default(),
+                },
+                instructions: 1000,
+                read_bytes: 20,
+                write_bytes: 10,
+            },
+            resource_fee: 900,
+        };
+        assert_eq!(
+            report(&tx(1000, 1, TransactionExt::V1(data.clone())), None).unwrap(),
+            "Fee: 1000 stroops\n\
+             \x20 Inclusion fee: 100 stroops\n\
+             \x20 Resource fee: 900 stroops, a maximum from which the unused refundable part is refunded\n\
+             Resources:\n\
+             \x20 Instructions: 1000\n\
+             \x20 Read bytes: 20\n\
+             \x20 Write bytes: 10\n\
+             \x20 Read-only entries: 0\n\
+             \x20 Read-write entries: 0\n\
+             Operations: 1\n\
+             \x20 0: Inflation\n"
+        );
+
+        // Only the instructions and bytes are charged, 10 stroops per 1000 instructions and
+        // 1 stroop per byte.
+        let fee_config = FeeConfiguration {
+            fee_per_instruction_increment: 100,
+            fee_per_read_entry: 0,
+            fee_per_write_entry: 0,
+            fee_per_read_1kb: 1024,
+            fee_per_write_1kb: 1024,
+            fee_per_historical_1kb: 0,
+            fee_per_contract_event_1kb: 0,
+            fee_per_transaction_size_1kb: 0,
+        };
+        assert!(
+            report(&tx(1000, 1, TransactionExt::V1(data)), Some(&fee_config))
+                .unwrap()
+                .contains(
+                    "\x20 Resource fee: 900 stroops, a maximum from which the unused refundable part is refunded\n\
+                     \x20   Non-refundable: 40 stroops\n\
+                     \x20   Refundable: 860 stroops, for rent and events\n"
+                )
+        );
+    }
+
+    #[test]
+    fn memos() {
+        for (memo, line) in [
+            (
+                Memo::Text("hello".try_into().unwrap()),
+                r#"text "hello""#.to_string(),
+            ),
+            (Memo::Id(7), "id 7".to_string()),
+            (
+                Memo::Hash(Hash([0xab; 32])),
+                format!("hash {}", "ab".repeat(32)),
+            ),
+            (
+                Memo::Return(Hash([0xcd; 32])),
+                format!("return {}", "cd".repeat(32)),
+            ),
+        ] {
+            assert_eq!(
+                report(&tx_with_memo(100, 1, TransactionExt::V0, memo), None).unwrap(),
+                format!(
+                    "Fee: 100 stroops\n\
+                     Operations: 1, 100 stroops each\n\
+                     \x20 0: Inflation\n\
+                     Memo: {line}\n"
+                )
+            );
+        }
+    }
+}
@@ -1,44 +1,76 @@
 use super::global;
 
 pub mod args;
+pub mod attach_sig;
+pub mod build;
+pub mod diff;
 pub mod hash;
 pub mod help;
+pub mod inspect_fees;
 pub mod new;
 pub mod op;
+pub mod rebuild_footprint;
 pub mod send;
 pub mod sign;
 pub mod simulate;
+pub mod weights;
 pub mod xdr;
 
 pub use args::Args;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Cmd {
+    /// Append signatures made with `tx sign --signature-only` to a transaction envelope
+    AttachSig(attach_sig::Cmd),
+    /// Build a transaction envelope and write it unsigned to stdout, e.g. to sign with `tx sign`
+    /// and submit with `tx send`. Works offline when `--seq-num` is given
+    #[command(subcommand)]
+    Build(build::Cmd),
+    /// Compare two transaction envelopes field by field, e.g. the input and output of `tx simulate`
+    Diff(diff::Cmd),
     /// Calculate the hash of a transaction envelope from stdin
     Hash(hash::Cmd),
+    /// Break down the fee of a transaction envelope from stdin into its inclusion and resource fees
+    InspectFees(inspect_fees::Cmd),
     /// Create a new transaction
     #[command(subcommand)]
     New(new::Cmd),
     /// Manipulate the operations in a transaction, including adding new operations
     #[command(subcommand, visible_alias = "op")]
     Operation(op::Cmd),
+    /// Replace the footprint and resources of a transaction envelope with the ones computed by
+    /// simulating it, keeping the signatures that are still valid
+    RebuildFootprint(rebuild_footprint::Cmd),
     /// Send a transaction envelope to the network
     Send(send::Cmd),
     /// Sign a transaction envelope appending the signature to the envelope
     Sign(sign::Cmd),
     /// Simulate a transaction envelope from stdin
     Simulate(simulate::Cmd),
+    /// Convert values to and from XDR
+    #[command(subcommand)]
+    Xdr(xdr::Cmd),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    #[error(transparent)]
+    AttachSig(#[from] attach_sig::Error),
+    #[error(transparent)]
+    Build(#[from] build::Error),
+    #[error(transparent)]
+    Diff(#[from] diff::Error),
     #[error(transparent)]
     Hash(#[from] hash::Error),
     #[error(transparent)]
+    InspectFees(#[from] inspect_fees::Error),
+    #[error(transparent)]
     New(#[from] new::Error),
     #[error(transparent)]
     Op(#[from] op::Error),
     #[error(transparent)]
+    RebuildFootprint(#[from] rebuild_footprint::Error),
+    #[error(transparent)]
     Send(#[from] send::Error),
     #[error(transparent)]
     Sign(#[from] sign::Error),
@@ -46,17 +78,25 @@ pub enum Error {
     Args(#[from] args::Error),
     #[error(transparent)]
     Simulate(#[from] simulate::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::scval::Error),
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
         match self {
+            Cmd::AttachSig(cmd) => cmd.run(global_args)?,
+            Cmd::Build(cmd) => cmd.run().await?,
+            Cmd::Diff(cmd) => cmd.run(global_args)?,
             Cmd::Hash(cmd) => cmd.run(global_args)?,
+            Cmd::InspectFees(cmd) => cmd.run(global_args).await?,
             Cmd::New(cmd) => cmd.run(global_args).await?,
             Cmd::Operation(cmd) => cmd.run(global_args)?,
+            Cmd::RebuildFootprint(cmd) => cmd.run(global_args).await?,
             Cmd::Send(cmd) => cmd.run(global_args).await?,
             Cmd::Sign(cmd) => cmd.run(global_args).await?,
             Cmd::Simulate(cmd) => cmd.run(global_args).await?,
+            Cmd::Xdr(cmd) => cmd.run()?,
         };
         Ok(())
     }
@@ -16,7 +16,8 @@ pub struct Args {
     /// Account to send to, e.g. `GBX...`
     #[arg(long)]
     pub destination: address::UnresolvedMuxedAccount,
-    /// Asset to send, default native, e.i. XLM
+    /// Asset to send, default native, e.i. XLM. Other assets are given as `CODE:ISSUER`, where
+    /// `CODE` is 1 to 12 letters and digits and `ISSUER` is a `G...` public key or an identity
     #[arg(long, default_value = "native")]
     pub asset: builder::Asset,
     /// Amount of the aforementioned asset to send. e.g. `10_000_000` (1 XLM)
@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+
+use crate::{
+    commands::global,
+    config::{locator, network},
+    print::Print,
+    rpc,
+    utils::transaction_hash,
+    xdr::{
+        self, Limits, TransactionEnvelope, TransactionExt, TransactionV1Envelope, VecM, WriteXdr,
+    },
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    XdrArgs(#[from] super::xdr::Error),
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Locator(#[from] locator::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("simulation did not return any Soroban resources for this transaction")]
+    MissingResources,
+}
+
+/// Replace the footprint and resources of a transaction envelope with the ones computed by
+/// simulating it, leaving the operations, auth entries, fee and sequence number untouched
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+pub struct Cmd {
+    /// File to read the base64 transaction envelope from, instead of stdin
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+    #[command(flatten)]
+    pub network: network::Args,
+    #[command(flatten)]
+    pub locator: locator::Args,
+}
+
+impl Cmd {
+    pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        let print = Print::new(global_args.quiet);
+        let tx_env = match &self.input {
+            Some(input) => super::xdr::tx_envelope_from_file(input)?,
+            None => super::xdr::tx_envelope_from_stdin()?,
+        };
+        let TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) = tx_env else {
+            return Err(super::xdr::Error::OnlyTransactionV1Supported.into());
+        };
+        let network = self.network.get(&self.locator)?;
+        let client = network
+            .checked_rpc_client(self.network.rpc_options())
+            .await?;
+        let unsigned = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: tx.clone(),
+            signatures: VecM::default(),
+        });
+        crate::log::rpc::request("simulateTransaction", &unsigned);
+        let sim_res = network
+            .with_rpc_timeout(client.simulate_transaction_envelope(&unsigned))
+            .await??;
+        crate::log::rpc::simulate_response(&sim_res);
+        if let Some(e) = &sim_res.error {
+            crate::log::event::failure(&sim_res.events()?, &print)?;
+            return Err(rpc::Error::TransactionSimulationFailed(e.clone()).into());
+        }
+        let transaction_data = sim_res
+            .transaction_data()
+            .map_err(|_| Error::MissingResources)?;
+        if i64::from(tx.fee) < transaction_data.resource_fee {
+            print.warnln(format!(
+                "The fee of {} stroops is below the new resource fee of {} stroops",
+                tx.fee, transaction_data.resource_fee
+            ));
+        }
+
+        let rebuilt = xdr::Transaction {
+            ext: TransactionExt::V1(transaction_data),
+            ..tx.clone()
+        };
+        let signatures = if signatures.is_empty()
+            || transaction_hash(&tx, &network.network_passphrase)?
+                == transaction_hash(&rebuilt, &network.network_passphrase)?
+        {
+            signatures
+        } else {
+            print.warnln(format!(
+                "Removed {} signature(s) that are no longer valid for the rebuilt transaction, \
+                sign it again",
+                signatures.len()
+            ));
+            VecM::default()
+        };
+        let tx_env = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: rebuilt,
+            signatures,
+        });
+        println!("{}", tx_env.to_xdr_base64(Limits::none())?);
+        Ok(())
+    }
+}
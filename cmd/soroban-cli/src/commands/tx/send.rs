@@ -1,10 +1,28 @@
-use crate::{print::Print, utils::transaction_hash};
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::{
+    log::extract_events,
+    output::{self, Output},
+    print::Print,
+    rpc, signer,
+    utils::{fee_bump_transaction_hash, transaction_hash},
+    xdr::{
+        self, Limits, ReadXdr, SorobanTransactionData, TransactionEnvelope, TransactionExt,
+        TransactionV1Envelope, VecM,
+    },
+};
 use async_trait::async_trait;
 use soroban_rpc::GetTransactionResponse;
+use soroban_spec_tools::Spec;
 
+use super::weights::Weights;
 use crate::{
     commands::{global, NetworkRunnable},
-    config::{self, locator, network},
+    config::{self, locator, network, sign_with},
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -19,6 +37,49 @@ pub enum Error {
     Rpc(#[from] crate::rpc::Error),
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Output(#[from] output::Error),
+    #[error(transparent)]
+    SignWith(#[from] sign_with::Error),
+    #[error("`--fee-retry` needs a key to sign the transaction again after raising its fee, e.g. `--sign-with-key`")]
+    FeeRetryRequiresSigner,
+    #[error("raising the fee to {0} stroops exceeds the maximum fee of a transaction")]
+    FeeTooLarge(i64),
+    #[error("`--fee-retry` can't raise the fee of a transaction signed by keys other than the `--sign-with-*` one, as their signatures would no longer be valid")]
+    FeeRetryDropsSignatures,
+    #[error("transaction submission failed: {0:#?}")]
+    SubmissionFailed(Box<xdr::TransactionResultResult>),
+    #[error("transaction submission failed without a transaction result")]
+    MissingErrorResult,
+    #[error("the RPC server didn't accept the transaction for now, send it again later or resend it with a higher fee with `--fee-retry`")]
+    TryAgainLater,
+    #[error("unexpected sendTransaction status {0}")]
+    UnexpectedSendStatus(String),
+    #[error("transaction was not sent")]
+    Aborted,
+    #[error("failed to decode the envelope on line {0}")]
+    EnvelopeDecode(usize),
+    #[error("no transaction envelope to send")]
+    NoEnvelope,
+    #[error(transparent)]
+    Weights(#[from] super::weights::Error),
+    #[error("signature weight {signed} of source account {account} is below the threshold {threshold} required by the transaction's operations, signers still needed: {needed}")]
+    InsufficientSignatures {
+        account: xdr::AccountId,
+        signed: u32,
+        threshold: u32,
+        needed: String,
+    },
+    #[error("envelope {index} failed, the envelopes after it were not sent: {source}")]
+    Envelope {
+        index: usize,
+        #[source]
+        source: Box<Error>,
+    },
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -30,14 +91,312 @@ pub struct Cmd {
     pub network: network::Args,
     #[clap(flatten)]
     pub locator: locator::Args,
+    /// Print the transaction hash and a summary of its operations, then exit without sending it
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Send the transaction without asking for confirmation when stdin is a terminal
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+    /// File with base64 envelopes to send in order, one per line. Several envelopes can also be
+    /// given on stdin, one per line. Sending stops at the first envelope that fails
+    #[arg(long, value_name = "FILE")]
+    pub batch: Option<PathBuf>,
+    /// Send each envelope of a batch without waiting for the previous one to be applied. Only
+    /// use it when the envelopes don't depend on each other, e.g. on sequence numbers
+    #[arg(long)]
+    pub no_wait: bool,
+    /// Resend a transaction rejected for an insufficient fee, or not accepted for now with
+    /// `TRY_AGAIN_LATER`, up to this many times, each time simulating it again to refresh its
+    /// resource fee, multiplying its inclusion fee by `--fee-retry-factor` and signing it again
+    /// with the `--sign-with-*` key. Fails if the transaction is also signed by other keys. This
+    /// can increase the fee paid. Off by default
+    #[arg(long, value_name = "ATTEMPTS")]
+    pub fee_retry: Option<u32>,
+    /// Factor the inclusion fee is multiplied by on each `--fee-retry` attempt, at least 2
+    #[arg(
+        long,
+        default_value = "2",
+        requires = "fee_retry",
+        value_parser = clap::value_parser!(u32).range(2..)
+    )]
+    pub fee_retry_factor: u32,
+    /// Before sending, fetch the source account's signers and thresholds and fail without
+    /// sending if the weight of the envelope's signatures is below what its operations require,
+    /// listing the signers still needed
+    #[arg(long)]
+    pub verify_weights: bool,
+    #[command(flatten)]
+    pub sign_with: sign_with::Args,
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
-        let response = self.run_against_rpc_server(Some(global_args), None).await?;
-        println!("{}", serde_json::to_string_pretty(&response)?);
+        let print = Print::new(global_args.quiet);
+        let network = self.network.get(&self.locator)?;
+        if self.fee_retry.is_some() && !self.has_signer() {
+            return Err(Error::FeeRetryRequiresSigner);
+        }
+        let envelopes = self.envelopes()?;
+        if self.dry_run {
+            for tx_env in &envelopes {
+                print_summary(tx_env, &network, &print)?;
+            }
+            return Ok(());
+        }
+        self.confirm(envelopes.len(), &network, &print)?;
+        match envelopes.as_slice() {
+            [tx_env] if self.batch.is_none() => {
+                let (_, response) = self.send(tx_env, &network, &print).await?;
+                if let Ok(val) = response.return_value() {
+                    print.infoln(format!(
+                        "Return value: {}",
+                        output::render(&val, &Spec::default(), None, Output::Json)?
+                    ));
+                }
+                println!("{}", serde_json::to_string_pretty(&response)?);
+            }
+            _ => self.send_batch(&envelopes, &network, &print).await?,
+        }
         Ok(())
     }
+
+    /// Ask once on the terminal, before anything is sent and whatever `--fee-retry` resends,
+    /// whether to send the `count` envelopes, unless `--yes` is given.
+    fn confirm(
+        &self,
+        count: usize,
+        network: &network::Network,
+        print: &Print,
+    ) -> Result<(), Error> {
+        let what = if count == 1 {
+            "this transaction".to_string()
+        } else {
+            format!("these {count} transactions")
+        };
+        if self.yes
+            || print.confirm_or(
+                &format!("Send {what} to \"{}\"?", network.network_passphrase),
+                true,
+            )?
+        {
+            Ok(())
+        } else {
+            Err(Error::Aborted)
+        }
+    }
+
+    /// The envelopes from `--batch` or stdin, one per line.
+    fn envelopes(&self) -> Result<Vec<xdr::TransactionEnvelope>, Error> {
+        let input = if let Some(path) = &self.batch {
+            fs::read_to_string(path)?
+        } else {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        };
+        let envelopes = decode_envelopes(&input)?;
+        if envelopes.is_empty() {
+            return Err(Error::NoEnvelope);
+        }
+        Ok(envelopes)
+    }
+
+    /// Send the envelopes in order, printing a `index hash status` line for each and stopping
+    /// at the first failure.
+    async fn send_batch(
+        &self,
+        envelopes: &[xdr::TransactionEnvelope],
+        network: &network::Network,
+        print: &Print,
+    ) -> Result<(), Error> {
+        // Check the RPC server up front, with `--no-wait` nothing else does before sending
+        network
+            .checked_rpc_client(self.network.rpc_options())
+            .await?;
+        for (index, tx_env) in envelopes.iter().enumerate() {
+            let result = if self.no_wait {
+                async {
+                    self.verify_weights(tx_env, network).await?;
+                    crate::log::rpc::request("sendTransaction", tx_env);
+                    let response = network.send_transaction(tx_env).await?;
+                    print_rejection_events(&response, print)?;
+                    let hash = accepted(&response)?;
+                    Ok::<_, Error>((hex::encode(hash), response.status))
+                }
+                .await
+            } else {
+                self.send(tx_env, network, print)
+                    .await
+                    .map(|(sent, response)| (tx_hash(&sent, network), response.status))
+            };
+            match result {
+                Ok((hash, status)) => println!("{index} {hash} {status}"),
+                Err(e) => {
+                    println!("{index} {} FAILED", tx_hash(tx_env, network));
+                    return Err(Error::Envelope {
+                        index,
+                        source: Box::new(e),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn has_signer(&self) -> bool {
+        let sign_with::Args {
+            sign_with_key,
+            sign_with_key_stdin,
+            signers,
+            sign_with_lab,
+            ..
+        } = &self.sign_with;
+        sign_with_key.is_some() || *sign_with_key_stdin || !signers.is_empty() || *sign_with_lab
+    }
+
+    /// Send `tx_env`, resending it with a higher fee while it is rejected for an insufficient
+    /// fee or not accepted for now, and `--fee-retry` attempts are left. Returns the envelope
+    /// that was applied.
+    async fn send(
+        &self,
+        tx_env: &xdr::TransactionEnvelope,
+        network: &network::Network,
+        print: &Print,
+    ) -> Result<(xdr::TransactionEnvelope, GetTransactionResponse), Error> {
+        self.verify_weights(tx_env, network).await?;
+        let mut tx_env = tx_env.clone();
+        let mut attempts = self.fee_retry.unwrap_or_default();
+        loop {
+            match self.send_once(&tx_env, network, print).await {
+                Err(e) if attempts > 0 && is_insufficient_fee(&e) => {
+                    attempts -= 1;
+                    tx_env = self.raise_fee(&tx_env, network, print).await?;
+                }
+                res => return res.map(|response| (tx_env, response)),
+            }
+        }
+    }
+
+    /// With `--verify-weights`, check that the signatures of the transaction in `tx_env`, the
+    /// inner one of a fee bump, meet the threshold its source account requires.
+    async fn verify_weights(
+        &self,
+        tx_env: &xdr::TransactionEnvelope,
+        network: &network::Network,
+    ) -> Result<(), Error> {
+        if !self.verify_weights {
+            return Ok(());
+        }
+        let weights = Weights::fetch_envelope(tx_env, network, self.network.rpc_options()).await?;
+        if weights.is_met() {
+            return Ok(());
+        }
+        let needed = weights
+            .needed()
+            .iter()
+            .map(|(key, weight)| format!("{key} (weight {weight})"))
+            .collect::<Vec<_>>();
+        Err(Error::InsufficientSignatures {
+            account: weights.source,
+            signed: weights.signed,
+            threshold: weights.threshold,
+            needed: if needed.is_empty() {
+                "none of the account's ed25519 signers".to_string()
+            } else {
+                needed.join(", ")
+            },
+        })
+    }
+
+    /// `tx_env` simulated again for its resource fee, with its inclusion fee multiplied by
+    /// `--fee-retry-factor` and signed again. Fails if `tx_env` has signatures that signing
+    /// again doesn't replace.
+    async fn raise_fee(
+        &self,
+        tx_env: &xdr::TransactionEnvelope,
+        network: &network::Network,
+        print: &Print,
+    ) -> Result<xdr::TransactionEnvelope, Error> {
+        let TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) = tx_env else {
+            return Err(super::xdr::Error::OnlyTransactionV1Supported.into());
+        };
+        let transaction_data = if let TransactionExt::V1(_) = &tx.ext {
+            let unsigned = TransactionEnvelope::Tx(TransactionV1Envelope {
+                tx: tx.clone(),
+                signatures: VecM::default(),
+            });
+            crate::log::rpc::request("simulateTransaction", &unsigned);
+            let client = network
+                .checked_rpc_client(self.network.rpc_options())
+                .await?;
+            let sim_res = network
+                .with_rpc_timeout(client.simulate_transaction_envelope(&unsigned))
+                .await??;
+            crate::log::rpc::simulate_response(&sim_res);
+            if let Some(e) = &sim_res.error {
+                return Err(rpc::Error::TransactionSimulationFailed(e.clone()).into());
+            }
+            Some(sim_res.transaction_data()?)
+        } else {
+            None
+        };
+        let tx = raised_fee(tx, transaction_data, self.fee_retry_factor)?;
+        let fee = tx.fee;
+        let signed = self.sign_with.sign_tx_env(
+            &TransactionEnvelope::Tx(TransactionV1Envelope {
+                tx,
+                signatures: VecM::default(),
+            }),
+            &self.locator,
+            network,
+            print.quiet,
+        )?;
+        match &signed {
+            TransactionEnvelope::Tx(TransactionV1Envelope {
+                signatures: resigned,
+                ..
+            }) if keeps_signers(signatures, resigned) => {}
+            _ => return Err(Error::FeeRetryDropsSignatures),
+        }
+        print.warnln(format!(
+            "Fee was insufficient, resending with a fee of {fee} stroops"
+        ));
+        Ok(signed)
+    }
+
+    async fn send_once(
+        &self,
+        tx_env: &xdr::TransactionEnvelope,
+        network: &network::Network,
+        print: &Print,
+    ) -> Result<GetTransactionResponse, Error> {
+        let client = network
+            .checked_rpc_client(self.network.rpc_options())
+            .await?;
+        if let Ok(Ok(hash)) = super::xdr::unwrap_envelope_v1(tx_env.clone())
+            .map(|tx| transaction_hash(&tx, &network.network_passphrase))
+        {
+            print.infoln(format!("Transaction Hash: {}", hex::encode(hash)));
+        }
+
+        crate::log::rpc::request("sendTransaction", tx_env);
+        let response = network.send_transaction(tx_env).await?;
+        print_rejection_events(&response, print)?;
+        let hash = accepted(&response)?;
+        match network
+            .with_rpc_timeout(client.get_transaction_polling(&hash, None))
+            .await?
+        {
+            Ok(response) => {
+                crate::log::rpc::transaction_response(&response);
+                Ok(response)
+            }
+            Err(e) => {
+                print_failure_events(network, &client, &hash, print).await;
+                Err(e.into())
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -55,16 +414,337 @@ impl NetworkRunnable for Cmd {
         } else {
             self.network.get(&self.locator)?
         };
-        let client = network.rpc_client()?;
         let tx_env = super::xdr::tx_envelope_from_stdin()?;
+        let print = Print::new(globals.map_or(false, |g| g.quiet));
+        self.confirm(1, &network, &print)?;
+        Ok(self.send(&tx_env, &network, &print).await?.1)
+    }
+}
 
-        if let Ok(Ok(hash)) = super::xdr::unwrap_envelope_v1(tx_env.clone())
-            .map(|tx| transaction_hash(&tx, &network.network_passphrase))
-        {
-            let print = Print::new(globals.map_or(false, |g| g.quiet));
-            print.infoln(format!("Transaction Hash: {}", hex::encode(hash)));
+fn decode_envelopes(input: &str) -> Result<Vec<xdr::TransactionEnvelope>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            xdr::TransactionEnvelope::from_xdr_base64(line.trim(), Limits::none())
+                .map_err(|_| Error::EnvelopeDecode(i + 1))
+        })
+        .collect()
+}
+
+/// Hash of the transaction sent with `response`, or why the RPC server didn't accept it.
+fn accepted(response: &network::SendTransactionResponse) -> Result<xdr::Hash, Error> {
+    match response.status.as_str() {
+        "PENDING" | "DUPLICATE" => Ok(response.hash.parse()?),
+        "ERROR" => {
+            let result = response
+                .error_result_xdr
+                .as_deref()
+                .ok_or(Error::MissingErrorResult)?;
+            let result = xdr::TransactionResult::from_xdr_base64(result, Limits::none())?;
+            Err(Error::SubmissionFailed(Box::new(result.result)))
+        }
+        "TRY_AGAIN_LATER" => Err(Error::TryAgainLater),
+        status => Err(Error::UnexpectedSendStatus(status.to_string())),
+    }
+}
+
+/// Whether sending failed because the fee was too low, either rejected for an insufficient fee
+/// or not accepted for now while the network is surging.
+fn is_insufficient_fee(e: &Error) -> bool {
+    match e {
+        Error::TryAgainLater => true,
+        Error::SubmissionFailed(result) => {
+            matches!(**result, xdr::TransactionResultResult::TxInsufficientFee)
+        }
+        _ => false,
+    }
+}
+
+/// Whether every signature in `before` has one with the same signer hint in `after`.
+fn keeps_signers(before: &[xdr::DecoratedSignature], after: &[xdr::DecoratedSignature]) -> bool {
+    before
+        .iter()
+        .all(|signature| after.iter().any(|s| s.hint == signature.hint))
+}
+
+/// `tx` with the resource fee of `transaction_data`, if any, and its inclusion fee multiplied by
+/// `factor`.
+fn raised_fee(
+    tx: &xdr::Transaction,
+    transaction_data: Option<SorobanTransactionData>,
+    factor: u32,
+) -> Result<xdr::Transaction, Error> {
+    let resource_fee = |ext: &TransactionExt| match ext {
+        TransactionExt::V1(data) => data.resource_fee,
+        TransactionExt::V0 => 0,
+    };
+    let inclusion_fee = i64::from(tx.fee) - resource_fee(&tx.ext);
+    let ext = transaction_data.map_or_else(|| tx.ext.clone(), TransactionExt::V1);
+    let fee = resource_fee(&ext) + inclusion_fee * i64::from(factor);
+    Ok(xdr::Transaction {
+        fee: u32::try_from(fee).map_err(|_| Error::FeeTooLarge(fee))?,
+        ext,
+        ..tx.clone()
+    })
+}
+
+/// Hex hash of the envelope, or `-` if it can't be computed.
+fn tx_hash(tx_env: &xdr::TransactionEnvelope, network: &network::Network) -> String {
+    let hash = match tx_env {
+        xdr::TransactionEnvelope::TxFeeBump(xdr::FeeBumpTransactionEnvelope { tx, .. }) => {
+            fee_bump_transaction_hash(tx, &network.network_passphrase)
+        }
+        tx_env => match super::xdr::unwrap_envelope_v1(tx_env.clone()) {
+            Ok(tx) => transaction_hash(&tx, &network.network_passphrase),
+            Err(_) => return "-".to_string(),
+        },
+    };
+    hash.map_or_else(|_| "-".to_string(), hex::encode)
+}
+
+/// Print the hash, source account, fee and operations of the transaction in `tx_env`.
+fn print_summary(
+    tx_env: &xdr::TransactionEnvelope,
+    network: &network::Network,
+    print: &Print,
+) -> Result<(), Error> {
+    let tx = match tx_env {
+        xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope { tx, .. }) => tx,
+        xdr::TransactionEnvelope::TxFeeBump(xdr::FeeBumpTransactionEnvelope {
+            tx: fee_bump,
+            ..
+        }) => {
+            let hash = fee_bump_transaction_hash(fee_bump, &network.network_passphrase)?;
+            print.infoln(format!(
+                "Fee bump transaction hash is {}",
+                hex::encode(hash)
+            ));
+            let xdr::FeeBumpTransactionInnerTx::Tx(xdr::TransactionV1Envelope { tx, .. }) =
+                &fee_bump.inner_tx;
+            tx
+        }
+        xdr::TransactionEnvelope::TxV0(_) => {
+            return Err(super::xdr::Error::OnlyTransactionV1Supported.into())
+        }
+    };
+    print.log_transaction(tx, network, false)?;
+    for line in signer::tx_summary(tx_env) {
+        print.infoln(line);
+    }
+    Ok(())
+}
+
+/// Fetch the failed transaction and print the contract errors and logs from its diagnostic events.
+async fn print_failure_events(
+    network: &network::Network,
+    client: &rpc::Client,
+    hash: &xdr::Hash,
+    print: &Print,
+) {
+    let Ok(Ok(Ok(response))) = network
+        .with_rpc_timeout(client.get_transaction(hash))
+        .await
+        .map(|response| response.map(GetTransactionResponse::try_from))
+    else {
+        return;
+    };
+    crate::log::rpc::transaction_response(&response);
+    let Some(meta) = response.result_meta else {
+        return;
+    };
+    let events = extract_events(&meta);
+    if !events.is_empty() {
+        print.errorln("Transaction failed with the following diagnostic events:");
+        if let Err(e) = crate::log::event::failure(&events, print) {
+            print.warnln(format!("Failed to print the diagnostic events: {e}"));
+        }
+    }
+}
+
+/// Print the contract errors and logs from the diagnostic events of a transaction the RPC server
+/// rejected with `ERROR`, which it only includes when it has diagnostic events enabled.
+fn print_rejection_events(
+    response: &network::SendTransactionResponse,
+    print: &Print,
+) -> Result<(), Error> {
+    if response.status != "ERROR" {
+        return Ok(());
+    }
+    let events = response
+        .diagnostic_events_xdr
+        .iter()
+        .flatten()
+        .map(|event| xdr::DiagnosticEvent::from_xdr_base64(event, Limits::none()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !events.is_empty() {
+        print.errorln("Transaction was rejected with the following diagnostic events:");
+        crate::log::event::failure(&events, print)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdr::WriteXdr;
+
+    #[test]
+    fn decode_envelopes_by_line() {
+        assert!(decode_envelopes("\n  \n").unwrap().is_empty());
+        assert!(matches!(
+            decode_envelopes("\nnot an envelope\n"),
+            Err(Error::EnvelopeDecode(2))
+        ));
+    }
+
+    fn tx(fee: u32, ext: TransactionExt) -> xdr::Transaction {
+        xdr::Transaction {
+            source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256([0; 32])),
+            fee,
+            seq_num: xdr::SequenceNumber(1),
+            cond: xdr::Preconditions::None,
+            memo: xdr::Memo::None,
+            operations: VecM::default(),
+            ext,
+        }
+    }
+
+    fn transaction_data(resource_fee: i64) -> SorobanTransactionData {
+        SorobanTransactionData {
+            ext: xdr::ExtensionPoint::V0,
+            resources: xdr::SorobanResources {
+                footprint: xdr::LedgerFootprint {
+                    read_only: VecM::default(),
+                    read_write: VecM::default(),
+                },
+                instructions: 0,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee,
         }
+    }
+
+    #[test]
+    fn raised_fee_multiplies_inclusion_fee() {
+        let raised = raised_fee(&tx(100, TransactionExt::V0), None, 3).unwrap();
+        assert_eq!(raised.fee, 300);
+    }
+
+    #[test]
+    fn raised_fee_refreshes_resource_fee() {
+        let tx = tx(1_100, TransactionExt::V1(transaction_data(1_000)));
+        let raised = raised_fee(&tx, Some(transaction_data(1_500)), 2).unwrap();
+        assert_eq!(raised.fee, 1_700);
+        assert_eq!(raised.ext, TransactionExt::V1(transaction_data(1_500)));
+    }
+
+    #[test]
+    fn raised_fee_too_large() {
+        assert!(matches!(
+            raised_fee(&tx(u32::MAX, TransactionExt::V0), None, 2),
+            Err(Error::FeeTooLarge(_))
+        ));
+    }
 
-        Ok(client.send_transaction_polling(&tx_env).await?)
+    fn send_response(
+        status: &str,
+        error_result_xdr: Option<&str>,
+    ) -> network::SendTransactionResponse {
+        network::SendTransactionResponse {
+            hash: "00".repeat(32),
+            status: status.to_string(),
+            error_result_xdr: error_result_xdr.map(str::to_string),
+            diagnostic_events_xdr: None,
+        }
+    }
+
+    #[test]
+    fn accepted_statuses() {
+        assert_eq!(
+            accepted(&send_response("PENDING", None)).unwrap(),
+            xdr::Hash([0; 32])
+        );
+        assert!(accepted(&send_response("DUPLICATE", None)).is_ok());
+        assert!(matches!(
+            accepted(&send_response("TRY_AGAIN_LATER", None)),
+            Err(Error::TryAgainLater)
+        ));
+        assert!(matches!(
+            accepted(&send_response("ERROR", None)),
+            Err(Error::MissingErrorResult)
+        ));
+    }
+
+    #[test]
+    fn insufficient_fee() {
+        // A `TransactionResult` with a fee charged of 100 and the code `txINSUFFICIENT_FEE`
+        let e = accepted(&send_response("ERROR", Some("AAAAAAAAAGT////3AAAAAA=="))).unwrap_err();
+        assert!(matches!(&e, Error::SubmissionFailed(result)
+            if matches!(**result, xdr::TransactionResultResult::TxInsufficientFee)));
+        assert!(is_insufficient_fee(&e));
+        assert!(is_insufficient_fee(&Error::TryAgainLater));
+        assert!(!is_insufficient_fee(&Error::SubmissionFailed(Box::new(
+            xdr::TransactionResultResult::TxBadSeq
+        ))));
+    }
+
+    #[test]
+    fn rejection_events() {
+        let event = xdr::DiagnosticEvent {
+            in_successful_contract_call: false,
+            event: xdr::ContractEvent {
+                ext: xdr::ExtensionPoint::V0,
+                contract_id: None,
+                type_: xdr::ContractEventType::Diagnostic,
+                body: xdr::ContractEventBody::V0(xdr::ContractEventV0 {
+                    topics: vec![xdr::ScVal::Symbol("log".try_into().unwrap())]
+                        .try_into()
+                        .unwrap(),
+                    data: xdr::ScVal::U32(1),
+                }),
+            },
+        };
+        let print = Print::new(true);
+        let mut response = send_response("ERROR", None);
+        response.diagnostic_events_xdr = Some(vec![event.to_xdr_base64(Limits::none()).unwrap()]);
+        assert!(print_rejection_events(&response, &print).is_ok());
+        response.diagnostic_events_xdr = Some(vec!["not xdr".to_string()]);
+        assert!(matches!(
+            print_rejection_events(&response, &print),
+            Err(Error::Xdr(_))
+        ));
+        // Only the events of a rejected transaction are printed
+        response.status = "PENDING".to_string();
+        assert!(print_rejection_events(&response, &print).is_ok());
+    }
+
+    fn signature(hint: u8) -> xdr::DecoratedSignature {
+        xdr::DecoratedSignature {
+            hint: xdr::SignatureHint([hint; 4]),
+            signature: xdr::Signature(xdr::BytesM::default()),
+        }
+    }
+
+    #[test]
+    fn keeps_signers_by_hint() {
+        assert!(keeps_signers(&[], &[signature(1)]));
+        assert!(keeps_signers(&[signature(1)], &[signature(1)]));
+        assert!(!keeps_signers(
+            &[signature(1), signature(2)],
+            &[signature(1)]
+        ));
+    }
+
+    #[test]
+    fn fee_retry_factor_is_at_least_two() {
+        use clap::Parser;
+        let parse = |factor: &str| {
+            Cmd::try_parse_from(["send", "--fee-retry", "1", "--fee-retry-factor", factor])
+        };
+        assert!(parse("1").is_err());
+        assert_eq!(parse("3").unwrap().fee_retry_factor, 3);
     }
 }
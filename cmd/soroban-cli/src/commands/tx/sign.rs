@@ -1,9 +1,19 @@
+use std::path::PathBuf;
+
 use crate::{
     commands::global,
-    config::{locator, network, sign_with},
-    xdr::{self, Limits, WriteXdr},
+    config::{
+        locator,
+        network::{self, Network, RpcOptions},
+        sign_with,
+    },
+    print::Print,
+    rpc,
+    xdr::{self, Limits, TransactionEnvelope, WriteXdr},
 };
 
+use super::weights::{self, Weights};
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
@@ -16,6 +26,16 @@ pub enum Error {
     SignWith(#[from] sign_with::Error),
     #[error(transparent)]
     Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Weights(#[from] weights::Error),
+    #[error("`--input` is required with `--sign-with-key-stdin`, as stdin is used for the key")]
+    InputRequired,
+    #[error("{0:?} already exists, use --overwrite to replace it")]
+    OutputFileExists(PathBuf),
+    #[error("writing {0:?}: {1}")]
+    WriteOutputFile(PathBuf, std::io::Error),
 }
 
 #[derive(Debug, clap::Parser, Clone)]
@@ -23,6 +43,24 @@ pub enum Error {
 pub struct Cmd {
     #[command(flatten)]
     pub sign_with: sign_with::Args,
+    /// File to read the base64 transaction envelope from, instead of stdin
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+    /// Fetch the source account's signers and thresholds from the network and warn if the
+    /// accumulated signature weight is below what the transaction's operations require
+    #[arg(long)]
+    pub verify_weights: bool,
+    /// Print only the base64 decorated signature instead of the signed envelope, so it can be
+    /// carried separately and added to the envelope later with `tx attach-sig`
+    #[arg(long, conflicts_with_all = ["verify_weights", "signers", "sign_with_lab"])]
+    pub signature_only: bool,
+    /// File to write the signed envelope, or the signature with `--signature-only`, to instead of
+    /// stdout, creating its parent directories
+    #[arg(long)]
+    pub output_file: Option<PathBuf>,
+    /// Replace `--output-file` if it already exists
+    #[arg(long, requires = "output_file")]
+    pub overwrite: bool,
     #[command(flatten)]
     pub network: network::Args,
     #[command(flatten)]
@@ -30,16 +68,70 @@ pub struct Cmd {
 }
 
 impl Cmd {
-    #[allow(clippy::unused_async)]
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
-        let tx_env = super::xdr::tx_envelope_from_stdin()?;
-        let tx_env_signed = self.sign_with.sign_tx_env(
-            &tx_env,
-            &self.locator,
-            &self.network.get(&self.locator)?,
-            global_args.quiet,
-        )?;
-        println!("{}", tx_env_signed.to_xdr_base64(Limits::none())?);
+        let tx_env = match &self.input {
+            Some(input) => super::xdr::tx_envelope_from_file(input)?,
+            None if self.sign_with.sign_with_key_stdin => return Err(Error::InputRequired),
+            None => super::xdr::tx_envelope_from_stdin()?,
+        };
+        let network = self.network.get_offline(&self.locator)?;
+        let print = Print::new(global_args.quiet);
+        if self.signature_only {
+            let signature = self
+                .sign_with
+                .signer(&self.locator, print.clone())?
+                .sign_tx_env_signature(&tx_env, &network)
+                .map_err(sign_with::Error::from)?;
+            return self.output(&signature.to_xdr_base64(Limits::none())?, &print);
+        }
+        let tx_env_signed =
+            self.sign_with
+                .sign_tx_env(&tx_env, &self.locator, &network, global_args.quiet)?;
+        if self.verify_weights {
+            verify_weights(&tx_env_signed, &network, self.network.rpc_options(), &print).await?;
+        }
+        self.output(&tx_env_signed.to_xdr_base64(Limits::none())?, &print)
+    }
+
+    /// Print `base64` to stdout, or write it to `--output-file`.
+    fn output(&self, base64: &str, print: &Print) -> Result<(), Error> {
+        let Some(path) = &self.output_file else {
+            println!("{base64}");
+            return Ok(());
+        };
+        if path.exists() && !self.overwrite {
+            return Err(Error::OutputFileExists(path.clone()));
+        }
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| Error::WriteOutputFile(path.clone(), e))?;
+        }
+        std::fs::write(path, format!("{base64}\n"))
+            .map_err(|e| Error::WriteOutputFile(path.clone(), e))?;
+        print.saveln(format!("Wrote {}", path.display()));
         Ok(())
     }
 }
+
+async fn verify_weights(
+    tx_env: &TransactionEnvelope,
+    network: &Network,
+    options: RpcOptions,
+    print: &Print,
+) -> Result<(), Error> {
+    let Weights {
+        source,
+        signed,
+        threshold,
+        ..
+    } = Weights::fetch_envelope(tx_env, network, options).await?;
+    if signed < threshold {
+        print.warnln(format!(
+            "Accumulated signature weight {signed} is below the required threshold {threshold} for {source}"
+        ));
+    } else {
+        print.checkln(format!(
+            "Accumulated signature weight {signed} meets the required threshold {threshold} for {source}"
+        ));
+    }
+    Ok(())
+}
@@ -1,6 +1,15 @@
+pub mod snapshot;
+
+use std::path::PathBuf;
+
 use crate::{
-    assembled::{simulate_and_assemble_transaction, Assembled},
-    xdr::{self, TransactionEnvelope, WriteXdr},
+    assembled::{assemble_simulation, simulate_and_assemble_transaction, Assembled},
+    failure_snapshot::OnFail,
+    print::Print,
+    xdr::{
+        self, SorobanResources, SorobanTransactionData, TransactionEnvelope, TransactionExt,
+        TransactionV1Envelope, VecM, WriteXdr,
+    },
 };
 use async_trait::async_trait;
 
@@ -18,6 +27,12 @@ pub enum Error {
     Xdr(#[from] xdr::Error),
     #[error(transparent)]
     Network(#[from] config::network::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("simulation did not return any Soroban resources for this transaction")]
+    MissingResources,
+    #[error(transparent)]
+    Snapshot(#[from] snapshot::Error),
 }
 
 /// Command to simulate a transaction envelope via rpc
@@ -27,10 +42,50 @@ pub enum Error {
 pub struct Cmd {
     #[clap(flatten)]
     pub config: super::super::config::Args,
+    /// Output the simulated and assembled transaction envelope as base64 XDR. This is the default
+    #[arg(long, conflicts_with = "resource_only")]
+    pub sim_only: bool,
+    /// Only output the resources, e.g. CPU instructions, read bytes and write bytes, and the
+    /// resource fee computed by the simulation, as JSON, without the assembled transaction
+    /// envelope. If the simulation fails, its decoded error is printed instead
+    #[arg(long)]
+    pub resource_only: bool,
+    /// Simulate with a local host against the ledger entries of a snapshot file, e.g. one created
+    /// with `snapshot create`, instead of with the RPC server. The snapshot must contain the
+    /// network's config settings and every ledger entry the transaction uses
+    #[arg(long)]
+    pub snapshot: Option<PathBuf>,
+}
+
+/// Resources computed by a simulation, as output by `--resource-only`
+#[derive(serde::Serialize)]
+struct ResourceEstimate<'a> {
+    resources: &'a SorobanResources,
+    resource_fee: i64,
+    fee: u32,
 }
 
 impl Cmd {
     pub async fn run(&self, global_args: &global::Args) -> Result<(), Error> {
+        if self.resource_only {
+            let res = self.estimate_resources(global_args).await?;
+            let tx = res.transaction();
+            let TransactionExt::V1(SorobanTransactionData {
+                resources,
+                resource_fee,
+                ..
+            }) = &tx.ext
+            else {
+                return Err(Error::MissingResources);
+            };
+            let estimate = ResourceEstimate {
+                resources,
+                resource_fee: *resource_fee,
+                fee: tx.fee,
+            };
+            println!("{}", serde_json::to_string_pretty(&estimate)?);
+            return Ok(());
+        }
         let res = self
             .run_against_rpc_server(Some(global_args), Some(&self.config))
             .await?;
@@ -38,6 +93,45 @@ impl Cmd {
         println!("{}", tx_env.to_xdr_base64(xdr::Limits::none())?);
         Ok(())
     }
+
+    // Simulate before assembling, so that a failed simulation can be reported with its
+    // decoded diagnostic events instead of an empty estimate.
+    async fn estimate_resources(&self, global_args: &global::Args) -> Result<Assembled, Error> {
+        let print = Print::new(global_args.quiet);
+        let tx = super::xdr::unwrap_envelope_v1(super::xdr::tx_envelope_from_stdin()?)?;
+        let sim_res = if let Some(snapshot) = &self.snapshot {
+            snapshot::simulate(snapshot, &tx)?
+        } else {
+            let network = self.config.get_network()?;
+            let client = network
+                .checked_rpc_client(self.config.network.rpc_options())
+                .await?;
+            let tx_env = TransactionEnvelope::Tx(TransactionV1Envelope {
+                tx: tx.clone(),
+                signatures: VecM::default(),
+            });
+            crate::log::rpc::request("simulateTransaction", &tx_env);
+            let on_fail = OnFail::new(Some(global_args), &network, &print);
+            let sim_res = on_fail
+                .check(
+                    network
+                        .with_rpc_timeout(client.simulate_transaction_envelope(&tx_env))
+                        .await?,
+                    &tx,
+                )
+                .await?;
+            crate::log::rpc::simulate_response(&sim_res);
+            if sim_res.error.is_some() {
+                on_fail.dump(&tx).await;
+            }
+            sim_res
+        };
+        if let Some(e) = &sim_res.error {
+            crate::log::event::failure(&sim_res.events()?, &print)?;
+            return Err(crate::rpc::Error::TransactionSimulationFailed(e.clone()).into());
+        }
+        Ok(assemble_simulation(&tx, sim_res)?)
+    }
 }
 
 #[async_trait]
@@ -47,14 +141,29 @@ impl NetworkRunnable for Cmd {
     type Result = Assembled;
     async fn run_against_rpc_server(
         &self,
-        _: Option<&global::Args>,
+        global_args: Option<&global::Args>,
         config: Option<&config::Args>,
     ) -> Result<Self::Result, Self::Error> {
         let config = config.unwrap_or(&self.config);
-        let network = config.get_network()?;
-        let client = network.rpc_client()?;
         let tx = super::xdr::unwrap_envelope_v1(super::xdr::tx_envelope_from_stdin()?)?;
-        let tx = simulate_and_assemble_transaction(&client, &tx).await?;
-        Ok(tx)
+        if let Some(snapshot) = &self.snapshot {
+            return Ok(assemble_simulation(
+                &tx,
+                snapshot::simulate(snapshot, &tx)?,
+            )?);
+        }
+        let network = config.get_network()?;
+        let client = network
+            .checked_rpc_client(config.network.rpc_options())
+            .await?;
+        let print = Print::new(global_args.is_some_and(|args| args.quiet));
+        Ok(OnFail::new(global_args, &network, &print)
+            .check(
+                network
+                    .with_rpc_timeout(simulate_and_assemble_transaction(&client, &tx))
+                    .await?,
+                &tx,
+            )
+            .await?)
     }
 }
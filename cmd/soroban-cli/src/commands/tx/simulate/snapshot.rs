@@ -0,0 +1,460 @@
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use soroban_env_host::{
+    storage::{EntryWithLiveUntil, SnapshotSource},
+    HostError,
+};
+use soroban_ledger_snapshot::LedgerSnapshot;
+use soroban_rpc::{SimulateHostFunctionResultRaw, SimulateTransactionResponse};
+use soroban_simulation::{
+    simulation::{simulate_invoke_host_function_op, SimulationAdjustmentConfig},
+    NetworkConfig, SnapshotSourceWithArchive,
+};
+
+use crate::xdr::{
+    self, ConfigSettingEntry, LedgerEntryData, LedgerKey, Limits, Operation, OperationBody,
+    SorobanResources, SorobanTransactionData, Transaction, TransactionExt, WriteXdr,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("reading snapshot {}: {1}", .0.display())]
+    ReadSnapshot(PathBuf, soroban_ledger_snapshot::Error),
+    #[error(
+        "only transactions with a single InvokeHostFunction operation can be simulated against a snapshot"
+    )]
+    UnsupportedOperation,
+    #[error("loading the network config from the snapshot, which must contain its config setting entries: {0}")]
+    NetworkConfig(String),
+    #[error("simulating against the snapshot: {0}")]
+    Simulation(String),
+    #[error("the transaction failed using ledger entries of its footprint missing from the snapshot: {}", .0.join(", "))]
+    MissingEntries(Vec<String>),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Simulate the `InvokeHostFunction` operation of `tx` with a local host against the ledger
+/// entries and ledger info of the snapshot at `path`, returning the response the RPC server's
+/// `simulateTransaction` would give.
+pub fn simulate(path: &Path, tx: &Transaction) -> Result<SimulateTransactionResponse, Error> {
+    let snapshot =
+        LedgerSnapshot::read_file(path).map_err(|e| Error::ReadSnapshot(path.to_path_buf(), e))?;
+    let [Operation {
+        source_account,
+        body: OperationBody::InvokeHostFunction(op),
+    }] = tx.operations.as_slice()
+    else {
+        return Err(Error::UnsupportedOperation);
+    };
+    let source_account = source_account
+        .clone()
+        .unwrap_or_else(|| tx.source_account.clone())
+        .account_id();
+    let ledger_info = snapshot.ledger_info();
+    let source = Rc::new(Source::new(snapshot));
+
+    let network_config =
+        NetworkConfig::load_from_snapshot(source.as_ref(), source.bucket_list_size())
+            .map_err(|e| Error::NetworkConfig(e.to_string()))?;
+    source.missing.borrow_mut().clear();
+
+    // Like the RPC server, enforce the authorization entries of the operation, or record them
+    // when there are none.
+    let auth_entries = if op.auth.is_empty() {
+        None
+    } else {
+        Some(op.auth.to_vec())
+    };
+    let result = simulate_invoke_host_function_op(
+        source.clone(),
+        &network_config,
+        &SimulationAdjustmentConfig::default_adjustment(),
+        &ledger_info,
+        op.host_function.clone(),
+        auth_entries,
+        &source_account,
+        rand::random(),
+        true,
+    )
+    .map_err(|e| Error::Simulation(e.to_string()))?;
+
+    let events = result
+        .diagnostic_events
+        .iter()
+        .map(|event| event.to_xdr_base64(Limits::none()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (val, transaction_data) = match (result.invoke_result, result.transaction_data) {
+        (Ok(val), Some(transaction_data)) => (val, transaction_data),
+        (Err(e), _) => {
+            let missing = missing_from_footprint(tx, source.missing.take());
+            if !missing.is_empty() {
+                return Err(Error::MissingEntries(
+                    missing
+                        .iter()
+                        .map(serde_json::to_string)
+                        .collect::<Result<_, _>>()?,
+                ));
+            }
+            return Ok(SimulateTransactionResponse {
+                error: Some(format!("HostError: {e:?}")),
+                events,
+                latest_ledger: ledger_info.sequence_number,
+                ..Default::default()
+            });
+        }
+        (Ok(_), None) => {
+            return Err(Error::Simulation(
+                "no transaction data was computed".to_string(),
+            ))
+        }
+    };
+
+    Ok(SimulateTransactionResponse {
+        min_resource_fee: transaction_data.resource_fee.try_into().unwrap_or_default(),
+        results: vec![SimulateHostFunctionResultRaw {
+            auth: result
+                .auth
+                .iter()
+                .map(|auth| auth.to_xdr_base64(Limits::none()))
+                .collect::<Result<_, _>>()?,
+            xdr: val.to_xdr_base64(Limits::none())?,
+        }],
+        transaction_data: transaction_data.to_xdr_base64(Limits::none())?,
+        events,
+        latest_ledger: ledger_info.sequence_number,
+        ..Default::default()
+    })
+}
+
+/// The keys of `missing` that are in the footprint of `tx`. The snapshot should have had them,
+/// while other keys looked up and missing from it may not exist on the network either, e.g.
+/// storage a contract checks for, so a failure using them is the contract's own.
+fn missing_from_footprint(tx: &Transaction, mut missing: Vec<LedgerKey>) -> Vec<LedgerKey> {
+    let TransactionExt::V1(SorobanTransactionData {
+        resources: SorobanResources { footprint, .. },
+        ..
+    }) = &tx.ext
+    else {
+        return Vec::new();
+    };
+    missing.retain(|key| footprint.read_only.contains(key) || footprint.read_write.contains(key));
+    missing.sort();
+    missing.dedup();
+    missing
+}
+
+/// The network config from the config setting entries of `snapshot`.
+pub fn network_config(snapshot: LedgerSnapshot) -> Result<NetworkConfig, Error> {
+    let source = Source::new(snapshot);
+    NetworkConfig::load_from_snapshot(&source, source.bucket_list_size())
+        .map_err(|e| Error::NetworkConfig(e.to_string()))
+}
+
+/// The ledger entries of a snapshot, recording the keys looked up that are not in it.
+struct Source {
+    snapshot: LedgerSnapshot,
+    missing: RefCell<Vec<LedgerKey>>,
+}
+
+impl Source {
+    fn new(snapshot: LedgerSnapshot) -> Self {
+        Self {
+            snapshot,
+            missing: RefCell::default(),
+        }
+    }
+
+    /// Latest bucket list size of the snapshot's `BucketlistSizeWindow`, used for the write fees.
+    fn bucket_list_size(&self) -> u64 {
+        self.snapshot
+            .ledger_entries
+            .iter()
+            .find_map(|(_, (entry, _))| match &entry.data {
+                LedgerEntryData::ConfigSetting(ConfigSettingEntry::BucketlistSizeWindow(
+                    window,
+                )) => window.last().copied(),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl SnapshotSourceWithArchive for Source {
+    fn get_including_archived(
+        &self,
+        key: &Rc<LedgerKey>,
+    ) -> Result<Option<EntryWithLiveUntil>, HostError> {
+        let entry = self
+            .snapshot
+            .ledger_entries
+            .iter()
+            .find(|(k, _)| **k == **key)
+            .map(|(_, (entry, live_until))| (Rc::new((**entry).clone()), *live_until));
+        if entry.is_none() {
+            self.missing.borrow_mut().push((**key).clone());
+        }
+        Ok(entry)
+    }
+}
+
+impl SnapshotSource for Source {
+    fn get(&self, key: &Rc<LedgerKey>) -> Result<Option<EntryWithLiveUntil>, HostError> {
+        self.get_including_archived(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xdr::{
+        Asset, BytesM, ConfigSettingContractBandwidthV0, ConfigSettingContractComputeV0,
+        ConfigSettingContractEventsV0, ConfigSettingContractHistoricalDataV0,
+        ConfigSettingContractLedgerCostV0, ContractCostParamEntry, ContractCostParams,
+        ContractCostType, ContractDataDurability, ContractExecutable, ContractIdPreimage,
+        CreateContractArgs, ExtensionPoint, Hash, HostFunction, InvokeContractArgs,
+        InvokeHostFunctionOp, LedgerEntry, LedgerEntryExt, LedgerFootprint, LedgerKeyConfigSetting,
+        LedgerKeyContractData, Memo, MuxedAccount, Preconditions, ScAddress, ScVal, SequenceNumber,
+        StateArchivalSettings, Uint256, VecM,
+    };
+
+    use super::*;
+
+    /// A snapshot with config settings under which everything the host does is free and within
+    /// limits, written to `dir`.
+    fn snapshot_with_network_config(dir: &Path) -> PathBuf {
+        let free = ContractCostParams(
+            vec![
+                ContractCostParamEntry {
+                    ext: ExtensionPoint::V0,
+                    const_term: 0,
+                    linear_term: 0,
+                };
+                ContractCostType::variants().len()
+            ]
+            .try_into()
+            .unwrap(),
+        );
+        let settings = [
+            ConfigSettingEntry::ContractComputeV0(ConfigSettingContractComputeV0 {
+                ledger_max_instructions: 100_000_000,
+                tx_max_instructions: 100_000_000,
+                fee_rate_per_instructions_increment: 25,
+                tx_memory_limit: 40_000_000,
+            }),
+            ConfigSettingEntry::ContractLedgerCostV0(ConfigSettingContractLedgerCostV0 {
+                ledger_max_read_ledger_entries: 100,
+                ledger_max_read_bytes: 1_000_000,
+                ledger_max_write_ledger_entries: 100,
+                ledger_max_write_bytes: 1_000_000,
+                tx_max_read_ledger_entries: 100,
+                tx_max_read_bytes: 1_000_000,
+                tx_max_write_ledger_entries: 100,
+                tx_max_write_bytes: 1_000_000,
+                fee_read_ledger_entry: 1_000,
+                fee_write_ledger_entry: 1_000,
+                fee_read1_kb: 1_000,
+                bucket_list_target_size_bytes: 100_000_000,
+                write_fee1_kb_bucket_list_low: 1_000,
+                write_fee1_kb_bucket_list_high: 10_000,
+                bucket_list_write_fee_growth_factor: 1,
+            }),
+            ConfigSettingEntry::ContractHistoricalDataV0(ConfigSettingContractHistoricalDataV0 {
+                fee_historical1_kb: 1_000,
+            }),
+            ConfigSettingEntry::ContractEventsV0(ConfigSettingContractEventsV0 {
+                tx_max_contract_events_size_bytes: 10_000,
+                fee_contract_events1_kb: 1_000,
+            }),
+            ConfigSettingEntry::ContractBandwidthV0(ConfigSettingContractBandwidthV0 {
+                ledger_max_txs_size_bytes: 1_000_000,
+                tx_max_size_bytes: 100_000,
+                fee_tx_size1_kb: 1_000,
+            }),
+            ConfigSettingEntry::StateArchival(StateArchivalSettings {
+                max_entry_ttl: 1_000_000,
+                min_temporary_ttl: 16,
+                min_persistent_ttl: 4_096,
+                persistent_rent_rate_denominator: 1_000,
+                temp_rent_rate_denominator: 1_000,
+                max_entries_to_archive: 100,
+                bucket_list_size_window_sample_size: 30,
+                bucket_list_window_sample_period: 64,
+                eviction_scan_size: 100_000,
+                starting_eviction_scan_level: 6,
+            }),
+            ConfigSettingEntry::ContractCostParamsCpuInstructions(free.clone()),
+            ConfigSettingEntry::ContractCostParamsMemoryBytes(free),
+        ];
+        let snapshot = LedgerSnapshot {
+            sequence_number: 100,
+            min_persistent_entry_ttl: 4_096,
+            min_temp_entry_ttl: 16,
+            max_entry_ttl: 1_000_000,
+            ledger_entries: settings
+                .into_iter()
+                .map(|setting| {
+                    let key = LedgerKey::ConfigSetting(LedgerKeyConfigSetting {
+                        config_setting_id: setting.discriminant(),
+                    });
+                    let entry = LedgerEntry {
+                        last_modified_ledger_seq: 0,
+                        data: LedgerEntryData::ConfigSetting(setting),
+                        ext: LedgerEntryExt::V0,
+                    };
+                    (Box::new(key), (Box::new(entry), None))
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let path = dir.join("snapshot.json");
+        snapshot.write_file(&path).unwrap();
+        path
+    }
+
+    fn instance_key(contract: Hash) -> LedgerKey {
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(contract),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+        })
+    }
+
+    fn invoke(contract: Hash, read_only: Vec<LedgerKey>) -> Transaction {
+        let mut tx = transaction(OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                contract_address: ScAddress::Contract(contract),
+                function_name: "hello".try_into().unwrap(),
+                args: VecM::default(),
+            }),
+            auth: VecM::default(),
+        }));
+        tx.ext = TransactionExt::V1(SorobanTransactionData {
+            ext: ExtensionPoint::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: read_only.try_into().unwrap(),
+                    read_write: VecM::default(),
+                },
+                instructions: 0,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+        });
+        tx
+    }
+
+    fn transaction(body: OperationBody) -> Transaction {
+        Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body,
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        }
+    }
+
+    #[test]
+    fn missing_snapshot() {
+        let tx = transaction(OperationBody::Inflation);
+        assert!(matches!(
+            simulate(Path::new("does-not-exist.json"), &tx),
+            Err(Error::ReadSnapshot(..))
+        ));
+    }
+
+    #[test]
+    fn unsupported_operation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        LedgerSnapshot::default().write_file(&path).unwrap();
+        let tx = transaction(OperationBody::Inflation);
+        assert!(matches!(
+            simulate(&path, &tx),
+            Err(Error::UnsupportedOperation)
+        ));
+    }
+
+    #[test]
+    fn missing_network_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+        LedgerSnapshot::default().write_file(&path).unwrap();
+        let tx = transaction(OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::UploadContractWasm(BytesM::default()),
+            auth: VecM::default(),
+        }));
+        assert!(matches!(simulate(&path, &tx), Err(Error::NetworkConfig(_))));
+    }
+
+    #[test]
+    fn successful_simulation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = snapshot_with_network_config(dir.path());
+        let tx = transaction(OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::CreateContract(CreateContractArgs {
+                contract_id_preimage: ContractIdPreimage::Asset(Asset::Native),
+                executable: ContractExecutable::StellarAsset,
+            }),
+            auth: VecM::default(),
+        }));
+        let sim_res = simulate(&path, &tx).unwrap();
+        assert_eq!(sim_res.error, None);
+        assert_eq!(sim_res.latest_ledger, 100);
+        assert_eq!(sim_res.results.len(), 1);
+        let transaction_data = sim_res.transaction_data().unwrap();
+        assert!(transaction_data
+            .resources
+            .footprint
+            .read_write
+            .iter()
+            .any(|key| matches!(
+                key,
+                LedgerKey::ContractData(LedgerKeyContractData {
+                    key: ScVal::LedgerKeyContractInstance,
+                    ..
+                })
+            )));
+        assert_eq!(
+            sim_res.min_resource_fee,
+            u64::try_from(transaction_data.resource_fee).unwrap()
+        );
+    }
+
+    #[test]
+    fn missing_footprint_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = snapshot_with_network_config(dir.path());
+        let contract = Hash([1; 32]);
+        let Err(Error::MissingEntries(missing)) = simulate(
+            &path,
+            &invoke(contract.clone(), vec![instance_key(contract)]),
+        ) else {
+            panic!("expected the instance missing from the snapshot to be reported");
+        };
+        assert_eq!(missing.len(), 1);
+    }
+
+    #[test]
+    fn missing_entries_outside_the_footprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = snapshot_with_network_config(dir.path());
+        // The instance is looked up and missing, but isn't in the footprint, so the simulation
+        // fails with the host error as the RPC server's would.
+        let sim_res = simulate(&path, &invoke(Hash([1; 32]), Vec::new())).unwrap();
+        assert!(sim_res.error.unwrap().starts_with("HostError"));
+    }
+}
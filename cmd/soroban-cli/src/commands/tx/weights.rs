@@ -0,0 +1,309 @@
+use crate::{
+    config::network::{self, Network, RpcOptions},
+    rpc,
+    signer::is_signed_by,
+    utils::transaction_hash,
+    xdr::{self, OperationBody},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    TxXdr(#[from] super::xdr::Error),
+}
+
+/// Signature weight of a transaction compared to the threshold its source account requires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Weights {
+    pub source: xdr::AccountId,
+    /// Sum of the weights of the source account's master key and signers that signed
+    pub signed: u32,
+    /// Highest threshold required by the operations sourced from the source account
+    pub threshold: u32,
+    /// Ed25519 keys of the source account that did not sign, with their weights, heaviest first
+    pub unsigned: Vec<(stellar_strkey::ed25519::PublicKey, u32)>,
+}
+
+impl Weights {
+    /// Fetch the signers and thresholds of the source account of `tx` from the network, and
+    /// weigh the `signatures` of `tx` against them.
+    pub async fn fetch(
+        tx: &xdr::Transaction,
+        signatures: &[xdr::DecoratedSignature],
+        network: &Network,
+        options: RpcOptions,
+    ) -> Result<Self, Error> {
+        let source = tx.source_account.clone().account_id();
+        let client = network.checked_rpc_client(options).await?;
+        let account = network
+            .with_rpc_timeout(client.get_account(&source.to_string()))
+            .await??;
+        let tx_hash = transaction_hash(tx, &network.network_passphrase)?;
+        Ok(Self::new(&account, tx, signatures, &tx_hash))
+    }
+
+    /// Like [`Weights::fetch`], for the transaction in `tx_env` and its signatures, the inner
+    /// ones of a fee bump.
+    pub async fn fetch_envelope(
+        tx_env: &xdr::TransactionEnvelope,
+        network: &Network,
+        options: RpcOptions,
+    ) -> Result<Self, Error> {
+        let (tx, signatures) = weighed_tx(tx_env)?;
+        Self::fetch(tx, signatures, network, options).await
+    }
+
+    /// Weigh the `signatures` of `tx`, whose hash is `tx_hash`, against the signers and
+    /// thresholds of its source `account`. Signatures are matched to signers by their hint and
+    /// then verified.
+    pub fn new(
+        account: &xdr::AccountEntry,
+        tx: &xdr::Transaction,
+        signatures: &[xdr::DecoratedSignature],
+        tx_hash: &[u8; 32],
+    ) -> Self {
+        let xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(master))) =
+            &account.account_id;
+        let signers = account
+            .signers
+            .iter()
+            .filter_map(|signer| match &signer.key {
+                xdr::SignerKey::Ed25519(xdr::Uint256(key)) => Some((key, signer.weight)),
+                _ => None,
+            });
+        let (signed, mut unsigned): (Vec<_>, Vec<_>) =
+            std::iter::once((master, u32::from(account.thresholds.0[0])))
+                .chain(signers)
+                .filter(|(_, weight)| *weight > 0)
+                .partition(|(key, _)| signatures.iter().any(|sig| is_signed_by(sig, key, tx_hash)));
+        unsigned.sort_by(|(_, a), (_, b)| b.cmp(a));
+        Self {
+            source: account.account_id.clone(),
+            signed: signed.iter().map(|(_, weight)| weight).sum(),
+            threshold: required_threshold(account, tx),
+            unsigned: unsigned
+                .into_iter()
+                .map(|(key, weight)| (stellar_strkey::ed25519::PublicKey(*key), weight))
+                .collect(),
+        }
+    }
+
+    pub fn is_met(&self) -> bool {
+        self.signed >= self.threshold
+    }
+
+    /// The unsigned keys, heaviest first, whose signatures would be enough to meet the
+    /// threshold, or all of them if they are not enough.
+    pub fn needed(&self) -> &[(stellar_strkey::ed25519::PublicKey, u32)] {
+        let mut weight = self.signed;
+        let count = self
+            .unsigned
+            .iter()
+            .take_while(|(_, w)| {
+                let needed = weight < self.threshold;
+                weight += w;
+                needed
+            })
+            .count();
+        &self.unsigned[..count]
+    }
+}
+
+/// Highest threshold required by the operations sourced from the transaction's source account.
+fn required_threshold(account: &xdr::AccountEntry, tx: &xdr::Transaction) -> u32 {
+    let level = tx
+        .operations
+        .iter()
+        .filter(|op| op.source_account.is_none())
+        .map(|op| threshold_level(&op.body))
+        .max()
+        .unwrap_or(ThresholdLevel::Low);
+    u32::from(account.thresholds.0[level as usize])
+}
+
+/// Index into an account's `Thresholds` for each security level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ThresholdLevel {
+    Low = 1,
+    Medium = 2,
+    High = 3,
+}
+
+fn threshold_level(body: &OperationBody) -> ThresholdLevel {
+    match body {
+        OperationBody::AllowTrust(_)
+        | OperationBody::SetTrustLineFlags(_)
+        | OperationBody::BumpSequence(_)
+        | OperationBody::ClaimClaimableBalance(_)
+        | OperationBody::ExtendFootprintTtl(_)
+        | OperationBody::RestoreFootprint(_) => ThresholdLevel::Low,
+        OperationBody::AccountMerge(_) => ThresholdLevel::High,
+        OperationBody::SetOptions(op)
+            if op.master_weight.is_some()
+                || op.low_threshold.is_some()
+                || op.med_threshold.is_some()
+                || op.high_threshold.is_some()
+                || op.signer.is_some() =>
+        {
+            ThresholdLevel::High
+        }
+        _ => ThresholdLevel::Medium,
+    }
+}
+
+/// The transaction in `tx_env` whose signatures are weighed and its signatures, the inner ones
+/// of a fee bump, as the signatures of a fee bump only authorize paying its fee.
+fn weighed_tx(
+    tx_env: &xdr::TransactionEnvelope,
+) -> Result<(&xdr::Transaction, &[xdr::DecoratedSignature]), Error> {
+    match tx_env {
+        xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope { tx, signatures })
+        | xdr::TransactionEnvelope::TxFeeBump(xdr::FeeBumpTransactionEnvelope {
+            tx:
+                xdr::FeeBumpTransaction {
+                    inner_tx:
+                        xdr::FeeBumpTransactionInnerTx::Tx(xdr::TransactionV1Envelope {
+                            tx,
+                            signatures,
+                        }),
+                    ..
+                },
+            ..
+        }) => Ok((tx, signatures)),
+        xdr::TransactionEnvelope::TxV0(_) => {
+            Err(super::xdr::Error::OnlyTransactionV1Supported.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer as _, SigningKey};
+
+    use crate::xdr::{
+        AccountEntryExt, DecoratedSignature, Memo, MuxedAccount, Operation, PaymentOp,
+        Preconditions, SequenceNumber, Signature, SignatureHint, Signer, SignerKey, Thresholds,
+        TransactionExt, Uint256,
+    };
+
+    use super::*;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn sign(key: &SigningKey, tx_hash: &[u8; 32]) -> DecoratedSignature {
+        let public = key.verifying_key().to_bytes();
+        DecoratedSignature {
+            hint: SignatureHint(public[28..].try_into().unwrap()),
+            signature: Signature(key.sign(tx_hash).to_bytes().to_vec().try_into().unwrap()),
+        }
+    }
+
+    /// Account of `key(1)` with master weight 1, signers `key(2)` of weight 1 and `key(3)` of
+    /// weight 2, and a medium threshold of 3.
+    fn account() -> xdr::AccountEntry {
+        let signer = |seed, weight| Signer {
+            key: SignerKey::Ed25519(Uint256(key(seed).verifying_key().to_bytes())),
+            weight,
+        };
+        xdr::AccountEntry {
+            account_id: xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(Uint256(
+                key(1).verifying_key().to_bytes(),
+            ))),
+            balance: 0,
+            seq_num: SequenceNumber(0),
+            num_sub_entries: 2,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: xdr::String32::default(),
+            thresholds: Thresholds([1, 1, 3, 3]),
+            signers: vec![signer(2, 1), signer(3, 2)].try_into().unwrap(),
+            ext: AccountEntryExt::V0,
+        }
+    }
+
+    fn payment() -> xdr::Transaction {
+        xdr::Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256(key(1).verifying_key().to_bytes())),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::Payment(PaymentOp {
+                    destination: MuxedAccount::Ed25519(Uint256([0; 32])),
+                    asset: xdr::Asset::Native,
+                    amount: 1,
+                }),
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        }
+    }
+
+    #[test]
+    fn threshold_met() {
+        let tx_hash = [7; 32];
+        let weights = Weights::new(
+            &account(),
+            &payment(),
+            &[sign(&key(1), &tx_hash), sign(&key(3), &tx_hash)],
+            &tx_hash,
+        );
+        assert_eq!((weights.signed, weights.threshold), (3, 3));
+        assert!(weights.is_met());
+        assert!(weights.needed().is_empty());
+    }
+
+    #[test]
+    fn threshold_not_met() {
+        let tx_hash = [7; 32];
+        let weights = Weights::new(&account(), &payment(), &[sign(&key(2), &tx_hash)], &tx_hash);
+        assert_eq!((weights.signed, weights.threshold), (1, 3));
+        assert!(!weights.is_met());
+        let needed = stellar_strkey::ed25519::PublicKey(key(3).verifying_key().to_bytes());
+        assert_eq!(weights.needed(), [(needed, 2)]);
+    }
+
+    #[test]
+    fn signature_of_another_hash_is_not_counted() {
+        let weights = Weights::new(
+            &account(),
+            &payment(),
+            &[sign(&key(1), &[8; 32]), sign(&key(3), &[8; 32])],
+            &[7; 32],
+        );
+        assert_eq!(weights.signed, 0);
+        assert_eq!(weights.needed().len(), 2);
+    }
+
+    #[test]
+    fn fee_bump_weighs_inner_transaction() {
+        let inner_signatures: xdr::VecM<DecoratedSignature, 20> =
+            vec![sign(&key(1), &[7; 32])].try_into().unwrap();
+        let tx_env = xdr::TransactionEnvelope::TxFeeBump(xdr::FeeBumpTransactionEnvelope {
+            tx: xdr::FeeBumpTransaction {
+                fee_source: MuxedAccount::Ed25519(Uint256(key(4).verifying_key().to_bytes())),
+                fee: 200,
+                inner_tx: xdr::FeeBumpTransactionInnerTx::Tx(xdr::TransactionV1Envelope {
+                    tx: payment(),
+                    signatures: inner_signatures.clone(),
+                }),
+                ext: xdr::FeeBumpTransactionExt::V0,
+            },
+            signatures: vec![sign(&key(4), &[8; 32])].try_into().unwrap(),
+        });
+        let (tx, signatures) = weighed_tx(&tx_env).unwrap();
+        assert_eq!(tx, &payment());
+        assert_eq!(signatures, inner_signatures.as_slice());
+    }
+}
@@ -1,10 +1,14 @@
 use std::{
+    fmt::Display,
     io::{stdin, Read},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    str::FromStr,
 };
 
+pub mod scval;
+
 use crate::xdr::{
-    Limits, Operation, ReadXdr, Transaction, TransactionEnvelope, TransactionV1Envelope,
+    self, Limits, Operation, ReadXdr, Transaction, TransactionEnvelope, TransactionV1Envelope,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -21,17 +25,125 @@ pub enum Error {
     OnlyTransactionV1Supported,
     #[error("too many operations, limited to 100 operations in a transaction")]
     TooManyOperations,
+    #[error("the XDR exceeds the decoding limits of depth {} and length {} bytes, raise them with `--xdr-limit` if the input is trusted", .0.depth, .0.len)]
+    LimitExceeded(DecodeLimits),
+    #[error("the XDR exceeds the decoding limits of depth {} and length {} bytes", .0.depth, .0.len)]
+    InputLimitExceeded(DecodeLimits),
+    #[error("failed to decode XDR: {0}")]
+    Decode(xdr::Error),
+    #[error("invalid `--xdr-limit` {0:?}, expected `DEPTH,LENGTH`, e.g. `500,10485760`")]
+    InvalidLimit(String),
+}
+
+/// Depth and length in bytes limiting the XDR decoded from untrusted input, so that a crafted
+/// value can't exhaust the memory or the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    pub depth: u32,
+    pub len: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            depth: 500,
+            len: 10 * 1024 * 1024,
+        }
+    }
+}
+
+impl FromStr for DecodeLimits {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (depth, len) = s
+            .split_once(',')
+            .ok_or_else(|| Error::InvalidLimit(s.to_string()))?;
+        Ok(Self {
+            depth: depth
+                .trim()
+                .parse()
+                .map_err(|_| Error::InvalidLimit(s.to_string()))?,
+            len: len
+                .trim()
+                .parse()
+                .map_err(|_| Error::InvalidLimit(s.to_string()))?,
+        })
+    }
+}
+
+impl Display for DecodeLimits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.depth, self.len)
+    }
+}
+
+impl DecodeLimits {
+    /// Decode a `T` from `base64` within the limits.
+    pub fn from_base64<T: ReadXdr>(self, base64: &str) -> Result<T, Error> {
+        T::from_xdr_base64(
+            base64,
+            Limits {
+                depth: self.depth,
+                len: self.len,
+            },
+        )
+        .map_err(|e| match e {
+            xdr::Error::DepthLimitExceeded | xdr::Error::LengthLimitExceeded => {
+                Error::LimitExceeded(self)
+            }
+            e => Error::Decode(e),
+        })
+    }
+}
+
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct LimitArgs {
+    /// Maximum depth and length in bytes of the XDR decoded from the input, as `DEPTH,LENGTH`
+    #[arg(long, value_name = "DEPTH,LENGTH", default_value_t)]
+    pub xdr_limit: DecodeLimits,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Convert an `ScVal` between JSON and base64 XDR
+    Scval(scval::Cmd),
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), scval::Error> {
+        match self {
+            Cmd::Scval(cmd) => cmd.run(),
+        }
+    }
 }
 
 pub fn tx_envelope_from_stdin() -> Result<TransactionEnvelope, Error> {
     from_stdin()
 }
+pub fn tx_envelope_from_file(path: &Path) -> Result<TransactionEnvelope, Error> {
+    let buf = std::fs::read_to_string(path)?;
+    decode_input(
+        buf.trim(),
+        DecodeLimits::default(),
+        Error::FileDecode(path.to_path_buf()),
+    )
+}
 pub fn from_stdin<T: ReadXdr>() -> Result<T, Error> {
     let mut buf = String::new();
     let _ = stdin()
         .read_to_string(&mut buf)
         .map_err(|_| Error::StdinDecode)?;
-    T::from_xdr_base64(buf.trim(), Limits::none()).map_err(|_| Error::StdinDecode)
+    decode_input(buf.trim(), DecodeLimits::default(), Error::StdinDecode)
+}
+
+/// Decode input within `limits`, failing with `other` unless a limit is exceeded.
+fn decode_input<T: ReadXdr>(base64: &str, limits: DecodeLimits, other: Error) -> Result<T, Error> {
+    limits.from_base64(base64).map_err(|e| match e {
+        Error::LimitExceeded(limits) => Error::InputLimitExceeded(limits),
+        _ => other,
+    })
 }
 
 pub fn unwrap_envelope_v1(tx_env: TransactionEnvelope) -> Result<Transaction, Error> {
@@ -48,3 +160,62 @@ pub fn add_op(tx_env: TransactionEnvelope, op: Operation) -> Result<TransactionE
     tx.operations = ops.try_into().map_err(|_| Error::TooManyOperations)?;
     Ok(tx.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_input_within_limits() {
+        let tx_env = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256([0; 32])),
+                fee: 100,
+                seq_num: xdr::SequenceNumber(1),
+                cond: xdr::Preconditions::None,
+                memo: xdr::Memo::None,
+                operations: xdr::VecM::default(),
+                ext: xdr::TransactionExt::V0,
+            },
+            signatures: xdr::VecM::default(),
+        });
+        let base64 = xdr::WriteXdr::to_xdr_base64(&tx_env, Limits::none()).unwrap();
+        let limits = DecodeLimits::default();
+        assert_eq!(
+            decode_input::<TransactionEnvelope>(&base64, limits, Error::StdinDecode).unwrap(),
+            tx_env
+        );
+        assert!(matches!(
+            decode_input::<TransactionEnvelope>("AAAA", limits, Error::StdinDecode),
+            Err(Error::StdinDecode)
+        ));
+        assert!(matches!(
+            decode_input::<TransactionEnvelope>(
+                &base64,
+                DecodeLimits { depth: 500, len: 8 },
+                Error::StdinDecode
+            ),
+            Err(Error::InputLimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn decode_limits_from_str() {
+        assert_eq!(
+            "100, 2048".parse::<DecodeLimits>().unwrap(),
+            DecodeLimits {
+                depth: 100,
+                len: 2048
+            }
+        );
+        let default = DecodeLimits::default();
+        assert_eq!(
+            default.to_string().parse::<DecodeLimits>().unwrap(),
+            default
+        );
+        assert!(matches!(
+            "100".parse::<DecodeLimits>(),
+            Err(Error::InvalidLimit(_))
+        ));
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    io::{stdin, Read},
+    path::PathBuf,
+};
+
+use soroban_spec_tools::Spec;
+
+use super::LimitArgs;
+use crate::xdr::{self, Limits, ScSpecTypeDef, ScSpecTypeUdt, ScVal, WriteXdr};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("reading value from stdin: {0}")]
+    Stdin(std::io::Error),
+    #[error("reading wasm {0}: {1}")]
+    ReadingWasm(PathBuf, std::io::Error),
+    #[error(transparent)]
+    Spec(#[from] soroban_spec_tools::Error),
+    #[error("invalid JSON value: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    XdrArgs(#[from] super::Error),
+    #[error("unknown type {0}, expected a primitive type such as `u128` or a type defined in the contract passed with `--wasm`")]
+    UnknownType(String),
+}
+
+#[derive(Debug, clap::Parser, Clone)]
+#[group(skip)]
+#[command(group(clap::ArgGroup::new("direction").required(true).args(["to_xdr", "to_json"])))]
+pub struct Cmd {
+    /// Value to convert. Read from stdin if not provided
+    pub value: Option<String>,
+    /// Convert a JSON value to a base64 `ScVal`
+    #[arg(long)]
+    pub to_xdr: bool,
+    /// Convert a base64 `ScVal` to a JSON value
+    #[arg(long)]
+    pub to_json: bool,
+    /// Type of the value: a primitive type, e.g. `u128`, `address` or `bytes`, or the name of a
+    /// struct, enum or union defined in the contract passed with `--wasm`. Without it the JSON is
+    /// the XDR JSON representation of the `ScVal`, as used by `stellar xdr`
+    #[arg(long = "type", value_name = "TYPE")]
+    pub type_: Option<String>,
+    /// Contract wasm whose spec defines the type passed with `--type`
+    #[arg(long, requires = "type_")]
+    pub wasm: Option<PathBuf>,
+    #[command(flatten)]
+    pub limits: LimitArgs,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<(), Error> {
+        let value = self.value()?;
+        let spec_type = self.typed()?;
+        let output = if self.to_xdr {
+            let json: serde_json::Value = serde_json::from_str(&value)?;
+            let val = match &spec_type {
+                Some((spec, type_)) => spec.from_json(&json, type_)?,
+                None => serde_json::from_value::<ScVal>(json)?,
+            };
+            val.to_xdr_base64(Limits::none())?
+        } else {
+            let val: ScVal = self.limits.xdr_limit.from_base64(&value)?;
+            match &spec_type {
+                Some((spec, type_)) => spec.xdr_to_json(&val, type_)?.to_string(),
+                None => serde_json::to_string(&val)?,
+            }
+        };
+        println!("{output}");
+        Ok(())
+    }
+
+    fn value(&self) -> Result<String, Error> {
+        if let Some(value) = &self.value {
+            return Ok(value.trim().to_string());
+        }
+        let mut value = String::new();
+        stdin().read_to_string(&mut value).map_err(Error::Stdin)?;
+        Ok(value.trim().to_string())
+    }
+
+    fn typed(&self) -> Result<Option<(Spec, ScSpecTypeDef)>, Error> {
+        let Some(name) = &self.type_ else {
+            return Ok(None);
+        };
+        let spec = match &self.wasm {
+            Some(wasm) => {
+                let bytes = std::fs::read(wasm).map_err(|e| Error::ReadingWasm(wasm.clone(), e))?;
+                Spec::from_wasm(&bytes)?
+            }
+            None => Spec::default(),
+        };
+        let type_ = match primitive_type(name) {
+            Some(type_) => type_,
+            None if spec.find(name).is_ok() => ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                name: name.parse().map_err(|_| Error::UnknownType(name.clone()))?,
+            }),
+            None => return Err(Error::UnknownType(name.clone())),
+        };
+        Ok(Some((spec, type_)))
+    }
+}
+
+fn primitive_type(name: &str) -> Option<ScSpecTypeDef> {
+    Some(match name {
+        "bool" => ScSpecTypeDef::Bool,
+        "u32" => ScSpecTypeDef::U32,
+        "i32" => ScSpecTypeDef::I32,
+        "u64" => ScSpecTypeDef::U64,
+        "i64" => ScSpecTypeDef::I64,
+        "u128" => ScSpecTypeDef::U128,
+        "i128" => ScSpecTypeDef::I128,
+        "u256" => ScSpecTypeDef::U256,
+        "i256" => ScSpecTypeDef::I256,
+        "bytes" => ScSpecTypeDef::Bytes,
+        "string" => ScSpecTypeDef::String,
+        "symbol" => ScSpecTypeDef::Symbol,
+        "address" => ScSpecTypeDef::Address,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xdr::ReadXdr;
+
+    use super::*;
+
+    fn round_trip(json: &str, type_: Option<&str>) {
+        let cmd = |value: &str, to_xdr: bool| Cmd {
+            value: Some(value.to_string()),
+            to_xdr,
+            to_json: !to_xdr,
+            type_: type_.map(ToString::to_string),
+            wasm: None,
+            limits: LimitArgs::default(),
+        };
+        let spec_type = cmd(json, true).typed().unwrap();
+        let json_value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let val = match &spec_type {
+            Some((spec, type_)) => spec.from_json(&json_value, type_).unwrap(),
+            None => serde_json::from_value::<ScVal>(json_value.clone()).unwrap(),
+        };
+        let base64 = val.to_xdr_base64(Limits::none()).unwrap();
+        let decoded = ScVal::from_xdr_base64(&base64, Limits::none()).unwrap();
+        let back = match &spec_type {
+            Some((spec, type_)) => spec.xdr_to_json(&decoded, type_).unwrap(),
+            None => serde_json::to_value(&decoded).unwrap(),
+        };
+        assert_eq!(back, json_value);
+        assert!(cmd(&base64, false).run().is_ok());
+    }
+
+    #[test]
+    fn round_trip_typed_primitives() {
+        round_trip(r#""340282366920938463463374607431768211455""#, Some("u128"));
+        round_trip(
+            r#""-170141183460469231731687303715884105728""#,
+            Some("i128"),
+        );
+        round_trip(
+            r#""GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF""#,
+            Some("address"),
+        );
+        round_trip(
+            r#""CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4""#,
+            Some("address"),
+        );
+    }
+
+    #[test]
+    fn round_trip_untyped_nested() {
+        let val = ScVal::Vec(Some(
+            vec![
+                ScVal::U128(xdr::UInt128Parts {
+                    hi: u64::MAX,
+                    lo: u64::MAX,
+                }),
+                ScVal::Map(Some(
+                    vec![xdr::ScMapEntry {
+                        key: ScVal::Symbol(xdr::ScSymbol("a".try_into().unwrap())),
+                        val: ScVal::I128(xdr::Int128Parts { hi: -1, lo: 0 }),
+                    }]
+                    .try_into()
+                    .unwrap(),
+                )),
+            ]
+            .try_into()
+            .unwrap(),
+        ));
+        round_trip(&serde_json::to_string(&val).unwrap(), None);
+    }
+
+    #[test]
+    fn to_json_within_limits() {
+        let nested = (0..10).fold(ScVal::U32(0), |val, _| {
+            ScVal::Vec(Some(vec![val].try_into().unwrap()))
+        });
+        let cmd = |xdr_limit: &str| Cmd {
+            value: Some(nested.to_xdr_base64(Limits::none()).unwrap()),
+            to_xdr: false,
+            to_json: true,
+            type_: None,
+            wasm: None,
+            limits: LimitArgs {
+                xdr_limit: xdr_limit.parse().unwrap(),
+            },
+        };
+        assert!(cmd("50,1000").run().is_ok());
+        assert!(matches!(
+            cmd("5,1000").run(),
+            Err(Error::XdrArgs(super::super::Error::LimitExceeded(_)))
+        ));
+        assert!(matches!(
+            cmd("20,10").run(),
+            Err(Error::XdrArgs(super::super::Error::LimitExceeded(_)))
+        ));
+    }
+
+    #[test]
+    fn unknown_type() {
+        let cmd = Cmd {
+            value: None,
+            to_xdr: true,
+            to_json: false,
+            type_: Some("Point".to_string()),
+            wasm: None,
+            limits: LimitArgs::default(),
+        };
+        assert!(matches!(cmd.typed(), Err(Error::UnknownType(_))));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::rpc::{GetTransactionResponse, GetTransactionResponseRaw, SimulateTransactionResponse};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::str::FromStr;
 use url::Url;
 
@@ -20,6 +21,8 @@ pub enum Error {
     Ulid(#[from] ulid::DecodeError),
     #[error(transparent)]
     Xdr(#[from] xdr::Error),
+    #[error("cached wasm does not match its hash {0}")]
+    WasmHashMismatch(String),
 }
 
 pub const XDG_DATA_HOME: &str = "XDG_DATA_HOME";
@@ -50,6 +53,12 @@ pub fn spec_dir() -> Result<std::path::PathBuf, Error> {
     Ok(dir)
 }
 
+pub fn wasm_dir() -> Result<std::path::PathBuf, Error> {
+    let dir = data_local_dir()?.join("wasm");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
 pub fn bucket_dir() -> Result<std::path::PathBuf, Error> {
     let dir = data_local_dir()?.join("bucket");
     std::fs::create_dir_all(&dir)?;
@@ -90,6 +99,23 @@ pub fn read_spec(hash: &str) -> Result<Vec<xdr::ScSpecEntry>, Error> {
     Ok(soroban_spec::read::parse_raw(&std::fs::read(file)?)?)
 }
 
+pub fn write_wasm(hash: &str, wasm: &[u8]) -> Result<(), Error> {
+    let file = wasm_dir()?.join(hash);
+    tracing::trace!("writing wasm to {:?}", file);
+    std::fs::write(file, wasm)?;
+    Ok(())
+}
+
+pub fn read_wasm(hash: &str) -> Result<Vec<u8>, Error> {
+    let file = wasm_dir()?.join(hash);
+    tracing::trace!("reading wasm from {:?}", file);
+    let wasm = std::fs::read(file)?;
+    if hex::encode(Sha256::digest(&wasm)) != hash {
+        return Err(Error::WasmHashMismatch(hash.to_string()));
+    }
+    Ok(wasm)
+}
+
 pub fn list_ulids() -> Result<Vec<ulid::Ulid>, Error> {
     let dir = actions_dir()?;
     let mut list = std::fs::read_dir(dir)?
@@ -214,4 +240,22 @@ mod test {
             _ => panic!("Action mismatch"),
         }
     }
+
+    #[test]
+    fn test_write_read_wasm() {
+        let t = assert_fs::TempDir::new().unwrap();
+        std::env::set_var(XDG_DATA_HOME, t.path().to_str().unwrap());
+        let wasm = b"\0asm\x01\0\0\0".to_vec();
+        let hash = hex::encode(Sha256::digest(&wasm));
+
+        write_wasm(&hash, &wasm).unwrap();
+        assert_eq!(read_wasm(&hash).unwrap(), wasm);
+
+        let other_hash = hex::encode([0u8; 32]);
+        write_wasm(&other_hash, &wasm).unwrap();
+        assert!(matches!(
+            read_wasm(&other_hash),
+            Err(Error::WasmHashMismatch(_))
+        ));
+    }
 }
@@ -45,6 +45,15 @@ impl Key {
         Ok(xdr::MuxedAccount::Ed25519(xdr::Uint256(bytes)))
     }
 
+    /// The G- or M-address of the key, using `hd_path` for a seed phrase.
+    pub fn address(&self, hd_path: Option<usize>) -> Result<String, Error> {
+        Ok(match self {
+            Key::Secret(secret) => secret.public_key(hd_path)?.to_string(),
+            Key::PublicKey(public) => public.to_string(),
+            Key::MuxedAccount(muxed) => muxed.to_string(),
+        })
+    }
+
     pub fn private_key(
         &self,
         hd_path: Option<usize>,
@@ -1,7 +1,7 @@
 use clap::arg;
 use directories::UserDirs;
 use itertools::Itertools;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 use std::{
     ffi::OsStr,
     fmt::Display,
@@ -56,6 +56,8 @@ pub enum Error {
     NetworkDeserialization,
     #[error("Failed to write network file: {0}")]
     NetworkCreationFailed(std::io::Error),
+    #[error("Failed to read identity {name}: {error}")]
+    InvalidIdentity { name: String, error: Box<Error> },
     #[error("Error Identity directory is invalid: {name}")]
     IdentityList { name: String },
     // #[error("Config file failed to deserialize")]
@@ -72,6 +74,8 @@ pub enum Error {
     ConfigRemoval(String, String),
     #[error("Failed to find config {0} for {1}")]
     ConfigMissing(String, String),
+    #[error("An identity with the name '{0}' already exists, use --overwrite to replace it")]
+    IdentityAlreadyExists(String),
     #[error(transparent)]
     String(#[from] std::string::FromUtf8Error),
     #[error(transparent)]
@@ -86,6 +90,12 @@ pub enum Error {
     CannotParseContractId(String, DecodeError),
     #[error("contract not found: {0}")]
     ContractNotFound(String),
+    #[error("network {name} has no default RPC provider ({placeholder}), add one with `stellar network add {name} --rpc-url <RPC_URL> --network-passphrase \"{passphrase}\"` or pass --rpc-url and --network-passphrase")]
+    NoDefaultRpcUrl {
+        name: String,
+        placeholder: String,
+        passphrase: String,
+    },
     #[error("Failed to read upgrade check file: {path}: {error}")]
     UpgradeCheckReadFailed { path: PathBuf, error: io::Error },
     #[error("Failed to write upgrade check file: {path}: {error}")]
@@ -102,6 +112,16 @@ pub enum Error {
     Key(#[from] key::Error),
 }
 
+/// The contents of an identity file. `hd_path` is informational, reading the file back only
+/// needs the secret.
+#[derive(Serialize)]
+struct IdentityFile<'a> {
+    #[serde(flatten)]
+    secret: &'a Secret,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hd_path: Option<usize>,
+}
+
 #[derive(Debug, clap::Args, Default, Clone)]
 #[group(skip)]
 pub struct Args {
@@ -109,6 +129,10 @@ pub struct Args {
     #[arg(long, global = true, help_heading = HEADING_GLOBAL)]
     pub global: bool,
 
+    /// Use local config, creating a `.stellar` directory in the config directory if there is none
+    #[arg(long, global = true, conflicts_with = "global", help_heading = HEADING_GLOBAL)]
+    pub local: bool,
+
     /// Location of config directory, default is "."
     #[arg(long, global = true, help_heading = HEADING_GLOBAL)]
     pub config_dir: Option<PathBuf>,
@@ -133,6 +157,16 @@ impl Display for Location {
     }
 }
 
+impl Location {
+    /// `local` or `global`.
+    pub fn scope(&self) -> &'static str {
+        match self {
+            Location::Local(_) => "local",
+            Location::Global(_) => "global",
+        }
+    }
+}
+
 impl AsRef<Path> for Location {
     fn as_ref(&self) -> &Path {
         match self {
@@ -152,11 +186,19 @@ impl Location {
 }
 
 impl Args {
+    /// Directory that identities, networks and contract aliases are written to:
+    /// 1. the global config if `--global` is given;
+    /// 2. the local config if `--local` or `--config-dir` is given, created if missing;
+    /// 3. the local config if a `.stellar` or `.soroban` directory exists in the current
+    ///    directory or one of its parents;
+    /// 4. the global config otherwise.
     pub fn config_dir(&self) -> Result<PathBuf, Error> {
         if self.global {
             global_config_path()
-        } else {
+        } else if self.local || self.config_dir.is_some() {
             self.local_config()
+        } else {
+            find_config_dir(self.current_dir()?).or_else(|_| global_config_path())
         }
     }
 
@@ -180,10 +222,21 @@ impl Args {
     }
 
     pub fn write_identity(&self, name: &str, secret: &Secret) -> Result<PathBuf, Error> {
+        self.write_identity_with_hd_path(name, secret, None)
+    }
+
+    /// Write an identity, noting `hd_path` in the file next to the secret when given.
+    pub fn write_identity_with_hd_path(
+        &self,
+        name: &str,
+        secret: &Secret,
+        hd_path: Option<usize>,
+    ) -> Result<PathBuf, Error> {
         if let Ok(Some(_)) = self.load_contract_from_alias(name) {
             return Err(Error::KeyCannotOverlapWithContractAlias(name.to_owned()));
         }
-        KeyType::Identity.write(name, secret, &self.config_dir()?)
+        let identity = IdentityFile { secret, hd_path };
+        KeyType::Identity.write(name, &identity, &self.config_dir()?)
     }
 
     pub fn write_public_key(
@@ -218,18 +271,21 @@ impl Args {
             .collect())
     }
 
-    pub fn list_identities_long(&self) -> Result<Vec<(String, String)>, Error> {
-        Ok(KeyType::Identity
+    pub fn list_identities_long(&self) -> Result<Vec<(String, Key, Location)>, Error> {
+        KeyType::Identity
             .list_paths(&self.local_and_global()?)
             .into_iter()
             .flatten()
             .map(|(name, location)| {
-                let path = match location {
-                    Location::Local(path) | Location::Global(path) => path,
-                };
-                (name, format!("{}", path.display()))
+                let key = KeyType::read_from_path::<Key>(location.as_ref()).map_err(|e| {
+                    Error::InvalidIdentity {
+                        name: name.clone(),
+                        error: Box::new(e),
+                    }
+                })?;
+                Ok((name, key, location))
             })
-            .collect())
+            .collect()
     }
 
     pub fn list_networks(&self) -> Result<Vec<String>, Error> {
@@ -291,14 +347,40 @@ impl Args {
             let Some(network) = network::DEFAULTS.get(name) else {
                 return res;
             };
+            // Mainnet has no public RPC, its default holds a pointer to providers instead of a URL
+            let (rpc_url, passphrase) = network;
+            if !rpc_url.starts_with("http") {
+                return Err(Error::NoDefaultRpcUrl {
+                    name: name.to_string(),
+                    placeholder: (*rpc_url).to_string(),
+                    passphrase: (*passphrase).to_string(),
+                });
+            }
             return Ok(network.into());
         }
         res
     }
 
+    /// Path of the file of identity `name`, in the config of `--global`, `--local` or
+    /// `--config-dir` when given, otherwise in the local config or else the global config.
+    pub fn identity_path(&self, name: &str) -> Result<PathBuf, Error> {
+        let dirs = if self.global {
+            vec![global_config_path()?]
+        } else if self.local || self.config_dir.is_some() {
+            vec![self.local_config()?]
+        } else {
+            vec![self.local_config()?, global_config_path()?]
+        };
+        dirs.iter()
+            .map(|dir| KeyType::Identity.path(dir, name))
+            .find(|path| path.exists())
+            .ok_or_else(|| Error::ConfigMissing(KeyType::Identity.to_string(), name.to_string()))
+    }
+
     pub fn remove_identity(&self, name: &str, global_args: &global::Args) -> Result<(), Error> {
         let print = Print::new(global_args.quiet);
-        let identity = self.read_identity(name)?;
+        let path = self.identity_path(name)?;
+        let identity: Key = KeyType::read_from_path(&path)?;
 
         if let Key::Secret(Secret::SecureStore { entry_name }) = identity {
             let entry = StellarEntry::new(&entry_name)?;
@@ -315,7 +397,27 @@ impl Args {
             }
         }
 
-        KeyType::Identity.remove(name, &self.config_dir()?)
+        std::fs::remove_file(&path)
+            .map_err(|_| Error::ConfigRemoval(KeyType::Identity.to_string(), name.to_string()))
+    }
+
+    /// Rename identity `old` to `new` in the config it is in, see [`Args::identity_path`], and
+    /// in the default identity. Returns the new path of the identity's file.
+    pub fn rename_identity(&self, old: &str, new: &str, overwrite: bool) -> Result<PathBuf, Error> {
+        if let Ok(Some(_)) = self.load_contract_from_alias(new) {
+            return Err(Error::KeyCannotOverlapWithContractAlias(new.to_owned()));
+        }
+        let from = self.identity_path(old)?;
+        let to = from.with_file_name(format!("{new}.toml"));
+        if to.exists() && !overwrite {
+            return Err(Error::IdentityAlreadyExists(new.to_string()));
+        }
+        std::fs::rename(&from, &to)?;
+        let config = Config::new()?;
+        if config.defaults.identity.as_deref() == Some(old) {
+            config.set_identity(new).save()?;
+        }
+        Ok(to)
     }
 
     pub fn remove_network(&self, name: &str) -> Result<(), Error> {
@@ -335,6 +437,31 @@ impl Args {
         Ok(Some(data))
     }
 
+    /// Names of the contract aliases defined for the network, sorted.
+    pub fn list_contract_aliases(&self, network_passphrase: &str) -> Result<Vec<String>, Error> {
+        let dir = self.config_dir()?.join("contract-ids");
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut aliases = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(alias) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let content = fs::read_to_string(&path)?;
+            let data: alias::Data = serde_json::from_str(&content).unwrap_or_default();
+            if data.ids.contains_key(network_passphrase) {
+                aliases.push(alias.to_string());
+            }
+        }
+        aliases.sort();
+        Ok(aliases)
+    }
+
     fn alias_path(&self, alias: &str) -> Result<PathBuf, Error> {
         let file_name = format!("{alias}.json");
         let config_dir = self.config_dir()?;
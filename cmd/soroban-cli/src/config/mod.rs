@@ -44,6 +44,8 @@ pub enum Error {
     StellarStrkey(#[from] stellar_strkey::DecodeError),
     #[error(transparent)]
     Address(#[from] address::Error),
+    #[error("no source account provided, use `--source` or set a default identity for the network with `stellar network use <NETWORK> --identity <NAME>`")]
+    NoSourceAccount,
 }
 
 #[derive(Debug, clap::Args, Clone, Default)]
@@ -59,7 +61,8 @@ pub struct Args {
     /// or a seed phrase (--source "kite urban…").
     /// If `--build-only` or `--sim-only` flags were NOT provided, this key will also be used to
     /// sign the final transaction. In that case, trying to sign with public key will fail.
-    pub source_account: UnresolvedMuxedAccount,
+    /// If omitted, the default identity of the network set with `network use --identity` is used.
+    pub source_account: Option<UnresolvedMuxedAccount>,
 
     #[arg(long)]
     /// If using a seed phrase, which hierarchical deterministic path to use, e.g. `m/44'/148'/{hd_path}`. Example: `--hd-path 1`. Default: `0`
@@ -73,12 +76,24 @@ impl Args {
     // TODO: Replace PublicKey with MuxedAccount once https://github.com/stellar/rs-stellar-xdr/pull/396 is merged.
     pub fn source_account(&self) -> Result<xdr::MuxedAccount, Error> {
         Ok(self
-            .source_account
+            .source()?
             .resolve_muxed_account(&self.locator, self.hd_path)?)
     }
 
+    /// The source account given with `--source`, falling back to the default identity of the
+    /// network.
+    pub fn source(&self) -> Result<UnresolvedMuxedAccount, Error> {
+        if let Some(source) = &self.source_account {
+            return Ok(source.clone());
+        }
+        self.get_network()?
+            .default_identity
+            .map(UnresolvedMuxedAccount::AliasOrSecret)
+            .ok_or(Error::NoSourceAccount)
+    }
+
     pub fn key_pair(&self) -> Result<ed25519_dalek::SigningKey, Error> {
-        let key = &self.source_account.resolve_secret(&self.locator)?;
+        let key = &self.source()?.resolve_secret(&self.locator)?;
         Ok(key.key_pair(self.hd_path)?)
     }
 
@@ -91,7 +106,7 @@ impl Args {
         let key = self.key_pair()?;
         let network = &self.get_network()?;
         let signer = Signer {
-            kind: SignerKind::Local(LocalKey { key }),
+            kind: SignerKind::Local(LocalKey { key, prompt: false }),
             print: Print::new(false),
         };
         Ok(signer.sign_tx(tx, network)?)
@@ -104,8 +119,13 @@ impl Args {
     ) -> Result<Option<Transaction>, Error> {
         let network = self.get_network()?;
         let source_key = self.key_pair()?;
-        let client = network.rpc_client()?;
-        let latest_ledger = client.get_latest_ledger().await?.sequence;
+        let client = network
+            .checked_rpc_client(self.network.rpc_options())
+            .await?;
+        let latest_ledger = network
+            .with_rpc_timeout(client.get_latest_ledger())
+            .await??
+            .sequence;
         let seq_num = latest_ledger + 60; // ~ 5 min
         Ok(signer::sign_soroban_authorizations(
             tx,
@@ -124,15 +144,7 @@ impl Args {
         &self,
         account: impl Into<xdr::AccountId>,
     ) -> Result<SequenceNumber, Error> {
-        let network = self.get_network()?;
-        let client = network.rpc_client()?;
-        Ok((client
-            .get_account(&account.into().to_string())
-            .await?
-            .seq_num
-            .0
-            + 1)
-        .into())
+        Ok(self.get_network()?.next_sequence_number(account).await?)
     }
 }
 
@@ -195,7 +207,7 @@ impl Config {
 
     pub fn save(&self) -> Result<(), locator::Error> {
         let toml_string = toml::to_string(&self)?;
-        let mut file = File::create(locator::config_file()?)?;
+        let mut file = File::create(locator::ensure_directory(locator::config_file()?)?)?;
         file.write_all(toml_string.as_bytes())?;
 
         Ok(())
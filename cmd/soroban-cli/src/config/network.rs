@@ -1,12 +1,20 @@
 use clap::arg;
 use itertools::Itertools;
-use jsonrpsee_http_client::HeaderMap;
+use jsonrpsee_core::{client::ClientT, params::ObjectParams};
+use jsonrpsee_http_client::{HeaderMap, HttpClient, HttpClientBuilder};
 use phf::phf_map;
-use reqwest::header::{HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue};
+use reqwest::{
+    header::{HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue},
+    StatusCode,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
 use stellar_strkey::ed25519::PublicKey;
 use url::Url;
 
@@ -15,6 +23,7 @@ use crate::utils::http;
 use crate::{
     commands::HEADING_RPC,
     rpc::{self, Client},
+    xdr,
 };
 pub mod passphrase;
 
@@ -43,18 +52,44 @@ STELLAR_NETWORK, STELLAR_RPC_URL and STELLAR_NETWORK_PASSPHRASE"#
     Rpc(#[from] rpc::Error),
     #[error(transparent)]
     HttpClient(#[from] reqwest::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
     #[error("Failed to parse JSON from {0}, {1}")]
     FailedToParseJSON(String, serde_json::Error),
     #[error("Invalid URL {0}")]
     InvalidUrl(String),
     #[error("funding failed: {0}")]
     FundingFailed(String),
+    #[error("friendbot is not available on mainnet, fund the account by sending it XLM from an existing account")]
+    FriendbotUnavailable,
+    #[error(
+        "account {0} was not found on the network, fund it first, e.g. with `stellar keys fund`"
+    )]
+    AccountNotFound(String),
     #[error(transparent)]
     InvalidHeaderName(#[from] InvalidHeaderName),
     #[error(transparent)]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
     #[error("invalid HTTP header: must be in the form 'key:value'")]
     InvalidHeader,
+    #[error("reading the value of header {0} from {1}: {2}")]
+    HeaderValueFile(String, PathBuf, std::io::Error),
+    #[error("the value of header {0} read from {1} contains control characters")]
+    HeaderValueControlCharacters(String, PathBuf),
+    #[error("RPC server at {url} is unreachable after {attempts} attempts: {source}")]
+    RpcUnreachable {
+        url: String,
+        attempts: u32,
+        source: rpc::Error,
+    },
+    #[error("RPC server at {url} returned an error: {source}")]
+    RpcErrorResponse { url: String, source: rpc::Error },
+    #[error("could not reach RPC at {url}: {reason}")]
+    RpcUnavailable { url: String, reason: String },
+    #[error("request to RPC at {url} timed out after {elapsed:?}, the timeout can be changed with `--rpc-timeout`")]
+    RpcTimeout { url: String, elapsed: Duration },
 }
 
 #[derive(Debug, clap::Args, Clone, Default)]
@@ -67,7 +102,9 @@ pub struct Args {
         help_heading = HEADING_RPC,
     )]
     pub rpc_url: Option<String>,
-    /// RPC Header(s) to include in requests to the RPC provider
+    /// RPC Header(s) to include in requests to the RPC provider. A value of the form `@path`
+    /// is read from the file at `path` when sending requests, e.g. `Authorization: @./token`. A
+    /// relative `path` is relative to the current directory
     #[arg(
         long = "rpc-header",
         env = "STELLAR_RPC_HEADERS",
@@ -93,9 +130,22 @@ pub struct Args {
         help_heading = HEADING_RPC,
     )]
     pub network: Option<String>,
+    /// Check that the RPC server is reachable and healthy with a `getHealth` request before
+    /// sending transactions or fetching ledger entries, failing early with a clear error when it
+    /// isn't
+    #[arg(long, env = "STELLAR_CHECK_RPC", help_heading = HEADING_RPC)]
+    pub check_rpc: bool,
 }
 
 impl Args {
+    /// Options for the requests to the RPC server given with these args, which are never saved
+    /// with a network.
+    pub fn rpc_options(&self) -> RpcOptions {
+        RpcOptions {
+            check: self.check_rpc,
+        }
+    }
+
     pub fn get(&self, locator: &locator::Args) -> Result<Network, Error> {
         match (
             self.network.as_deref(),
@@ -109,10 +159,34 @@ impl Args {
             (_, Some(rpc_url), Some(network_passphrase)) => Ok(Network {
                 rpc_url,
                 rpc_headers: self.rpc_headers.clone(),
+                default_identity: None,
                 network_passphrase,
             }),
         }
     }
+
+    /// Like [`Args::get`], but only the network passphrase is required, for commands that don't
+    /// use the RPC server, e.g. signing. The RPC URL is left empty when it isn't given, so
+    /// [`Network::rpc_client`] fails with [`Error::MissingRpcUrl`].
+    pub fn get_offline(&self, locator: &locator::Args) -> Result<Network, Error> {
+        match (self.rpc_url.clone(), self.network_passphrase.clone()) {
+            (rpc_url, Some(network_passphrase)) => Ok(Network {
+                rpc_url: rpc_url.unwrap_or_default(),
+                rpc_headers: self.rpc_headers.clone(),
+                default_identity: None,
+                network_passphrase,
+            }),
+            _ => self.get(locator),
+        }
+    }
+}
+
+/// Options for the requests to the RPC server that are given per invocation, unlike the
+/// [`Network`] they are sent to, see [`Args::rpc_options`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RpcOptions {
+    /// Check that the RPC server is healthy before the first request, from `--check-rpc`
+    pub check: bool,
 }
 
 #[derive(Debug, clap::Args, Serialize, Deserialize, Clone)]
@@ -125,7 +199,10 @@ pub struct Network {
         help_heading = HEADING_RPC,
     )]
     pub rpc_url: String,
-    /// Optional header (e.g. API Key) to include in requests to the RPC
+    /// Optional header (e.g. API Key) to include in requests to the RPC. A value of the form
+    /// `@path` is read from the file at `path` when sending requests, e.g.
+    /// `Authorization: @./token`. A relative `path` is relative to the current directory, and
+    /// saved as an absolute path
     #[arg(
         long = "rpc-header",
         env = "STELLAR_RPC_HEADERS",
@@ -135,6 +212,7 @@ pub struct Network {
         value_delimiter = '\n',
         value_parser = parse_http_header,
     )]
+    #[serde(default)]
     pub rpc_headers: Vec<(String, String)>,
     /// Network passphrase to sign the transaction sent to the rpc server
     #[arg(
@@ -143,6 +221,11 @@ pub struct Network {
             help_heading = HEADING_RPC,
         )]
     pub network_passphrase: String,
+    /// Identity used as the source account on this network when `--source` is omitted, set with
+    /// `network use --identity`
+    #[arg(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_identity: Option<String>,
 }
 
 fn parse_http_header(header: &str) -> Result<(String, String), Error> {
@@ -153,18 +236,127 @@ fn parse_http_header(header: &str) -> Result<(String, String), Error> {
         .next_tuple()
         .ok_or_else(|| Error::InvalidHeader)?;
 
-    // Check that the headers are properly formatted
+    // Check that the headers are properly formatted. A value of the form `@path` is only read
+    // from the file when building the client, so that a saved network never holds the secret
+    // itself. Its path is made absolute here, so that it doesn't depend on the directory the
+    // network is used from once saved.
     HeaderName::from_str(key)?;
+    if let Some(path) = value.strip_prefix('@') {
+        let path = std::path::absolute(path)
+            .map_err(|e| Error::HeaderValueFile(key.to_string(), PathBuf::from(path), e))?;
+        return Ok((key.to_string(), format!("@{}", path.display())));
+    }
     HeaderValue::from_str(value)?;
 
     Ok((key.to_string(), value.to_string()))
 }
 
+fn read_header_value(key: &str, path: &Path) -> Result<String, Error> {
+    let value = fs::read_to_string(path)
+        .map_err(|e| Error::HeaderValueFile(key.to_string(), path.to_path_buf(), e))?;
+    let value = value.trim();
+    if value.chars().any(char::is_control) {
+        return Err(Error::HeaderValueControlCharacters(
+            key.to_string(),
+            path.to_path_buf(),
+        ));
+    }
+    Ok(value.to_string())
+}
+
+/// How requests that fail with a transient error are retried: friendbot requests in
+/// [`Network::fund_address_with_retry`] that time out, fail to connect or get a
+/// `429 Too Many Requests` or a `5xx`, and requests for the network's info in
+/// [`Network::helper_url_with_retry`] while the RPC server can't be reached, e.g. while it is
+/// still starting up. Commands build it from `--rpc-retries` and `--rpc-retry-backoff` with
+/// [`global::Args::rpc_retry`](crate::commands::global::Args::rpc_retry).
+#[derive(Debug, Clone, Copy)]
+pub struct Retry {
+    /// Number of retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled after every retry
+    pub initial_backoff: Duration,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            max_retries: 4,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Default of `--rpc-timeout`, in seconds.
+pub const DEFAULT_RPC_TIMEOUT: u64 = 60;
+
+static RPC_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
+/// Set the timeout of every request made with [`Network::with_rpc_timeout`], from the global
+/// `--rpc-timeout`. Only the first call has an effect.
+pub fn set_rpc_timeout(timeout: Duration) {
+    let _ = RPC_TIMEOUT.set(timeout);
+}
+
+/// Whether the request failed to connect to the server, including resolving its host, or
+/// timed out, as opposed to the server responding, even with an error.
+fn is_unreachable(e: &rpc::Error) -> bool {
+    match e {
+        rpc::Error::JsonRpc(jsonrpsee_core::Error::RequestTimeout) => true,
+        rpc::Error::JsonRpc(e @ jsonrpsee_core::Error::Transport(_)) => {
+            let transport = std::error::Error::source(e)
+                .and_then(|e| e.downcast_ref::<jsonrpsee_http_client::transport::Error>());
+            match transport {
+                Some(jsonrpsee_http_client::transport::Error::Http(e)) => e
+                    .downcast_ref::<hyper::Error>()
+                    .is_some_and(|e| e.is_connect() || e.is_timeout()),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Outcome of a successful friendbot request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Funded {
+    /// The account was created and funded
+    Funded,
+    /// Friendbot reported that the account is already funded to the starting balance
+    AlreadyFunded,
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
 impl Network {
-    pub async fn helper_url(&self, addr: &str) -> Result<Url, Error> {
+    /// Friendbot URL funding `addr`. `friendbot_url`, when given, is used instead of the local
+    /// `/friendbot` endpoint or the URL reported by the RPC server.
+    pub async fn helper_url(&self, addr: &str, friendbot_url: Option<&Url>) -> Result<Url, Error> {
+        self.helper_url_with_retry(addr, friendbot_url, Retry::default())
+            .await
+    }
+
+    /// Like [`Network::helper_url`], retrying the requests to the RPC server with `retry` while
+    /// it is unreachable.
+    pub async fn helper_url_with_retry(
+        &self,
+        addr: &str,
+        friendbot_url: Option<&Url>,
+        retry: Retry,
+    ) -> Result<Url, Error> {
         tracing::debug!("address {addr:?}");
         let rpc_url = Url::from_str(&self.rpc_url)
             .map_err(|_| Error::InvalidUrl(self.rpc_url.to_string()))?;
+        if self.network_passphrase.as_str() == passphrase::MAINNET {
+            return Err(Error::FriendbotUnavailable);
+        }
+        if let Some(friendbot_url) = friendbot_url {
+            let mut url = friendbot_url.clone();
+            url.query_pairs_mut().append_pair("addr", addr);
+            return Ok(url);
+        }
         if self.network_passphrase.as_str() == passphrase::LOCAL {
             let mut local_url = rpc_url;
             local_url.set_path("/friendbot");
@@ -172,9 +364,13 @@ impl Network {
             Ok(local_url)
         } else {
             let client = self.rpc_client()?;
-            let network = client.get_network().await?;
+            let network = self
+                .with_rpc_retry("getNetwork", retry, || client.get_network())
+                .await?;
             tracing::debug!("network {network:?}");
-            let url = client.friendbot_url().await?;
+            let url = self
+                .with_rpc_retry("friendbot URL", retry, || client.friendbot_url())
+                .await?;
             tracing::debug!("URL {url:?}");
             let mut url = Url::from_str(&url).map_err(|e| {
                 tracing::error!("{e}");
@@ -185,11 +381,53 @@ impl Network {
         }
     }
 
+    /// Fund `addr` using the network's friendbot, retrying transient failures with the default
+    /// [`Retry`] policy.
+    pub async fn fund_address(
+        &self,
+        addr: &PublicKey,
+        friendbot_url: Option<&Url>,
+    ) -> Result<(), Error> {
+        self.fund_address_with_retry(addr, friendbot_url, Retry::default())
+            .await?;
+        Ok(())
+    }
+
     #[allow(clippy::similar_names)]
-    pub async fn fund_address(&self, addr: &PublicKey) -> Result<(), Error> {
-        let uri = self.helper_url(&addr.to_string()).await?;
+    pub async fn fund_address_with_retry(
+        &self,
+        addr: &PublicKey,
+        friendbot_url: Option<&Url>,
+        retry: Retry,
+    ) -> Result<Funded, Error> {
+        let uri = self
+            .helper_url_with_retry(&addr.to_string(), friendbot_url, retry)
+            .await?;
         tracing::debug!("URL {uri:?}");
-        let response = http::client().get(uri.as_str()).send().await?;
+        let mut backoff = retry.initial_backoff;
+        let mut attempt = 0;
+        let response = loop {
+            attempt += 1;
+            let response = http::client().get(uri.as_str()).send().await;
+            let failure = match &response {
+                Ok(r) if is_transient_status(r.status()) => Some(r.status().to_string()),
+                Err(e) if e.is_timeout() || e.is_connect() => Some(e.to_string()),
+                _ => None,
+            };
+            let Some(failure) = failure else {
+                break response?;
+            };
+            if attempt > retry.max_retries {
+                return Err(Error::FundingFailed(format!(
+                    "{failure} after {attempt} attempts"
+                )));
+            }
+            tracing::warn!(
+                "friendbot request failed on attempt {attempt} ({failure}), retrying in {backoff:?}"
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+        };
 
         let request_successful = response.status().is_success();
         let body = response.bytes().await?;
@@ -204,32 +442,237 @@ impl Network {
                     // user's goal is to get funded, and the account is funded
                     // so it is success much the same.
                     tracing::debug!("already funded error ignored because account is funded");
-                } else {
-                    return Err(Error::FundingFailed(detail.to_string()));
+                    return Ok(Funded::AlreadyFunded);
                 }
-            } else {
-                return Err(Error::FundingFailed("unknown cause".to_string()));
+                return Err(Error::FundingFailed(detail.to_string()));
             }
+            return Err(Error::FundingFailed("unknown cause".to_string()));
         }
-        Ok(())
+        Ok(Funded::Funded)
+    }
+
+    /// Run `request` against the RPC server, retrying with `retry` while the server is
+    /// unreachable.
+    async fn with_rpc_retry<T, F, Fut>(
+        &self,
+        what: &str,
+        retry: Retry,
+        request: F,
+    ) -> Result<T, Error>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, rpc::Error>>,
+    {
+        let mut backoff = retry.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let source = match self.with_rpc_timeout(request()).await? {
+                Ok(value) => return Ok(value),
+                Err(e) if is_unreachable(&e) => e,
+                Err(source) => {
+                    return Err(Error::RpcErrorResponse {
+                        url: self.rpc_url.clone(),
+                        source,
+                    })
+                }
+            };
+            if attempt > retry.max_retries {
+                return Err(Error::RpcUnreachable {
+                    url: self.rpc_url.clone(),
+                    attempts: attempt,
+                    source,
+                });
+            }
+            tracing::warn!(
+                "{what} request to {} failed on attempt {attempt} ({source}), retrying in {backoff:?}",
+                self.rpc_url
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+        }
+    }
+
+    /// Await `request` to the RPC server, failing with [`Error::RpcTimeout`] if it takes longer
+    /// than `--rpc-timeout`. The timeout covers the whole request, including connecting. The
+    /// client doesn't take a timeout, so every request to the RPC server is made through this.
+    pub async fn with_rpc_timeout<F: std::future::Future>(
+        &self,
+        request: F,
+    ) -> Result<F::Output, Error> {
+        let timeout = RPC_TIMEOUT
+            .get()
+            .copied()
+            .unwrap_or(Duration::from_secs(DEFAULT_RPC_TIMEOUT));
+        self.with_timeout(timeout, request).await
+    }
+
+    async fn with_timeout<F: std::future::Future>(
+        &self,
+        timeout: Duration,
+        request: F,
+    ) -> Result<F::Output, Error> {
+        tokio::time::timeout(timeout, request)
+            .await
+            .map_err(|_| Error::RpcTimeout {
+                url: self.rpc_url.clone(),
+                elapsed: timeout,
+            })
     }
 
     pub fn rpc_uri(&self) -> Result<Url, Error> {
         Url::from_str(&self.rpc_url).map_err(|_| Error::InvalidUrl(self.rpc_url.to_string()))
     }
 
+    fn rpc_unavailable(&self, reason: impl Into<String>) -> Error {
+        Error::RpcUnavailable {
+            url: self.rpc_url.clone(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Check that `rpc_url` is an HTTP(S) URL, so that a mistyped one fails before any request.
+    fn validate_rpc_url(&self) -> Result<Url, Error> {
+        let url = self.rpc_uri()?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(Error::InvalidUrl(self.rpc_url.clone()));
+        }
+        Ok(url)
+    }
+
+    /// Check with a `getHealth` request that the RPC server can be reached and is healthy.
+    pub async fn check_rpc(&self) -> Result<(), Error> {
+        let client = self.json_rpc_client()?;
+        let health: GetHealthResponse = self
+            .with_rpc_timeout(client.request("getHealth", ObjectParams::new()))
+            .await?
+            .map_err(|e| self.rpc_unavailable(e.to_string()))?;
+        if health.status == "healthy" {
+            Ok(())
+        } else {
+            Err(self.rpc_unavailable(format!("the server is {}", health.status)))
+        }
+    }
+
+    /// Client for the RPC server like [`Network::rpc_client`], first checking that the server
+    /// is healthy with [`Network::check_rpc`] when `--check-rpc` is given. Commands use this
+    /// before their first requests so that an unreachable server fails with a clear error
+    /// rather than deep into the command.
+    pub async fn checked_rpc_client(&self, options: RpcOptions) -> Result<Client, Error> {
+        let client = self.rpc_client()?;
+        if options.check {
+            self.check_rpc().await?;
+        }
+        Ok(client)
+    }
+
+    /// The configured `rpc_headers`, with values of the form `@path` read from the file at
+    /// `path`. Relative paths are resolved from the current directory.
+    fn resolved_rpc_headers(&self) -> Result<Vec<(String, String)>, Error> {
+        self.rpc_headers
+            .iter()
+            .map(|(name, value)| {
+                let value = match value.strip_prefix('@') {
+                    Some(path) => read_header_value(name, Path::new(path))?,
+                    None => value.clone(),
+                };
+                Ok((name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Client for the RPC server of the network, sending the configured `rpc_headers` with
+    /// every request. Construct clients with this rather than with `rpc::Client::new` so that
+    /// the headers, e.g. API keys of RPC providers, are never dropped.
     pub fn rpc_client(&self) -> Result<Client, Error> {
-        let mut header_hash_map = HashMap::new();
-        for (header_name, header_value) in &self.rpc_headers {
-            header_hash_map.insert(header_name.to_string(), header_value.to_string());
+        if self.rpc_url.is_empty() {
+            return Err(Error::MissingRpcUrl);
         }
+        self.validate_rpc_url()?;
+        let header_map = self.rpc_header_map()?;
+        crate::log::rpc::client(&self.rpc_url, &self.rpc_headers);
 
-        let header_map: HeaderMap = (&header_hash_map)
+        Ok(rpc::Client::new_with_headers(&self.rpc_url, header_map)?)
+    }
+
+    fn rpc_header_map(&self) -> Result<HeaderMap, Error> {
+        let mut header_hash_map = HashMap::new();
+        for (header_name, header_value) in self.resolved_rpc_headers()? {
+            header_hash_map.insert(header_name, header_value);
+        }
+        (&header_hash_map)
             .try_into()
-            .map_err(|_| Error::InvalidHeader)?;
+            .map_err(|_| Error::InvalidHeader)
+    }
 
-        Ok(rpc::Client::new_with_headers(&self.rpc_url, header_map)?)
+    /// JSON-RPC client for requests to the RPC server that `rpc::Client` has no method for, or
+    /// whose response it does not return as is.
+    fn json_rpc_client(&self) -> Result<HttpClient, Error> {
+        if self.rpc_url.is_empty() {
+            return Err(Error::MissingRpcUrl);
+        }
+        let url = self.validate_rpc_url()?;
+        Ok(HttpClientBuilder::default()
+            .set_headers(self.rpc_header_map()?)
+            .build(url.as_str())
+            .map_err(rpc::Error::JsonRpc)?)
     }
+
+    /// Send `tx_env` with a `sendTransaction` request and return the response as is. Unlike
+    /// `rpc::Client::send_transaction`, which only formats the result of a rejected transaction
+    /// into its error and treats `TRY_AGAIN_LATER` as sent, this leaves the status, the result
+    /// and the diagnostic events to the caller.
+    pub async fn send_transaction(
+        &self,
+        tx_env: &xdr::TransactionEnvelope,
+    ) -> Result<SendTransactionResponse, Error> {
+        let client = self.json_rpc_client()?;
+        let mut params = ObjectParams::new();
+        params.insert(
+            "transaction",
+            xdr::WriteXdr::to_xdr_base64(tx_env, xdr::Limits::none())?,
+        )?;
+        Ok(self
+            .with_rpc_timeout(client.request("sendTransaction", params))
+            .await?
+            .map_err(rpc::Error::JsonRpc)?)
+    }
+
+    /// Sequence number for the next transaction of `account`, one more than its current one.
+    pub async fn next_sequence_number(
+        &self,
+        account: impl Into<xdr::AccountId>,
+    ) -> Result<xdr::SequenceNumber, Error> {
+        let account = account.into().to_string();
+        let client = self.rpc_client()?;
+        match self.with_rpc_timeout(client.get_account(&account)).await? {
+            Ok(entry) => Ok(xdr::SequenceNumber(entry.seq_num.0 + 1)),
+            Err(rpc::Error::NotFound(..)) => Err(Error::AccountNotFound(account)),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Response to a `getHealth` request, see [`Network::check_rpc`].
+#[derive(Debug, Clone, Deserialize)]
+struct GetHealthResponse {
+    status: String,
+}
+
+/// Response to a `sendTransaction` request, see [`Network::send_transaction`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendTransactionResponse {
+    pub hash: String,
+    /// One of `PENDING`, `DUPLICATE`, `TRY_AGAIN_LATER` or `ERROR`.
+    pub status: String,
+    /// Base64 `TransactionResult` of a transaction rejected with `ERROR`.
+    #[serde(default)]
+    pub error_result_xdr: Option<String>,
+    /// Base64 `DiagnosticEvent`s of a transaction rejected with `ERROR`, when the server has
+    /// diagnostic events enabled.
+    #[serde(default)]
+    pub diagnostic_events_xdr: Option<Vec<String>>,
 }
 
 pub static DEFAULTS: phf::Map<&'static str, (&'static str, &'static str)> = phf_map! {
@@ -257,6 +700,7 @@ impl From<&(&str, &str)> for Network {
         Self {
             rpc_url: n.0.to_string(),
             rpc_headers: Vec::new(),
+            default_identity: None,
             network_passphrase: n.1.to_string(),
         }
     }
@@ -277,10 +721,14 @@ mod tests {
             rpc_url: "http://localhost:8000".to_string(),
             network_passphrase: passphrase::LOCAL.to_string(),
             rpc_headers: Vec::new(),
+            default_identity: None,
         };
 
         let result = network
-            .helper_url("GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI")
+            .helper_url(
+                "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI",
+                None,
+            )
             .await;
 
         assert!(result.is_ok());
@@ -288,6 +736,267 @@ mod tests {
         assert_eq!(url.as_str(), "http://localhost:8000/friendbot?addr=GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI");
     }
 
+    #[tokio::test]
+    async fn test_helper_url_override_wins_for_local_network() {
+        let network = Network {
+            rpc_url: "http://localhost:8000".to_string(),
+            network_passphrase: passphrase::LOCAL.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        let friendbot_url = Url::parse("http://localhost:8001/fund?token=abc").unwrap();
+
+        let url = network
+            .helper_url(
+                "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI",
+                Some(&friendbot_url),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(url.as_str(), "http://localhost:8001/fund?token=abc&addr=GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI");
+    }
+
+    #[tokio::test]
+    async fn test_helper_url_mainnet_has_no_friendbot() {
+        let network = Network {
+            rpc_url: "http://localhost:8000".to_string(),
+            network_passphrase: passphrase::MAINNET.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+
+        let result = network
+            .helper_url(
+                "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI",
+                None,
+            )
+            .await;
+
+        assert!(matches!(result, Err(Error::FriendbotUnavailable)));
+    }
+
+    #[tokio::test]
+    async fn test_next_sequence_number_account_not_found() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/")
+            .with_body_from_request(|req| {
+                let body: Value = serde_json::from_slice(req.body().unwrap()).unwrap();
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"].clone(),
+                    "result": { "entries": [], "latestLedger": 2 }
+                })
+                .to_string()
+                .into()
+            })
+            .create_async()
+            .await;
+
+        let network = Network {
+            rpc_url: server.url(),
+            network_passphrase: passphrase::TESTNET.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        let account = xdr::AccountId::from_str(FUND_ADDR).unwrap();
+        let err = network.next_sequence_number(account).await.unwrap_err();
+        assert!(matches!(&err, Error::AccountNotFound(address) if address == FUND_ADDR));
+        assert!(err.to_string().contains("stellar keys fund"));
+    }
+
+    fn health_mock_network(server: &Server) -> Network {
+        Network {
+            rpc_url: server.url(),
+            network_passphrase: passphrase::TESTNET.to_string(),
+            rpc_headers: vec![("api-key".to_string(), "secret".to_string())],
+            default_identity: None,
+        }
+    }
+
+    /// Mock `getHealth` response body reporting `status`.
+    fn health_response(status: &'static str) -> impl Fn(&mockito::Request) -> Vec<u8> {
+        move |req| {
+            let body: Value = serde_json::from_slice(req.body().unwrap()).unwrap();
+            json!({
+                "jsonrpc": "2.0",
+                "id": body["id"].clone(),
+                "result": {
+                    "status": status,
+                    "latestLedger": 2,
+                    "oldestLedger": 1,
+                    "ledgerRetentionWindow": 17280
+                }
+            })
+            .to_string()
+            .into()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_rpc_healthy() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_header("api-key", "secret")
+            .match_body(mockito::Matcher::PartialJson(
+                json!({ "method": "getHealth" }),
+            ))
+            .with_body_from_request(health_response("healthy"))
+            .create_async()
+            .await;
+
+        health_mock_network(&server).check_rpc().await.unwrap();
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_check_rpc_unhealthy() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/")
+            .with_body_from_request(health_response("catching up"))
+            .create_async()
+            .await;
+
+        let err = health_mock_network(&server).check_rpc().await.unwrap_err();
+        assert!(
+            matches!(&err, Error::RpcUnavailable { reason, .. } if reason.contains("catching up"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_rpc_error_status() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/")
+            .with_status(503)
+            .create_async()
+            .await;
+
+        let err = health_mock_network(&server).check_rpc().await.unwrap_err();
+        assert!(matches!(&err, Error::RpcUnavailable { reason, .. } if reason.contains("503")));
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_returns_the_error_result() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .match_header("api-key", "secret")
+            .match_body(mockito::Matcher::PartialJson(
+                json!({ "method": "sendTransaction" }),
+            ))
+            .with_body_from_request(|req| {
+                let body: Value = serde_json::from_slice(req.body().unwrap()).unwrap();
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"].clone(),
+                    "result": {
+                        "hash": "00".repeat(32),
+                        "status": "ERROR",
+                        "errorResultXdr": "AAAAAAAAAGT////3AAAAAA==",
+                        "latestLedger": 2,
+                        "latestLedgerCloseTime": "0"
+                    }
+                })
+                .to_string()
+                .into()
+            })
+            .create_async()
+            .await;
+        let tx_env = xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx: xdr::Transaction {
+                source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256([0; 32])),
+                fee: 100,
+                seq_num: xdr::SequenceNumber(1),
+                cond: xdr::Preconditions::None,
+                memo: xdr::Memo::None,
+                operations: xdr::VecM::default(),
+                ext: xdr::TransactionExt::V0,
+            },
+            signatures: xdr::VecM::default(),
+        });
+
+        let response = health_mock_network(&server)
+            .send_transaction(&tx_env)
+            .await
+            .unwrap();
+        mock.assert_async().await;
+        assert_eq!(response.status, "ERROR");
+        assert_eq!(
+            response.error_result_xdr.as_deref(),
+            Some("AAAAAAAAAGT////3AAAAAA==")
+        );
+        assert_eq!(response.diagnostic_events_xdr, None);
+    }
+
+    #[tokio::test]
+    async fn test_check_rpc_unreachable() {
+        let network = Network {
+            // Nothing listens on port 1 of localhost
+            rpc_url: "http://127.0.0.1:1".to_string(),
+            network_passphrase: passphrase::TESTNET.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+
+        let err = network.check_rpc().await.unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("could not reach RPC at http://127.0.0.1:1: "));
+    }
+
+    #[tokio::test]
+    async fn test_rpc_request_timeout() {
+        // A server that accepts connections but takes longer to respond than the timeout.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                    drop(socket);
+                });
+            }
+        });
+
+        let timeout = Duration::from_millis(100);
+        let network = Network {
+            rpc_url: url.clone(),
+            network_passphrase: passphrase::TESTNET.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        let client = network.rpc_client().unwrap();
+        let err = network
+            .with_timeout(timeout, client.get_account(FUND_ADDR))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::RpcTimeout { url: u, elapsed } if *u == url && *elapsed == timeout)
+        );
+        assert!(err.to_string().contains(&url));
+        assert!(err.to_string().contains("--rpc-timeout"));
+    }
+
+    #[test]
+    fn test_rpc_client_invalid_url() {
+        for rpc_url in ["not a url", "localhost:8000"] {
+            let network = Network {
+                rpc_url: rpc_url.to_string(),
+                network_passphrase: passphrase::TESTNET.to_string(),
+                rpc_headers: Vec::new(),
+                default_identity: None,
+            };
+            assert!(matches!(
+                network.rpc_client(),
+                Err(Error::InvalidUrl(url)) if url == rpc_url
+            ));
+        }
+    }
+
     #[tokio::test]
     async fn test_helper_url_test_network() {
         let mut server = Server::new_async().await;
@@ -315,9 +1024,13 @@ mod tests {
             rpc_url: server.url(),
             network_passphrase: passphrase::TESTNET.to_string(),
             rpc_headers: Vec::new(),
+            default_identity: None,
         };
         let url = network
-            .helper_url("GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI")
+            .helper_url(
+                "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI",
+                None,
+            )
             .await
             .unwrap();
         assert_eq!(url.as_str(), "https://friendbot.stellar.org/?addr=GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI");
@@ -346,14 +1059,329 @@ mod tests {
             rpc_url: server.url(),
             network_passphrase: passphrase::TESTNET.to_string(),
             rpc_headers: Vec::new(),
+            default_identity: None,
         };
         let url = network
-            .helper_url("GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI")
+            .helper_url(
+                "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI",
+                None,
+            )
             .await
             .unwrap();
         assert_eq!(url.as_str(), "https://friendbot.stellar.org/secret?api_key=123456&user=demo&addr=GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI");
     }
 
+    #[tokio::test]
+    async fn test_helper_url_retries_unreachable_rpc() {
+        // Nothing listens on the port once the listener is dropped, so connections are refused.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let network = Network {
+            rpc_url: format!("http://127.0.0.1:{port}"),
+            network_passphrase: passphrase::TESTNET.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        let result = network
+            .helper_url_with_retry(
+                "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI",
+                None,
+                Retry {
+                    max_retries: 2,
+                    initial_backoff: Duration::from_millis(1),
+                },
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::RpcUnreachable { attempts: 3, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_helper_url_error_response_is_not_retried() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .with_body_from_request(|req| {
+                let body: Value = serde_json::from_slice(req.body().unwrap()).unwrap();
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"].clone(),
+                    "error": { "code": -32603, "message": "internal error" }
+                })
+                .to_string()
+                .into()
+            })
+            .expect(1)
+            .create_async()
+            .await;
+        let network = Network {
+            rpc_url: server.url(),
+            network_passphrase: passphrase::TESTNET.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        let result = network
+            .helper_url_with_retry(
+                "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI",
+                None,
+                Retry {
+                    max_retries: 2,
+                    initial_backoff: Duration::from_millis(1),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(Error::RpcErrorResponse { .. })));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_helper_url_error_status_is_not_retried() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .with_status(500)
+            .expect(1)
+            .create_async()
+            .await;
+        let network = Network {
+            rpc_url: server.url(),
+            network_passphrase: passphrase::TESTNET.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        let result = network
+            .helper_url_with_retry(FUND_ADDR, None, fast_retry(2))
+            .await;
+        assert!(matches!(result, Err(Error::RpcErrorResponse { .. })));
+        mock.assert_async().await;
+    }
+
+    const FUND_ADDR: &str = "GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI";
+
+    fn fast_retry(max_retries: u32) -> Retry {
+        Retry {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fund_address_retries_transient_failures() {
+        let mut server = Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/friendbot")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let funded = server
+            .mock("GET", "/friendbot")
+            .match_query(mockito::Matcher::Any)
+            .with_body(json!({ "successful": true }).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let network = Network {
+            rpc_url: server.url(),
+            network_passphrase: passphrase::LOCAL.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        let status = network
+            .fund_address_with_retry(
+                &PublicKey::from_string(FUND_ADDR).unwrap(),
+                None,
+                fast_retry(3),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status, Funded::Funded);
+        unavailable.assert_async().await;
+        funded.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_fund_address_fails_after_exhausting_retries() {
+        let mut server = Server::new_async().await;
+        let rate_limited = server
+            .mock("GET", "/friendbot")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let network = Network {
+            rpc_url: server.url(),
+            network_passphrase: passphrase::LOCAL.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        let err = network
+            .fund_address_with_retry(
+                &PublicKey::from_string(FUND_ADDR).unwrap(),
+                None,
+                fast_retry(2),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::FundingFailed(_)), "{err}");
+        rate_limited.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_fund_address_already_funded_is_not_retried() {
+        let mut server = Server::new_async().await;
+        let already_funded = server
+            .mock("GET", "/friendbot")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .with_body(
+                json!({ "detail": "createAccountAlreadyExist (account already funded to starting balance)" })
+                    .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let network = Network {
+            rpc_url: server.url(),
+            network_passphrase: passphrase::LOCAL.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        let status = network
+            .fund_address_with_retry(
+                &PublicKey::from_string(FUND_ADDR).unwrap(),
+                None,
+                fast_retry(2),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status, Funded::AlreadyFunded);
+        already_funded.assert_async().await;
+    }
+
+    #[test]
+    fn test_network_with_headers_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let locator = locator::Args {
+            config_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let network = Network {
+            rpc_url: "https://rpc.example.com".to_string(),
+            rpc_headers: vec![
+                ("Authorization".to_string(), "Bearer abc".to_string()),
+                ("X-Api-Key".to_string(), "123".to_string()),
+            ],
+            network_passphrase: passphrase::TESTNET.to_string(),
+            default_identity: None,
+        };
+        locator.write_network("provider", &network).unwrap();
+        let read = locator.read_network("provider").unwrap();
+        assert_eq!(read.rpc_url, network.rpc_url);
+        assert_eq!(read.rpc_headers, network.rpc_headers);
+        assert_eq!(read.network_passphrase, network.network_passphrase);
+    }
+
+    #[test]
+    fn test_get_falls_back_to_default_networks() {
+        let dir = tempfile::tempdir().unwrap();
+        let locator = locator::Args {
+            config_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let args = Args {
+            network: Some("futurenet".to_string()),
+            ..Args::default()
+        };
+        let network = args.get(&locator).unwrap();
+        assert_eq!(network.rpc_url, DEFAULTS["futurenet"].0);
+        assert_eq!(network.network_passphrase, passphrase::FUTURENET);
+
+        let args = Args {
+            network: Some("mainnet".to_string()),
+            ..Args::default()
+        };
+        let err = args.get(&locator).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Config(locator::Error::NoDefaultRpcUrl { .. })
+        ));
+        assert!(err.to_string().contains("stellar network add mainnet"));
+
+        // A saved network takes precedence over the placeholder
+        let saved = Network {
+            rpc_url: "https://mainnet.example.com".to_string(),
+            rpc_headers: Vec::new(),
+            network_passphrase: passphrase::MAINNET.to_string(),
+            default_identity: None,
+        };
+        locator.write_network("mainnet", &saved).unwrap();
+        assert_eq!(args.get(&locator).unwrap().rpc_url, saved.rpc_url);
+    }
+
+    #[test]
+    fn test_check_rpc_is_an_rpc_option() {
+        let args = Args {
+            network: Some("testnet".to_string()),
+            check_rpc: true,
+            ..Args::default()
+        };
+        assert!(args.rpc_options().check);
+        assert!(!Args::default().rpc_options().check);
+    }
+
+    #[tokio::test]
+    async fn test_checked_rpc_client_checks_only_with_check_rpc() {
+        let network = Network {
+            // Nothing listens on port 1 of localhost
+            rpc_url: "http://127.0.0.1:1".to_string(),
+            network_passphrase: passphrase::TESTNET.to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+        };
+        network
+            .checked_rpc_client(RpcOptions::default())
+            .await
+            .unwrap();
+        assert!(matches!(
+            network.checked_rpc_client(RpcOptions { check: true }).await,
+            Err(Error::RpcUnavailable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_network_without_headers_field_reads() {
+        let network: Network = toml::from_str(&format!(
+            "rpc_url = \"https://rpc.example.com\"\nnetwork_passphrase = \"{}\"\n",
+            passphrase::TESTNET
+        ))
+        .unwrap();
+        assert!(network.rpc_headers.is_empty());
+    }
+
+    #[test]
+    fn test_get_offline_with_passphrase_only() {
+        let args = Args {
+            network_passphrase: Some(passphrase::TESTNET.to_string()),
+            ..Args::default()
+        };
+        let locator = locator::Args::default();
+        assert!(matches!(args.get(&locator), Err(Error::MissingRpcUrl)));
+        let network = args.get_offline(&locator).unwrap();
+        assert_eq!(network.network_passphrase, passphrase::TESTNET);
+        assert!(matches!(network.rpc_client(), Err(Error::MissingRpcUrl)));
+    }
+
     // testing parse_header function
     #[tokio::test]
     async fn test_parse_http_header_ok() {
@@ -383,6 +1411,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_http_header_keeps_file_reference() {
+        let token = std::env::current_dir().unwrap().join("token");
+        let result = parse_http_header("Authorization: @./token").unwrap();
+        assert_eq!(
+            result,
+            ("Authorization".to_string(), format!("@{}", token.display()))
+        );
+
+        let result = parse_http_header(&format!("Authorization: @{}", token.display())).unwrap();
+        assert_eq!(
+            result,
+            ("Authorization".to_string(), format!("@{}", token.display()))
+        );
+    }
+
+    #[test]
+    fn test_rpc_headers_value_from_file() {
+        let temp_dir = assert_fs::TempDir::new().unwrap();
+        let token = temp_dir.path().join("token");
+        let network = Network {
+            rpc_url: "http://localhost:1234".to_string(),
+            network_passphrase: "Network passphrase".to_string(),
+            rpc_headers: vec![("Authorization".to_string(), format!("@{}", token.display()))],
+            default_identity: None,
+        };
+
+        fs::write(&token, "Bearer 1234\n").unwrap();
+        assert_eq!(
+            network.resolved_rpc_headers().unwrap(),
+            vec![("Authorization".to_string(), "Bearer 1234".to_string())]
+        );
+        assert!(network.rpc_client().is_ok());
+
+        fs::write(&token, "Bearer\r\n1234").unwrap();
+        assert!(matches!(
+            network.rpc_client(),
+            Err(Error::HeaderValueControlCharacters(..))
+        ));
+
+        fs::remove_file(&token).unwrap();
+        assert!(matches!(
+            network.rpc_client(),
+            Err(Error::HeaderValueFile(..))
+        ));
+    }
+
     // testing rpc_client function - we're testing this and the parse_http_header function separately because when a user has their network already configured in a toml file, the parse_http_header function is not called and we want to make sure that if the toml file is correctly formatted, the rpc_client function will work as expected
 
     #[tokio::test]
@@ -391,6 +1466,7 @@ mod tests {
             rpc_url: "http://localhost:1234".to_string(),
             network_passphrase: "Network passphrase".to_string(),
             rpc_headers: [].to_vec(),
+            default_identity: None,
         };
 
         let result = network.rpc_client();
@@ -403,6 +1479,7 @@ mod tests {
             rpc_url: "http://localhost:1234".to_string(),
             network_passphrase: "Network passphrase".to_string(),
             rpc_headers: [("Authorization".to_string(), "Bearer 1234".to_string())].to_vec(),
+            default_identity: None,
         };
 
         let result = network.rpc_client();
@@ -419,6 +1496,7 @@ mod tests {
                 ("api-key".to_string(), "5678".to_string()),
             ]
             .to_vec(),
+            default_identity: None,
         };
 
         let result = network.rpc_client();
@@ -431,6 +1509,7 @@ mod tests {
             rpc_url: "http://localhost:8000".to_string(),
             network_passphrase: passphrase::LOCAL.to_string(),
             rpc_headers: [(INVALID_HEADER_NAME.to_string(), "Bearer".to_string())].to_vec(),
+            default_identity: None,
         };
 
         let result = network.rpc_client();
@@ -447,6 +1526,7 @@ mod tests {
             rpc_url: "http://localhost:8000".to_string(),
             network_passphrase: passphrase::LOCAL.to_string(),
             rpc_headers: [("api-key".to_string(), INVALID_HEADER_VALUE.to_string())].to_vec(),
+            default_identity: None,
         };
 
         let result = network.rpc_client();
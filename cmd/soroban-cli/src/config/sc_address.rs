@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use crate::xdr;
 
@@ -24,8 +24,10 @@ pub enum Error {
     Locator(#[from] locator::Error),
     #[error(transparent)]
     Key(#[from] key::Error),
-    #[error("Account alias not Found{0}")]
+    #[error("Account alias not found: {0}")]
     AccountAliasNotFound(String),
+    #[error("{0} is an account, not a contract")]
+    NotAContract(String),
 }
 
 impl FromStr for UnresolvedScAddress {
@@ -45,24 +47,136 @@ impl UnresolvedScAddress {
         locator: &locator::Args,
         network_passphrase: &str,
     ) -> Result<xdr::ScAddress, Error> {
-        let alias = match self {
-            UnresolvedScAddress::Resolved(addr) => return Ok(addr),
-            UnresolvedScAddress::Alias(alias) => alias,
-        };
-        let contract = UnresolvedContract::resolve_alias(&alias, locator, network_passphrase);
-        let key = locator.read_key(&alias);
-        match (contract, key) {
-            (Ok(contract), Ok(_)) => {
-                eprintln!(
-                    "Warning: ScAddress alias {alias} is ambiguous, assuming it is a contract"
-                );
-                Ok(xdr::ScAddress::Contract(xdr::Hash(contract.0)))
+        match self {
+            UnresolvedScAddress::Resolved(addr) => Ok(addr),
+            UnresolvedScAddress::Alias(alias) => resolve_alias(alias, locator, network_passphrase),
+        }
+    }
+
+    /// Resolve to the id of a contract, failing when the address or alias is an account's.
+    pub fn resolve_contract(
+        self,
+        locator: &locator::Args,
+        network_passphrase: &str,
+    ) -> Result<stellar_strkey::Contract, Error> {
+        match self.resolve(locator, network_passphrase) {
+            Ok(xdr::ScAddress::Contract(xdr::Hash(contract_id))) => {
+                Ok(stellar_strkey::Contract(contract_id))
+            }
+            Ok(account @ xdr::ScAddress::Account(_)) => {
+                Err(Error::NotAContract(account.to_string()))
+            }
+            Err(Error::AccountAliasNotFound(alias)) => {
+                Err(locator::Error::ContractNotFound(alias).into())
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl From<&UnresolvedContract> for UnresolvedScAddress {
+    fn from(contract: &UnresolvedContract) -> Self {
+        match contract {
+            UnresolvedContract::Resolved(contract) => {
+                UnresolvedScAddress::Resolved(xdr::ScAddress::Contract(xdr::Hash(contract.0)))
+            }
+            UnresolvedContract::Alias(alias) => UnresolvedScAddress::Alias(alias.clone()),
+        }
+    }
+}
+
+/// Resolve a batch of addresses, looking each distinct alias up only once. The results are in
+/// the order of `addresses`, so that a failure to resolve one doesn't hide the others.
+pub fn resolve_all(
+    addresses: &[UnresolvedScAddress],
+    locator: &locator::Args,
+    network_passphrase: &str,
+) -> Vec<Result<xdr::ScAddress, Error>> {
+    let mut resolved: HashMap<&str, xdr::ScAddress> = HashMap::new();
+    addresses
+        .iter()
+        .map(|address| match address {
+            UnresolvedScAddress::Resolved(addr) => Ok(addr.clone()),
+            UnresolvedScAddress::Alias(alias) => {
+                if let Some(addr) = resolved.get(alias.as_str()) {
+                    return Ok(addr.clone());
+                }
+                let addr = resolve_alias(alias.clone(), locator, network_passphrase)?;
+                resolved.insert(alias, addr.clone());
+                Ok(addr)
             }
-            (Ok(contract), _) => Ok(xdr::ScAddress::Contract(xdr::Hash(contract.0))),
-            (_, Ok(key)) => Ok(xdr::ScAddress::Account(
-                key.muxed_account(None)?.account_id(),
-            )),
-            _ => Err(Error::AccountAliasNotFound(alias)),
+        })
+        .collect()
+}
+
+fn resolve_alias(
+    alias: String,
+    locator: &locator::Args,
+    network_passphrase: &str,
+) -> Result<xdr::ScAddress, Error> {
+    let contract = UnresolvedContract::resolve_alias(&alias, locator, network_passphrase);
+    let key = locator.read_key(&alias);
+    match (contract, key) {
+        (Ok(contract), Ok(_)) => {
+            eprintln!("Warning: ScAddress alias {alias} is ambiguous, assuming it is a contract");
+            Ok(xdr::ScAddress::Contract(xdr::Hash(contract.0)))
+        }
+        (Ok(contract), _) => Ok(xdr::ScAddress::Contract(xdr::Hash(contract.0))),
+        (_, Ok(key)) => Ok(xdr::ScAddress::Account(
+            key.muxed_account(None)?.account_id(),
+        )),
+        _ => Err(Error::AccountAliasNotFound(alias)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::secret::Secret;
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn locator(temp_dir: &tempfile::TempDir) -> locator::Args {
+        locator::Args {
+            global: false,
+            local: false,
+            config_dir: Some(temp_dir.path().to_path_buf()),
         }
     }
+
+    #[test]
+    fn resolve_contract_accepts_contract_address() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let address: UnresolvedScAddress =
+            "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4"
+                .parse()
+                .unwrap();
+        let contract = address
+            .resolve_contract(&locator(&temp_dir), PASSPHRASE)
+            .unwrap();
+        assert_eq!(contract, stellar_strkey::Contract([0; 32]));
+    }
+
+    #[test]
+    fn resolve_contract_rejects_account_alias() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let locator = locator(&temp_dir);
+        locator
+            .write_identity("alice", &Secret::test_seed_phrase().unwrap())
+            .unwrap();
+        let result =
+            UnresolvedScAddress::Alias("alice".to_string()).resolve_contract(&locator, PASSPHRASE);
+        assert!(matches!(result, Err(Error::NotAContract(_))));
+    }
+
+    #[test]
+    fn resolve_contract_reports_missing_contract_alias() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let result = UnresolvedScAddress::Alias("missing".to_string())
+            .resolve_contract(&locator(&temp_dir), PASSPHRASE);
+        assert!(matches!(
+            result,
+            Err(Error::Locator(locator::Error::ContractNotFound(_)))
+        ));
+    }
 }
@@ -29,6 +29,8 @@ pub enum Error {
     Keyring(#[from] keyring::Error),
     #[error("Secure Store does not reveal secret key")]
     SecureStoreDoesNotRevealSecretKey,
+    #[error("--hd-path {0} cannot be used with a secret key, only with a seed phrase")]
+    HdPathForSecretKey(usize),
 }
 
 #[derive(Debug, clap::Args, Clone)]
@@ -98,12 +100,22 @@ impl From<SeedPhrase> for Secret {
 }
 
 impl Secret {
+    /// The index of the hierarchical deterministic path of the key to use, `hd_path` defaulting
+    /// to 0. A secret key is a single key without paths, so any other path is an error for it.
+    pub fn hd_path(&self, hd_path: Option<usize>) -> Result<usize, Error> {
+        match (self, hd_path.unwrap_or_default()) {
+            (Secret::SecretKey { .. }, hd_path @ 1..) => Err(Error::HdPathForSecretKey(hd_path)),
+            (_, hd_path) => Ok(hd_path),
+        }
+    }
+
     pub fn private_key(&self, index: Option<usize>) -> Result<PrivateKey, Error> {
+        let index = self.hd_path(index)?;
         Ok(match self {
             Secret::SecretKey { secret_key } => PrivateKey::from_string(secret_key)?,
             Secret::SeedPhrase { seed_phrase } => PrivateKey::from_payload(
                 &sep5::SeedPhrase::from_str(seed_phrase)?
-                    .from_path_index(index.unwrap_or_default(), None)?
+                    .from_path_index(index, None)?
                     .private()
                     .0,
             )?,
@@ -116,7 +128,7 @@ impl Secret {
     pub fn public_key(&self, index: Option<usize>) -> Result<PublicKey, Error> {
         if let Secret::SecureStore { entry_name } = self {
             let entry = keyring::StellarEntry::new(entry_name)?;
-            Ok(entry.get_public_key(index)?)
+            Ok(entry.get_public_key(Some(self.hd_path(index)?))?)
         } else {
             let key = self.key_pair(index)?;
             Ok(stellar_strkey::ed25519::PublicKey::from_payload(
@@ -126,10 +138,11 @@ impl Secret {
     }
 
     pub fn signer(&self, hd_path: Option<usize>, print: Print) -> Result<Signer, Error> {
+        let hd_path = Some(self.hd_path(hd_path)?);
         let kind = match self {
             Secret::SecretKey { .. } | Secret::SeedPhrase { .. } => {
                 let key = self.key_pair(hd_path)?;
-                SignerKind::Local(LocalKey { key })
+                SignerKind::Local(LocalKey { key, prompt: false })
             }
             Secret::SecureStore { entry_name } => SignerKind::SecureStore(SecureStoreEntry {
                 name: entry_name.to_string(),
@@ -161,7 +174,7 @@ pub fn seed_phrase_from_seed(seed: Option<&str>) -> Result<SeedPhrase, Error> {
 }
 
 pub fn test_seed_phrase() -> Result<SeedPhrase, Error> {
-    Ok("0000000000000000".parse()?)
+    seed_phrase_from_seed(Some("0000000000000000"))
 }
 
 #[cfg(test)]
@@ -209,6 +222,57 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_seed_phrase_hd_path_defaults_to_0() {
+        let secret = Secret::from_str(TEST_SEED_PHRASE).unwrap();
+        assert_eq!(secret.hd_path(None).unwrap(), 0);
+        assert_eq!(
+            secret.public_key(None).unwrap(),
+            secret.public_key(Some(0)).unwrap()
+        );
+        assert_eq!(
+            secret.private_key(None).unwrap().to_string(),
+            secret.private_key(Some(0)).unwrap().to_string()
+        );
+        assert_eq!(
+            secret
+                .signer(None, Print::new(true))
+                .unwrap()
+                .public_key()
+                .unwrap(),
+            secret
+                .signer(Some(0), Print::new(true))
+                .unwrap()
+                .public_key()
+                .unwrap()
+        );
+        assert_ne!(
+            secret.public_key(None).unwrap(),
+            secret.public_key(Some(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_secret_key_hd_path() {
+        let secret = Secret::from_str(TEST_SECRET_KEY).unwrap();
+        assert_eq!(
+            secret.public_key(Some(0)).unwrap().to_string(),
+            TEST_PUBLIC_KEY
+        );
+        assert!(matches!(
+            secret.public_key(Some(1)),
+            Err(Error::HdPathForSecretKey(1))
+        ));
+        assert!(matches!(
+            secret.private_key(Some(2)),
+            Err(Error::HdPathForSecretKey(2))
+        ));
+        assert!(matches!(
+            secret.signer(Some(1), Print::new(true)),
+            Err(Error::HdPathForSecretKey(1))
+        ));
+    }
+
     #[test]
     fn test_secret_from_invalid_string() {
         let secret = Secret::from_str("invalid");
@@ -4,6 +4,7 @@ use crate::{
     xdr::{self, TransactionEnvelope},
 };
 use clap::arg;
+use std::io::Read;
 
 use super::{
     locator,
@@ -29,6 +30,10 @@ pub enum Error {
     StrKey(#[from] stellar_strkey::DecodeError),
     #[error(transparent)]
     Xdr(#[from] xdr::Error),
+    #[error("reading the key from stdin: {0}")]
+    StdinKey(std::io::Error),
+    #[error("--check only asks for confirmation before signing with a local key, {0} is in the OS secure storage; remove --check to sign with it")]
+    CheckRequiresLocalKey(String),
 }
 
 #[derive(Debug, clap::Args, Clone, Default)]
@@ -38,10 +43,30 @@ pub struct Args {
     #[arg(long, env = "STELLAR_SIGN_WITH_KEY")]
     pub sign_with_key: Option<String>,
 
-    #[arg(long, requires = "sign_with_key")]
+    /// Sign with a secret key or seed phrase read from stdin, so that it doesn't end up in the shell history or the process list. The key is never printed.
+    #[arg(long, conflicts_with_all = ["sign_with_key", "signers", "sign_with_lab"])]
+    pub sign_with_key_stdin: bool,
+
+    /// Sign with several local keys or identities in one invocation, appending a signature for each, e.g. `--signers alice,bob`. Keys that have already signed the envelope are skipped.
+    #[arg(
+        long,
+        num_args = 1..,
+        value_delimiter = ',',
+        conflicts_with_all = ["sign_with_key", "sign_with_lab"],
+    )]
+    pub signers: Vec<String>,
+
+    #[arg(long)]
     /// If using a seed phrase to sign, sets which hierarchical deterministic path to use, e.g. `m/44'/148'/{hd_path}`. Example: `--hd-path 1`. Default: `0`
     pub hd_path: Option<usize>,
 
+    /// Ask for confirmation on the terminal before signing with a local key, showing the
+    /// transaction hash, a summary of its operations and the public key that will sign it. Fails
+    /// without signing when stdin or stderr is not a terminal, or when the key is in OS secure
+    /// storage
+    #[arg(long, conflicts_with = "sign_with_lab")]
+    pub check: bool,
+
     #[allow(clippy::doc_markdown)]
     /// Sign with https://lab.stellar.org
     #[arg(long, conflicts_with = "sign_with_key", env = "STELLAR_SIGN_WITH_LAB")]
@@ -57,16 +82,103 @@ impl Args {
         quiet: bool,
     ) -> Result<TransactionEnvelope, Error> {
         let print = Print::new(quiet);
-        let signer = if self.sign_with_lab {
-            Signer {
+        if !self.signers.is_empty() {
+            return self.sign_tx_env_with_signers(tx, locator, network, &print);
+        }
+        let signer = self.signer(locator, print)?;
+        Ok(signer.sign_tx_env(tx, network)?)
+    }
+
+    /// The signer selected with `--sign-with-key`, `--sign-with-key-stdin` or `--sign-with-lab`.
+    pub fn signer(&self, locator: &locator::Args, print: Print) -> Result<Signer, Error> {
+        if self.sign_with_lab {
+            return Ok(Signer {
                 kind: SignerKind::Lab,
                 print,
-            }
+            });
+        }
+        let secret = if self.sign_with_key_stdin {
+            let mut key = String::new();
+            std::io::stdin()
+                .read_to_string(&mut key)
+                .map_err(Error::StdinKey)?;
+            key.trim().parse::<secret::Secret>()?
         } else {
             let key_or_name = self.sign_with_key.as_deref().ok_or(Error::NoSignWithKey)?;
-            let secret = locator.get_secret_key(key_or_name)?;
-            secret.signer(self.hd_path, print)?
+            locator.get_secret_key(key_or_name)?
         };
-        Ok(signer.sign_tx_env(tx, network)?)
+        self.with_check(secret.signer(self.hd_path, print)?)
+    }
+
+    /// Ask for confirmation before signing with `signer` if `--check` is set. Only a local key
+    /// can ask, so `--check` fails with any other signer rather than signing without asking.
+    fn with_check(&self, mut signer: Signer) -> Result<Signer, Error> {
+        match &mut signer.kind {
+            SignerKind::Local(key) => key.prompt = self.check,
+            SignerKind::SecureStore(entry) if self.check => {
+                return Err(Error::CheckRequiresLocalKey(entry.name.clone()));
+            }
+            SignerKind::SecureStore(_) | SignerKind::Lab => {}
+        }
+        Ok(signer)
+    }
+
+    fn sign_tx_env_with_signers(
+        &self,
+        tx: &TransactionEnvelope,
+        locator: &locator::Args,
+        network: &Network,
+        print: &Print,
+    ) -> Result<TransactionEnvelope, Error> {
+        let mut tx_env = tx.clone();
+        for key_or_name in &self.signers {
+            let signer = self.with_check(
+                locator
+                    .get_secret_key(key_or_name)?
+                    .signer(self.hd_path, print.clone())?,
+            )?;
+            if signer.has_signed(&tx_env, network)? {
+                print.warnln(format!(
+                    "Skipping {key_or_name}: the envelope is already signed by this key"
+                ));
+                continue;
+            }
+            tx_env = signer.sign_tx_env(&tx_env, network)?;
+        }
+        Ok(tx_env)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::SecureStoreEntry;
+
+    fn secure_store_signer() -> Signer {
+        Signer {
+            kind: SignerKind::SecureStore(SecureStoreEntry {
+                name: "secure.store.alice".to_string(),
+                hd_path: None,
+            }),
+            print: Print::new(true),
+        }
+    }
+
+    #[test]
+    fn check_rejects_secure_store_key() {
+        let args = Args {
+            check: true,
+            ..Default::default()
+        };
+        let Err(err) = args.with_check(secure_store_signer()) else {
+            panic!("expected --check to be rejected for a key in secure storage");
+        };
+        assert!(matches!(&err, Error::CheckRequiresLocalKey(name) if name == "secure.store.alice"));
+        assert!(err.to_string().contains("--check"));
+    }
+
+    #[test]
+    fn secure_store_key_signs_without_check() {
+        assert!(Args::default().with_check(secure_store_signer()).is_ok());
     }
 }
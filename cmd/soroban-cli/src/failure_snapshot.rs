@@ -0,0 +1,369 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use sha2::{Digest, Sha256};
+use soroban_ledger_snapshot::LedgerSnapshot;
+
+use crate::{
+    commands::global,
+    config::network::{self, Network},
+    print::Print,
+    rpc,
+    xdr::{
+        self, ConfigSettingEntry, ConfigSettingId, ContractDataDurability, ContractExecutable,
+        HostFunction, LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerKey,
+        LedgerKeyConfigSetting, LedgerKeyContractCode, LedgerKeyContractData, OperationBody,
+        ScAddress, ScContractInstance, ScVal, StateArchivalSettings, Transaction, TransactionExt,
+    },
+};
+
+/// Most keys the RPC server accepts in a `getLedgerEntries` request.
+const MAX_KEYS_PER_REQUEST: usize = 200;
+
+/// Base reserve of the public networks, which is not available from the RPC server.
+const BASE_RESERVE: u32 = 5_000_000;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Network(#[from] network::Error),
+    #[error(transparent)]
+    Rpc(#[from] rpc::Error),
+    #[error("writing the snapshot {}: {1}", .0.display())]
+    WriteSnapshot(PathBuf, soroban_ledger_snapshot::Error),
+}
+
+/// Writes a snapshot of the ledger entries of a transaction that failed to simulate or be
+/// applied, when `--dump-snapshot-on-fail` is given, so that the failure can be reproduced
+/// offline with `tx simulate --snapshot`.
+pub struct OnFail<'a> {
+    path: Option<&'a Path>,
+    network: &'a Network,
+    print: &'a Print,
+}
+
+impl<'a> OnFail<'a> {
+    pub fn new(
+        global_args: Option<&'a global::Args>,
+        network: &'a Network,
+        print: &'a Print,
+    ) -> Self {
+        Self {
+            path: global_args.and_then(|args| args.dump_snapshot_on_fail.as_deref()),
+            network,
+            print,
+        }
+    }
+
+    /// Return `result`, first writing the snapshot of the ledger entries of `tx` if it is an
+    /// error. Failing to write the snapshot is only warned about, so that the error of `result`
+    /// is the one reported.
+    pub async fn check<T, E>(&self, result: Result<T, E>, tx: &Transaction) -> Result<T, E> {
+        if result.is_err() {
+            self.dump(tx).await;
+        }
+        result
+    }
+
+    /// Write the snapshot of the ledger entries of `tx`, warning if that fails.
+    pub async fn dump(&self, tx: &Transaction) {
+        let Some(path) = self.path else {
+            return;
+        };
+        match write(path, tx, self.network).await {
+            Ok(count) => {
+                self.print.saveln(format!(
+                    "Wrote a snapshot of the {count} ledger entries of the failed transaction to {}, reproduce the failure with `stellar tx simulate --snapshot {}`",
+                    path.display(),
+                    path.display()
+                ));
+                if misses_storage(tx) {
+                    self.print.warnln(
+                        "The failed transaction has no footprint, so the snapshot doesn't have the contract storage it uses other than the instance, add it with `stellar snapshot create`",
+                    );
+                }
+            }
+            Err(e) => self.print.warnln(format!(
+                "Failed to write the snapshot of the failed transaction: {e}"
+            )),
+        }
+    }
+}
+
+/// Fetch the ledger entries `tx` uses from the RPC server and write them with the network's
+/// config settings and the latest ledger's info to `path` as a `LedgerSnapshot`. Returns the
+/// number of entries written.
+pub async fn write(path: &Path, tx: &Transaction, network: &Network) -> Result<usize, Error> {
+    let client = network.rpc_client()?;
+    let latest = network
+        .with_rpc_timeout(client.get_latest_ledger())
+        .await??;
+    let mut entries = fetch(network, &client, &keys(tx)).await?;
+    let code = entries
+        .iter()
+        .filter_map(|entry| match &entry.ledger_entry.data {
+            LedgerEntryData::ContractData(data) => match &data.val {
+                ScVal::ContractInstance(ScContractInstance {
+                    executable: ContractExecutable::Wasm(hash),
+                    ..
+                }) => Some(LedgerKey::ContractCode(LedgerKeyContractCode {
+                    hash: hash.clone(),
+                })),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    entries.extend(fetch(network, &client, &code).await?);
+
+    let (min_persistent_entry_ttl, min_temp_entry_ttl, max_entry_ttl) = entries
+        .iter()
+        .find_map(|entry| match &entry.ledger_entry.data {
+            LedgerEntryData::ConfigSetting(ConfigSettingEntry::StateArchival(
+                StateArchivalSettings {
+                    min_persistent_ttl,
+                    min_temporary_ttl,
+                    max_entry_ttl,
+                    ..
+                },
+            )) => Some((*min_persistent_ttl, *min_temporary_ttl, *max_entry_ttl)),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let count = entries.len();
+    let snapshot = LedgerSnapshot {
+        protocol_version: latest.protocol_version,
+        sequence_number: latest.sequence,
+        // The close time of the latest ledger is not available from the RPC server, the current
+        // time is close to it.
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        network_id: Sha256::digest(&network.network_passphrase).into(),
+        base_reserve: BASE_RESERVE,
+        min_persistent_entry_ttl,
+        min_temp_entry_ttl,
+        max_entry_ttl,
+        ledger_entries: entries
+            .into_iter()
+            .map(|entry| {
+                (
+                    Box::new(entry.key),
+                    (Box::new(entry.ledger_entry), entry.live_until),
+                )
+            })
+            .collect(),
+    };
+    snapshot
+        .write_file(path)
+        .map_err(|e| Error::WriteSnapshot(path.to_path_buf(), e))?;
+    Ok(count)
+}
+
+struct FetchedEntry {
+    key: LedgerKey,
+    ledger_entry: LedgerEntry,
+    live_until: Option<u32>,
+}
+
+async fn fetch(
+    network: &Network,
+    client: &rpc::Client,
+    keys: &[LedgerKey],
+) -> Result<Vec<FetchedEntry>, Error> {
+    let mut entries = Vec::new();
+    for chunk in keys.chunks(MAX_KEYS_PER_REQUEST) {
+        let response = network
+            .with_rpc_timeout(client.get_full_ledger_entries(chunk))
+            .await??;
+        entries.extend(response.entries.into_iter().map(|entry| {
+            let live_until = matches!(
+                entry.key,
+                LedgerKey::ContractData(_) | LedgerKey::ContractCode(_)
+            )
+            .then_some(entry.live_until_ledger_seq);
+            FetchedEntry {
+                ledger_entry: LedgerEntry {
+                    last_modified_ledger_seq: entry.last_modified_ledger,
+                    data: entry.val,
+                    ext: LedgerEntryExt::V0,
+                },
+                key: entry.key,
+                live_until,
+            }
+        }));
+    }
+    Ok(entries)
+}
+
+/// Keys of the ledger entries `tx` uses: its footprint, source accounts, the instance and code
+/// of the contract it invokes or creates, and the network's config settings, which a local
+/// simulation needs.
+///
+/// The storage a contract reads or writes other than its instance is only known from the
+/// footprint, which a transaction that failed to simulate doesn't have yet, see
+/// [`misses_storage`].
+pub fn keys(tx: &Transaction) -> Vec<LedgerKey> {
+    let mut keys = Vec::new();
+    let mut account = |account: xdr::AccountId| {
+        keys.push(LedgerKey::Account(xdr::LedgerKeyAccount {
+            account_id: account,
+        }));
+    };
+    account(tx.source_account.clone().account_id());
+    for op in tx.operations.iter() {
+        if let Some(source) = &op.source_account {
+            account(source.clone().account_id());
+        }
+    }
+    if let TransactionExt::V1(data) = &tx.ext {
+        keys.extend(data.resources.footprint.read_only.iter().cloned());
+        keys.extend(data.resources.footprint.read_write.iter().cloned());
+    }
+    for op in tx.operations.iter() {
+        let OperationBody::InvokeHostFunction(op) = &op.body else {
+            continue;
+        };
+        match &op.host_function {
+            HostFunction::InvokeContract(args) => {
+                keys.push(contract_instance(&args.contract_address));
+            }
+            HostFunction::CreateContract(xdr::CreateContractArgs {
+                executable: ContractExecutable::Wasm(hash),
+                ..
+            })
+            | HostFunction::CreateContractV2(xdr::CreateContractArgsV2 {
+                executable: ContractExecutable::Wasm(hash),
+                ..
+            }) => keys.push(LedgerKey::ContractCode(LedgerKeyContractCode {
+                hash: hash.clone(),
+            })),
+            _ => {}
+        }
+    }
+    keys.extend(
+        ConfigSettingId::variants()
+            .into_iter()
+            .map(|config_setting_id| {
+                LedgerKey::ConfigSetting(LedgerKeyConfigSetting { config_setting_id })
+            }),
+    );
+    let mut seen = HashSet::new();
+    keys.retain(|key| seen.insert(key.clone()));
+    keys
+}
+
+/// Whether `tx` invokes a host function but has no footprint, so that [`keys`] can't include the
+/// contract storage it uses.
+fn misses_storage(tx: &Transaction) -> bool {
+    let invokes = tx
+        .operations
+        .iter()
+        .any(|op| matches!(op.body, OperationBody::InvokeHostFunction(_)));
+    let has_footprint = match &tx.ext {
+        TransactionExt::V1(data) => {
+            !data.resources.footprint.read_only.is_empty()
+                || !data.resources.footprint.read_write.is_empty()
+        }
+        TransactionExt::V0 => false,
+    };
+    invokes && !has_footprint
+}
+
+fn contract_instance(contract: &ScAddress) -> LedgerKey {
+    LedgerKey::ContractData(LedgerKeyContractData {
+        contract: contract.clone(),
+        key: ScVal::LedgerKeyContractInstance,
+        durability: ContractDataDurability::Persistent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xdr::{
+        Hash, InvokeContractArgs, InvokeHostFunctionOp, LedgerFootprint, Memo, MuxedAccount,
+        Operation, Preconditions, ScSymbol, SequenceNumber, SorobanResources,
+        SorobanTransactionData, Uint256, VecM,
+    };
+
+    use super::*;
+
+    fn invocation(contract: &ScAddress, ext: TransactionExt) -> Transaction {
+        Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                    host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                        contract_address: contract.clone(),
+                        function_name: ScSymbol("run".try_into().unwrap()),
+                        args: VecM::default(),
+                    }),
+                    auth: VecM::default(),
+                }),
+            }]
+            .try_into()
+            .unwrap(),
+            ext,
+        }
+    }
+
+    #[test]
+    fn keys_of_invocation() {
+        let contract = ScAddress::Contract(Hash([1; 32]));
+        let data = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: contract.clone(),
+            key: ScVal::U32(1),
+            durability: ContractDataDurability::Persistent,
+        });
+        let tx = invocation(
+            &contract,
+            TransactionExt::V1(SorobanTransactionData {
+                ext: xdr::ExtensionPoint::V0,
+                resources: SorobanResources {
+                    footprint: LedgerFootprint {
+                        read_only: vec![contract_instance(&contract)].try_into().unwrap(),
+                        read_write: vec![data.clone()].try_into().unwrap(),
+                    },
+                    instructions: 0,
+                    read_bytes: 0,
+                    write_bytes: 0,
+                },
+                resource_fee: 0,
+            }),
+        );
+        assert!(!misses_storage(&tx));
+        let keys = keys(&tx);
+        assert_eq!(
+            keys[..3],
+            [
+                LedgerKey::Account(xdr::LedgerKeyAccount {
+                    account_id: tx.source_account.clone().account_id(),
+                }),
+                contract_instance(&contract),
+                data,
+            ]
+        );
+        // The instance in the footprint is not repeated
+        assert_eq!(keys.len(), 3 + ConfigSettingId::variants().len());
+    }
+
+    #[test]
+    fn invocation_without_footprint_misses_storage() {
+        let contract = ScAddress::Contract(Hash([1; 32]));
+        let tx = invocation(&contract, TransactionExt::V0);
+        assert!(misses_storage(&tx));
+        assert_eq!(
+            keys(&tx)[1..2],
+            [contract_instance(&contract)],
+            "the instance is still included"
+        );
+    }
+}
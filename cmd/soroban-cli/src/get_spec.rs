@@ -6,7 +6,7 @@ use soroban_spec::read::FromWasmError;
 pub use soroban_spec_tools::contract as contract_spec;
 
 use crate::commands::global;
-use crate::config::{self, data, locator, network};
+use crate::config::{self, data, locator, network, sc_address, UnresolvedScAddress};
 use crate::rpc;
 use crate::utils::rpc::get_remote_wasm_from_hash;
 
@@ -28,6 +28,32 @@ pub enum Error {
     Config(#[from] config::Error),
     #[error(transparent)]
     ContractSpec(#[from] contract_spec::Error),
+    #[error(transparent)]
+    ScAddress(#[from] sc_address::Error),
+}
+
+/// Like [`get_remote_contract_spec`], for a contract given by its address or alias, which is
+/// resolved with `locator` and the network's passphrase.
+///
+/// # Errors
+/// If the address or alias is an account's rather than a contract's.
+pub async fn get_remote_contract_spec_for_address(
+    address: &UnresolvedScAddress,
+    locator: &locator::Args,
+    network: &network::Args,
+    global_args: Option<&global::Args>,
+    config: Option<&config::Args>,
+) -> Result<Vec<ScSpecEntry>, Error> {
+    let network_passphrase = config
+        .map_or_else(
+            || network.get(locator).map_err(Error::from),
+            |c| c.get_network().map_err(Error::from),
+        )?
+        .network_passphrase;
+    let contract_id = address
+        .clone()
+        .resolve_contract(locator, &network_passphrase)?;
+    get_remote_contract_spec(&contract_id.0, locator, network, global_args, config).await
 }
 
 ///
@@ -39,14 +65,17 @@ pub async fn get_remote_contract_spec(
     global_args: Option<&global::Args>,
     config: Option<&config::Args>,
 ) -> Result<Vec<ScSpecEntry>, Error> {
+    let options = config.map_or_else(|| network.rpc_options(), |c| c.network.rpc_options());
     let network = config.map_or_else(
         || network.get(locator).map_err(Error::from),
         |c| c.get_network().map_err(Error::from),
     )?;
     tracing::trace!(?network);
-    let client = network.rpc_client()?;
+    let client = network.checked_rpc_client(options).await?;
     // Get contract data
-    let r = client.get_contract_data(contract_id).await?;
+    let r = network
+        .with_rpc_timeout(client.get_contract_data(contract_id))
+        .await??;
     tracing::trace!("{r:?}");
 
     let ContractDataEntry {
@@ -64,10 +93,22 @@ pub async fn get_remote_contract_spec(
             if let Ok(entries) = data::read_spec(&hash_str) {
                 entries
             } else {
-                let raw_wasm = get_remote_wasm_from_hash(&client, &hash).await?;
+                let use_cache = global_args.map_or(true, |a| !a.no_cache);
+                let cached_wasm = use_cache.then(|| data::read_wasm(&hash_str).ok()).flatten();
+                let raw_wasm = if let Some(raw_wasm) = cached_wasm {
+                    raw_wasm
+                } else {
+                    let raw_wasm = network
+                        .with_rpc_timeout(get_remote_wasm_from_hash(&client, &hash))
+                        .await??;
+                    if use_cache {
+                        data::write_wasm(&hash_str, &raw_wasm)?;
+                    }
+                    raw_wasm
+                };
                 let res = contract_spec::Spec::new(&raw_wasm)?;
                 let res = res.spec;
-                if global_args.map_or(true, |a| !a.no_cache) {
+                if use_cache {
                     data::write_spec(&hash_str, &res)?;
                 }
                 res
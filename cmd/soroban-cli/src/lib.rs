@@ -14,12 +14,15 @@ pub use cli::main;
 pub mod assembled;
 pub mod commands;
 pub mod config;
+pub mod failure_snapshot;
 pub mod fee;
 pub mod get_spec;
 pub mod key;
 pub mod log;
+pub mod output;
 pub mod print;
 pub mod signer;
+pub mod spec_help;
 pub mod toid;
 pub mod tx;
 pub mod upgrade_check;
@@ -4,6 +4,7 @@ pub mod auth;
 pub mod cost;
 pub mod event;
 pub mod footprint;
+pub mod rpc;
 
 pub use auth::*;
 pub use cost::*;
@@ -88,3 +88,34 @@ pub fn contract(events: &[xdr::DiagnosticEvent], print: &Print) {
         }
     }
 }
+
+/// Print the contract errors and log messages carried by the diagnostic events of a failed
+/// transaction. The full list of events is also logged at `DEBUG` level.
+pub fn failure(events: &[xdr::DiagnosticEvent], print: &Print) -> Result<(), serde_json::Error> {
+    all(events);
+    let error_symbol = xdr::ScVal::Symbol(str_to_sc_symbol("error"));
+    let log_symbol = xdr::ScVal::Symbol(str_to_sc_symbol("log"));
+    for event in events {
+        let xdr::ContractEventBody::V0(xdr::ContractEventV0 { topics, data }) = &event.event.body;
+        let source = event
+            .event
+            .contract_id
+            .as_ref()
+            .map_or_else(|| "host".to_string(), |hash| Contract(hash.0).to_string());
+        let data = serde_json::to_string(data)?;
+        match topics.as_slice() {
+            [topic, xdr::ScVal::Error(error), ..] if topic == &error_symbol => {
+                let error = match error {
+                    xdr::ScError::Contract(code) => format!("contract error #{code}"),
+                    error => format!("{error:?}"),
+                };
+                print.errorln(format!("{source} - Error: {error}: {data}"));
+            }
+            [topic] if topic == &log_symbol => {
+                print.logln(format!("{source} - Log: {data}"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use soroban_rpc::{GetTransactionResponse, SimulateTransactionResponse};
+use tracing::debug;
+
+use crate::xdr::{Limits, TransactionEnvelope, WriteXdr};
+
+/// Filter directive turning on the logs of this module, set by `--log-rpc-xdr`.
+pub const DIRECTIVE: &str = "soroban_cli::log::rpc=debug";
+
+/// Log the RPC server a client is created for, with the values of its headers redacted as they
+/// are often API keys.
+pub fn client(rpc_url: &str, rpc_headers: &[(String, String)]) {
+    let headers = rpc_headers
+        .iter()
+        .map(|(name, _)| format!("{name}: <redacted>"))
+        .collect::<Vec<_>>();
+    debug!("RPC server {rpc_url}, headers {headers:?}");
+}
+
+/// Log the envelope sent with the `method` request, e.g. `simulateTransaction`.
+pub fn request(method: &str, tx_env: &TransactionEnvelope) {
+    debug!("{method} request: {}", base64(Some(tx_env)));
+}
+
+pub fn simulate_response(response: &SimulateTransactionResponse) {
+    debug!(
+        "simulateTransaction response: {}",
+        serde_json::to_string(response).unwrap_or_default()
+    );
+}
+
+/// Log the status and base64 XDR of a `getTransaction` response, including the one polled for
+/// after `sendTransaction`.
+pub fn transaction_response(response: &GetTransactionResponse) {
+    debug!(
+        "getTransaction response: status {}, envelope {}, result {}, result meta {}",
+        response.status,
+        base64(response.envelope.as_ref()),
+        base64(response.result.as_ref()),
+        base64(response.result_meta.as_ref()),
+    );
+}
+
+fn base64(xdr: Option<&impl WriteXdr>) -> String {
+    match xdr.map(|xdr| xdr.to_xdr_base64(Limits::none())) {
+        Some(Ok(base64)) => base64,
+        Some(Err(e)) => format!("<{e}>"),
+        None => "-".to_string(),
+    }
+}
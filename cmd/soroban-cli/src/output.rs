@@ -0,0 +1,126 @@
+use serde_json::Value;
+use soroban_spec_tools::Spec;
+
+use crate::xdr::{self, Limits, ScSpecTypeDef, ScVal, WriteXdr};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("cannot print value {val:?}: {error}")]
+    CannotPrint {
+        val: ScVal,
+        error: soroban_spec_tools::Error,
+    },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Xdr(#[from] xdr::Error),
+}
+
+/// Format of the `ScVal` results printed to stdout.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, clap::ValueEnum)]
+pub enum Output {
+    /// JSON of the value as its type in the contract spec
+    #[default]
+    Json,
+    /// Pretty printed JSON of the value as its type in the contract spec
+    JsonFormatted,
+    /// Base64 XDR of the `ScVal`
+    Xdr,
+}
+
+/// Convert `val` to JSON as `type_` of `spec`, or as the type in `spec` it has the shape of
+/// when there is no `type_`, falling back to the untyped JSON of the `ScVal`.
+pub fn to_json(val: &ScVal, spec: &Spec, type_: Option<&ScSpecTypeDef>) -> Result<Value, Error> {
+    match type_ {
+        Some(type_) => spec.xdr_to_json(val, type_),
+        None => spec.xdr_to_json_inferred(val),
+    }
+    .map_err(|error| Error::CannotPrint {
+        val: val.clone(),
+        error,
+    })
+}
+
+/// Render `val` in the `output` format, typing its JSON with [`to_json`].
+pub fn render(
+    val: &ScVal,
+    spec: &Spec,
+    type_: Option<&ScSpecTypeDef>,
+    output: Output,
+) -> Result<String, Error> {
+    Ok(match output {
+        Output::Json => to_json(val, spec, type_)?.to_string(),
+        Output::JsonFormatted => serde_json::to_string_pretty(&to_json(val, spec, type_)?)?,
+        Output::Xdr => val.to_xdr_base64(Limits::none())?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xdr::{ScMap, ScSymbol};
+
+    use super::*;
+
+    fn map() -> ScVal {
+        ScVal::Map(Some(
+            ScMap::sorted_from(vec![
+                (
+                    ScVal::Symbol(ScSymbol("a".try_into().unwrap())),
+                    ScVal::U32(1),
+                ),
+                (
+                    ScVal::Symbol(ScSymbol("b".try_into().unwrap())),
+                    ScVal::U32(2),
+                ),
+            ])
+            .unwrap(),
+        ))
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            render(
+                &ScVal::U32(42),
+                &Spec(None),
+                Some(&ScSpecTypeDef::U32),
+                Output::Json
+            )
+            .unwrap(),
+            "42"
+        );
+        assert_eq!(
+            render(&map(), &Spec(None), None, Output::Json).unwrap(),
+            r#"{"a":1,"b":2}"#
+        );
+    }
+
+    #[test]
+    fn json_formatted() {
+        assert_eq!(
+            render(&map(), &Spec(None), None, Output::JsonFormatted).unwrap(),
+            "{\n  \"a\": 1,\n  \"b\": 2\n}"
+        );
+    }
+
+    #[test]
+    fn xdr() {
+        assert_eq!(
+            render(&ScVal::U32(42), &Spec(None), None, Output::Xdr).unwrap(),
+            ScVal::U32(42).to_xdr_base64(Limits::none()).unwrap()
+        );
+    }
+
+    #[test]
+    fn mismatched_type() {
+        assert!(matches!(
+            render(
+                &ScVal::U32(42),
+                &Spec(None),
+                Some(&ScSpecTypeDef::String),
+                Output::Json
+            ),
+            Err(Error::CannotPrint { .. })
+        ));
+    }
+}
@@ -1,4 +1,12 @@
-use std::{env, fmt::Display};
+use std::{
+    env,
+    fmt::Display,
+    io::{self, BufRead, IsTerminal, Write},
+    sync::OnceLock,
+};
+
+use serde_json::json;
+use url::Url;
 
 use crate::xdr::{Error as XdrError, Transaction};
 
@@ -8,29 +16,135 @@ use crate::{
 
 const TERMS: &[&str] = &["Apple_Terminal", "vscode"];
 
+/// Format of the messages written to stderr.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human readable lines prefixed with an emoji
+    #[default]
+    Text,
+    /// One JSON object per line, e.g. `{"level":"info","msg":"..."}`
+    Json,
+}
+
+static LOG_FORMAT: OnceLock<LogFormat> = OnceLock::new();
+static NO_COLOR: OnceLock<bool> = OnceLock::new();
+
+/// Set the format used by every `Print` created with [`Print::new`]. Only the
+/// first call has an effect.
+pub fn set_log_format(format: LogFormat) {
+    let _ = LOG_FORMAT.set(format);
+}
+
+/// Disable emoji for every `Print` created with [`Print::new`]. Only the first
+/// call has an effect.
+pub fn set_no_color(no_color: bool) {
+    let _ = NO_COLOR.set(no_color);
+}
+
+// Emoji are only used when writing to a terminal, and never when `NO_COLOR` is
+// set, see https://no-color.org.
+fn plain_by_default() -> bool {
+    NO_COLOR.get().copied().unwrap_or_default()
+        || env::var_os("NO_COLOR").is_some_and(|no_color| !no_color.is_empty())
+        || !io::stderr().is_terminal()
+}
+
 #[derive(Clone)]
 pub struct Print {
     pub quiet: bool,
+    pub format: LogFormat,
+    /// Prefix messages with their level, e.g. `info:`, instead of an emoji.
+    pub plain: bool,
+    /// Explorer base URL overriding the built-in one for the network, see
+    /// [`crate::utils::explorer_url_for_transaction`].
+    pub explorer_url: Option<Url>,
 }
 
 impl Print {
     pub fn new(quiet: bool) -> Print {
-        Print { quiet }
+        Print {
+            quiet,
+            format: LOG_FORMAT.get().copied().unwrap_or_default(),
+            plain: plain_by_default(),
+            explorer_url: None,
+        }
+    }
+
+    pub fn json(quiet: bool) -> Print {
+        Print {
+            format: LogFormat::Json,
+            ..Print::new(quiet)
+        }
+    }
+
+    #[must_use]
+    pub fn with_explorer_url(self, explorer_url: Option<Url>) -> Print {
+        Print {
+            explorer_url,
+            ..self
+        }
     }
 
     pub fn print<T: Display + Sized>(&self, message: T) {
-        if !self.quiet {
+        if self.format == LogFormat::Json {
+            self.record("info", message);
+        } else if !self.quiet {
             eprint!("{message}");
         }
     }
 
     pub fn println<T: Display + Sized>(&self, message: T) {
-        if !self.quiet {
+        if self.format == LogFormat::Json {
+            self.record("info", message);
+        } else if !self.quiet {
             eprintln!("{message}");
         }
     }
 
+    // Write a single line JSON record, skipping blank messages that are only
+    // used for spacing in the text format.
+    fn record<T: Display + Sized>(&self, level: &str, message: T) {
+        let msg = message.to_string();
+        if !self.quiet && !msg.trim().is_empty() {
+            eprintln!("{}", json!({ "level": level, "msg": msg.trim() }));
+        }
+    }
+
+    /// Ask `prompt` on the terminal, appending `[y/N]`, and read the answer from stdin. Only `y`
+    /// or `yes`, in any case, is a yes. Returns `false` without asking when stdin is not a
+    /// terminal, so that non-interactive runs don't hang waiting for an answer.
+    pub fn confirm(&self, prompt: &str) -> io::Result<bool> {
+        self.confirm_or(prompt, false)
+    }
+
+    /// Like [`Print::confirm`], but returns `unattended` without asking when stdin is not a
+    /// terminal, for prompts that only guard interactive runs.
+    pub fn confirm_or(&self, prompt: &str, unattended: bool) -> io::Result<bool> {
+        if !io::stdin().is_terminal() {
+            return Ok(unattended);
+        }
+        self.confirm_from(prompt, &mut io::stdin().lock())
+    }
+
+    // When quiet the prompt is still written, as the answer is still read, but without its
+    // emoji or level.
+    fn confirm_from(&self, prompt: &str, input: &mut impl BufRead) -> io::Result<bool> {
+        let prompt = format!("{prompt} [y/N] ");
+        if self.quiet {
+            eprint!("{prompt}");
+        } else {
+            self.warn(prompt);
+        }
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        input.read_line(&mut answer)?;
+        Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+    }
+
     pub fn clear_line(&self) {
+        if self.quiet || self.plain || self.format == LogFormat::Json {
+            return;
+        }
         if cfg!(windows) {
             eprint!("\r");
         } else {
@@ -62,13 +176,29 @@ impl Print {
     ) -> Result<(), XdrError> {
         let tx_hash = transaction_hash(tx, &network.network_passphrase)?;
         let hash = hex::encode(tx_hash);
+        let url = show_link
+            .then(|| explorer_url_for_transaction(network, self.explorer_url.as_ref(), &hash))
+            .flatten();
+
+        if self.format == LogFormat::Json {
+            if !self.quiet {
+                eprintln!(
+                    "{}",
+                    json!({
+                        "level": "info",
+                        "msg": format!("Transaction hash is {hash}"),
+                        "hash": hash,
+                        "explorer_url": url,
+                    })
+                );
+            }
+            return Ok(());
+        }
 
         self.infoln(format!("Transaction hash is {hash}").as_str());
 
-        if show_link {
-            if let Some(url) = explorer_url_for_transaction(network, &hash) {
-                self.linkln(url);
-            }
+        if let Some(url) = url {
+            self.linkln(url);
         }
 
         Ok(())
@@ -76,36 +206,81 @@ impl Print {
 }
 
 macro_rules! create_print_functions {
-    ($name:ident, $nameln:ident, $icon:expr) => {
+    ($name:ident, $nameln:ident, $icon:expr, $level:expr) => {
         impl Print {
+            // In plain mode the line is always terminated, as it can't be cleared
+            // with `clear_line`.
             #[allow(dead_code)]
             pub fn $name<T: Display + Sized>(&self, message: T) {
-                if !self.quiet {
-                    eprint!("{} {}", self.compute_emoji($icon), message);
+                if self.format == LogFormat::Json {
+                    self.record($level, message);
+                } else if !self.quiet {
+                    if self.plain {
+                        eprintln!("{}: {}", $level, message);
+                    } else {
+                        eprint!("{} {}", self.compute_emoji($icon), message);
+                    }
                 }
             }
 
             #[allow(dead_code)]
             pub fn $nameln<T: Display + Sized>(&self, message: T) {
-                if !self.quiet {
-                    eprintln!("{} {}", self.compute_emoji($icon), message);
+                if self.format == LogFormat::Json {
+                    self.record($level, message);
+                } else if !self.quiet {
+                    if self.plain {
+                        eprintln!("{}: {}", $level, message);
+                    } else {
+                        eprintln!("{} {}", self.compute_emoji($icon), message);
+                    }
                 }
             }
         }
     };
 }
 
-create_print_functions!(bucket, bucketln, "🪣");
-create_print_functions!(check, checkln, "✅");
-create_print_functions!(error, errorln, "❌");
-create_print_functions!(globe, globeln, "🌎");
-create_print_functions!(info, infoln, "ℹ️");
-create_print_functions!(link, linkln, "🔗");
-create_print_functions!(plus, plusln, "➕");
-create_print_functions!(save, saveln, "💾");
-create_print_functions!(search, searchln, "🔎");
-create_print_functions!(warn, warnln, "⚠️");
-create_print_functions!(exclaim, exclaimln, "❗️");
-create_print_functions!(arrow, arrowln, "➡️");
-create_print_functions!(log, logln, "📔");
-create_print_functions!(event, eventln, "📅");
+create_print_functions!(bucket, bucketln, "🪣", "info");
+create_print_functions!(check, checkln, "✅", "info");
+create_print_functions!(error, errorln, "❌", "error");
+create_print_functions!(globe, globeln, "🌎", "info");
+create_print_functions!(info, infoln, "ℹ️", "info");
+create_print_functions!(link, linkln, "🔗", "info");
+create_print_functions!(plus, plusln, "➕", "info");
+create_print_functions!(save, saveln, "💾", "info");
+create_print_functions!(search, searchln, "🔎", "info");
+create_print_functions!(warn, warnln, "⚠️", "warn");
+create_print_functions!(exclaim, exclaimln, "❗️", "warn");
+create_print_functions!(arrow, arrowln, "➡️", "info");
+create_print_functions!(log, logln, "📔", "info");
+create_print_functions!(event, eventln, "📅", "info");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirm(quiet: bool, input: &str) -> bool {
+        Print::new(quiet)
+            .confirm_from("Continue?", &mut input.as_bytes())
+            .unwrap()
+    }
+
+    #[test]
+    fn confirm_yes() {
+        for input in ["y\n", "Y\n", "yes\n", "YES\n", " Yes \n", "y"] {
+            assert!(confirm(false, input), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn confirm_defaults_to_no() {
+        for input in ["\n", "", "n\n", "no\n", "yep\n", "x\n"] {
+            assert!(!confirm(false, input), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn confirm_when_quiet() {
+        assert!(confirm(true, "y\n"));
+        assert!(!confirm(true, "\n"));
+    }
+}
@@ -1,16 +1,23 @@
-use ed25519_dalek::ed25519::signature::Signer as _;
+use std::io::{self, IsTerminal};
+
+use ed25519_dalek::ed25519::signature::{Signer as _, Verifier as _};
 use keyring::StellarEntry;
 use sha2::{Digest, Sha256};
 
 use crate::xdr::{
-    self, AccountId, DecoratedSignature, Hash, HashIdPreimage, HashIdPreimageSorobanAuthorization,
-    InvokeHostFunctionOp, Limits, Operation, OperationBody, PublicKey, ScAddress, ScMap, ScSymbol,
-    ScVal, Signature, SignatureHint, SorobanAddressCredentials, SorobanAuthorizationEntry,
-    SorobanAuthorizedFunction, SorobanCredentials, Transaction, TransactionEnvelope,
+    self, AccountId, DecoratedSignature, FeeBumpTransactionEnvelope, Hash, HashIdPreimage,
+    HashIdPreimageSorobanAuthorization, InvokeHostFunctionOp, Limits, Operation, OperationBody,
+    PublicKey, ScAddress, ScMap, ScSymbol, ScVal, Signature, SignatureHint,
+    SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
+    SorobanCredentials, Transaction, TransactionEnvelope, TransactionV0Envelope,
     TransactionV1Envelope, Uint256, VecM, WriteXdr,
 };
 
-use crate::{config::network::Network, print::Print, utils::transaction_hash};
+use crate::{
+    config::network::Network,
+    print::Print,
+    utils::{fee_bump_transaction_hash, transaction_hash},
+};
 
 pub mod keyring;
 pub mod secure_store;
@@ -25,11 +32,13 @@ pub enum Error {
     MissingSignerForAddress { address: String },
     #[error(transparent)]
     TryFromSlice(#[from] std::array::TryFromSliceError),
-    #[error("User cancelled signing, perhaps need to add -y")]
+    #[error("User cancelled signing, remove --check to sign without confirmation")]
     UserCancelledSigning,
+    #[error("--check asks for confirmation on the terminal and never signs without an answer, but stdin or stderr is not a terminal; pass the envelope with --input instead of stdin, or remove --check to sign without confirmation")]
+    ConfirmationRequiresTerminal,
     #[error(transparent)]
     Xdr(#[from] xdr::Error),
-    #[error("Only Transaction envelope V1 type is supported")]
+    #[error("Only Transaction envelope V1 and fee bump types are supported")]
     UnsupportedTransactionEnvelopeType,
     #[error(transparent)]
     Url(#[from] url::ParseError),
@@ -204,6 +213,20 @@ fn sign_soroban_authorization_entry(
     Ok(auth)
 }
 
+/// Check whether `sig` is a valid signature of `tx_hash` made by the ed25519 `public_key`.
+pub fn is_signed_by(sig: &DecoratedSignature, public_key: &[u8; 32], tx_hash: &[u8; 32]) -> bool {
+    if sig.hint.0 != public_key[28..] {
+        return false;
+    }
+    let Ok(verifying_key) = ed25519_dalek::VerifyingKey::from_bytes(public_key) else {
+        return false;
+    };
+    let Ok(signature) = ed25519_dalek::Signature::from_slice(sig.signature.as_slice()) else {
+        return false;
+    };
+    verifying_key.verify(tx_hash, &signature).is_ok()
+}
+
 pub struct Signer {
     pub kind: SignerKind,
     pub print: Print,
@@ -234,38 +257,172 @@ impl Signer {
         tx_env: &TransactionEnvelope,
         network: &Network,
     ) -> Result<TransactionEnvelope, Error> {
-        match &tx_env {
-            TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) => {
-                let tx_hash = transaction_hash(tx, &network.network_passphrase)?;
-                self.print
-                    .infoln(format!("Signing transaction: {}", hex::encode(tx_hash),));
-                let decorated_signature = match &self.kind {
-                    SignerKind::Local(key) => key.sign_tx_hash(tx_hash)?,
-                    SignerKind::Lab => Lab::sign_tx_env(tx_env, network, &self.print)?,
-                    SignerKind::SecureStore(entry) => entry.sign_tx_hash(tx_hash)?,
-                };
-                let mut sigs = signatures.clone().into_vec();
-                sigs.push(decorated_signature);
-                Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
-                    tx: tx.clone(),
-                    signatures: sigs.try_into()?,
-                }))
+        let decorated_signature = self.sign_tx_env_signature(tx_env, network)?;
+        append_signatures(tx_env, [decorated_signature])
+    }
+
+    /// Sign the envelope, returning the signature instead of appending it to the envelope.
+    pub fn sign_tx_env_signature(
+        &self,
+        tx_env: &TransactionEnvelope,
+        network: &Network,
+    ) -> Result<DecoratedSignature, Error> {
+        let (tx_hash, _) = signature_payload_hash(tx_env, network)?;
+        self.print
+            .infoln(format!("Signing transaction: {}", hex::encode(tx_hash)));
+        match &self.kind {
+            SignerKind::Local(key) => {
+                if key.prompt {
+                    key.confirm(tx_env, tx_hash, &self.print)?;
+                }
+                key.sign_tx_hash(tx_hash)
             }
-            _ => Err(Error::UnsupportedTransactionEnvelopeType),
+            SignerKind::Lab => Lab::sign_tx_env(tx_env, network, &self.print),
+            SignerKind::SecureStore(entry) => entry.sign_tx_hash(tx_hash),
         }
     }
+
+    /// The public key of the signer, if it can be known without signing (not the case for Lab).
+    pub fn public_key(&self) -> Result<Option<stellar_strkey::ed25519::PublicKey>, Error> {
+        Ok(match &self.kind {
+            SignerKind::Local(LocalKey { key, .. }) => Some(stellar_strkey::ed25519::PublicKey(
+                key.verifying_key().to_bytes(),
+            )),
+            SignerKind::SecureStore(entry) => {
+                Some(StellarEntry::new(&entry.name)?.get_public_key(entry.hd_path)?)
+            }
+            SignerKind::Lab => None,
+        })
+    }
+
+    /// Whether the envelope already carries a valid signature from this signer.
+    pub fn has_signed(
+        &self,
+        tx_env: &TransactionEnvelope,
+        network: &Network,
+    ) -> Result<bool, Error> {
+        let (tx_hash, signatures) = signature_payload_hash(tx_env, network)?;
+        let Some(stellar_strkey::ed25519::PublicKey(public_key)) = self.public_key()? else {
+            return Ok(false);
+        };
+        Ok(signatures
+            .iter()
+            .any(|sig| is_signed_by(sig, &public_key, &tx_hash)))
+    }
+}
+
+/// Copy of the envelope with `new_signatures` appended to its signatures.
+pub fn append_signatures(
+    tx_env: &TransactionEnvelope,
+    new_signatures: impl IntoIterator<Item = DecoratedSignature>,
+) -> Result<TransactionEnvelope, Error> {
+    let mut tx_env = tx_env.clone();
+    match &mut tx_env {
+        TransactionEnvelope::TxV0(TransactionV0Envelope { signatures, .. })
+        | TransactionEnvelope::Tx(TransactionV1Envelope { signatures, .. })
+        | TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { signatures, .. }) => {
+            let mut sigs = signatures.to_vec();
+            sigs.extend(new_signatures);
+            *signatures = sigs.try_into()?;
+        }
+    }
+    Ok(tx_env)
+}
+
+/// The hash that signers of the envelope sign, and the signatures already on it.
+pub fn signature_payload_hash<'a>(
+    tx_env: &'a TransactionEnvelope,
+    network: &Network,
+) -> Result<([u8; 32], &'a VecM<DecoratedSignature, 20>), Error> {
+    match tx_env {
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, signatures }) => Ok((
+            transaction_hash(tx, &network.network_passphrase)?,
+            signatures,
+        )),
+        TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, signatures }) => Ok((
+            fee_bump_transaction_hash(tx, &network.network_passphrase)?,
+            signatures,
+        )),
+        TransactionEnvelope::TxV0(_) => Err(Error::UnsupportedTransactionEnvelopeType),
+    }
 }
 
 pub struct LocalKey {
     pub key: ed25519_dalek::SigningKey,
+    /// Ask on the terminal for confirmation before signing a transaction, set with `--check`.
+    pub prompt: bool,
+}
+
+/// Short description of the source account, fee, sequence number and operations of the
+/// transaction in `tx_env`, and of the fee bump wrapping it if any, one line each. Shown before
+/// signing with `--check` and sending with `tx send`.
+pub fn tx_summary(tx_env: &TransactionEnvelope) -> Vec<String> {
+    let mut lines = Vec::new();
+    let tx = match tx_env {
+        TransactionEnvelope::Tx(TransactionV1Envelope { tx, .. }) => tx,
+        TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx: fee_bump, .. }) => {
+            lines.push(format!(
+                "Fee bump of {} stroops paid by {}",
+                fee_bump.fee, fee_bump.fee_source
+            ));
+            let xdr::FeeBumpTransactionInnerTx::Tx(TransactionV1Envelope { tx, .. }) =
+                &fee_bump.inner_tx;
+            tx
+        }
+        TransactionEnvelope::TxV0(_) => return lines,
+    };
+    lines.push(format!(
+        "Source account {}, fee {} stroops, sequence number {}",
+        tx.source_account, tx.fee, tx.seq_num.0
+    ));
+    for (i, op) in tx.operations.iter().enumerate() {
+        let source = op
+            .source_account
+            .as_ref()
+            .map(|source| format!(" from {source}"))
+            .unwrap_or_default();
+        lines.push(format!("Operation {}: {}{source}", i + 1, op.body.name()));
+    }
+    lines
 }
 
 impl LocalKey {
+    /// Show a summary of the transaction and ask on the terminal whether to sign it, defaulting
+    /// to no. Fails instead of signing when there is no terminal to ask on.
+    fn confirm(
+        &self,
+        tx_env: &TransactionEnvelope,
+        tx_hash: [u8; 32],
+        print: &Print,
+    ) -> Result<(), Error> {
+        if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+            return Err(Error::ConfirmationRequiresTerminal);
+        }
+        for line in tx_summary(tx_env) {
+            print.infoln(line);
+        }
+        let public_key = stellar_strkey::ed25519::PublicKey(self.key.verifying_key().to_bytes());
+        if print.confirm(&format!(
+            "Sign transaction {} with {public_key}?",
+            hex::encode(tx_hash)
+        ))? {
+            Ok(())
+        } else {
+            Err(Error::UserCancelledSigning)
+        }
+    }
+
     pub fn sign_tx_hash(&self, tx_hash: [u8; 32]) -> Result<DecoratedSignature, Error> {
         let hint = SignatureHint(self.key.verifying_key().to_bytes()[28..].try_into()?);
         let signature = Signature(self.key.sign(&tx_hash).to_bytes().to_vec().try_into()?);
         Ok(DecoratedSignature { hint, signature })
     }
+
+    /// Raw Ed25519 signature over `payload`. The payload is signed as is, without hashing it or
+    /// prefixing the network id as is done for transactions.
+    pub fn sign_payload(&self, payload: &[u8]) -> [u8; 64] {
+        self.key.sign(payload).to_bytes()
+    }
 }
 
 pub struct Lab;
@@ -0,0 +1,175 @@
+//! Help of contract function arguments, rendered from the contract spec the same way as in
+//! `contract invoke -- <function> --help`.
+use std::fmt::Write;
+
+use soroban_spec_tools::Spec;
+
+use crate::xdr::{ScSpecEntry, ScSpecTypeDef};
+
+/// Help of one argument of a contract function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgHelp {
+    pub name: String,
+    pub type_: ScSpecTypeDef,
+    /// Value name shown after the flag, e.g. `Map<u32, bool>`
+    pub value_name: Option<String>,
+    /// Doc of the argument's type and an example value
+    pub doc: Option<&'static str>,
+    /// Whether the argument can be omitted, which is the case for `Option` types
+    pub optional: bool,
+}
+
+/// Help of the arguments of `function`, in the order of the function's inputs.
+///
+/// # Errors
+///
+/// If the function is not in the spec or the spec of an argument's type can't be found.
+pub fn function_args(
+    spec: &Spec,
+    function: &str,
+) -> Result<Vec<ArgHelp>, soroban_spec_tools::Error> {
+    spec.find_function(function)?
+        .inputs
+        .iter()
+        .map(|input| {
+            let name = input.name.to_utf8_string_lossy();
+            Ok(ArgHelp {
+                value_name: spec.arg_value_name(&input.type_, 0),
+                doc: spec.doc(&name, &input.type_)?,
+                optional: matches!(input.type_, ScSpecTypeDef::Option(_)),
+                type_: input.type_.clone(),
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Render the arguments of `function` in the spec `entries`, one flag per line followed by its
+/// indented doc, e.g.
+///
+/// ```text
+/// --arg <Map<u32, bool>>
+///     Example:
+///       --arg '{ "1": true }'
+/// ```
+///
+/// # Errors
+///
+/// If the function is not in the spec or the spec of an argument's type can't be found.
+pub fn render_args(
+    entries: Vec<ScSpecEntry>,
+    function: &str,
+) -> Result<String, soroban_spec_tools::Error> {
+    let spec = Spec::new(entries);
+    let mut out = String::new();
+    for arg in function_args(&spec, function)? {
+        let value_name = arg.value_name.as_deref().unwrap_or("VALUE");
+        let optional = if arg.optional { " (optional)" } else { "" };
+        let _ = writeln!(out, "--{} <{value_name}>{optional}", arg.name);
+        for line in arg.doc.unwrap_or_default().lines() {
+            let _ = writeln!(out, "    {line}");
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xdr::{
+        ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeTuple,
+        ScSpecTypeUdt, ScSpecTypeVec, ScSpecUdtStructFieldV0, ScSpecUdtStructV0,
+    };
+
+    use super::*;
+
+    fn input(name: &str, type_: ScSpecTypeDef) -> ScSpecFunctionInputV0 {
+        ScSpecFunctionInputV0 {
+            doc: "".try_into().unwrap(),
+            name: name.try_into().unwrap(),
+            type_,
+        }
+    }
+
+    fn fixture() -> Vec<ScSpecEntry> {
+        let map = ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+            key_type: Box::new(ScSpecTypeDef::U32),
+            value_type: Box::new(ScSpecTypeDef::Bool),
+        }));
+        let tuple = ScSpecTypeDef::Tuple(Box::new(ScSpecTypeTuple {
+            value_types: vec![ScSpecTypeDef::Symbol, ScSpecTypeDef::U32]
+                .try_into()
+                .unwrap(),
+        }));
+        let optional_vec = ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+            value_type: Box::new(ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+                element_type: Box::new(tuple.clone()),
+            }))),
+        }));
+        let udt = ScSpecTypeDef::Udt(ScSpecTypeUdt {
+            name: "Point".try_into().unwrap(),
+        });
+        vec![
+            ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                doc: "A point".try_into().unwrap(),
+                lib: "".try_into().unwrap(),
+                name: "Point".try_into().unwrap(),
+                fields: vec![
+                    ScSpecUdtStructFieldV0 {
+                        doc: "".try_into().unwrap(),
+                        name: "x".try_into().unwrap(),
+                        type_: ScSpecTypeDef::I32,
+                    },
+                    ScSpecUdtStructFieldV0 {
+                        doc: "".try_into().unwrap(),
+                        name: "y".try_into().unwrap(),
+                        type_: ScSpecTypeDef::I32,
+                    },
+                ]
+                .try_into()
+                .unwrap(),
+            }),
+            ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+                doc: "".try_into().unwrap(),
+                name: "hello".try_into().unwrap(),
+                inputs: vec![
+                    input("map", map),
+                    input("tuple", tuple),
+                    input("pairs", optional_vec),
+                    input("point", udt),
+                ]
+                .try_into()
+                .unwrap(),
+                outputs: vec![].try_into().unwrap(),
+            }),
+        ]
+    }
+
+    #[test]
+    fn nested_and_optional_types() {
+        let spec = Spec::new(fixture());
+        let args = function_args(&spec, "hello").unwrap();
+        let names = args
+            .iter()
+            .map(|arg| (arg.name.as_str(), arg.value_name.as_deref(), arg.optional))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                ("map", Some("Map<u32, bool>"), false),
+                ("tuple", Some("Tuple<Symbol, u32>"), false),
+                ("pairs", Some("Option<Array<Tuple<Symbol, u32>>>"), true),
+                ("point", Some("{ x: i32, y: i32 }"), false),
+            ]
+        );
+        assert!(args[3].doc.unwrap().starts_with("A point"));
+    }
+
+    #[test]
+    fn render() {
+        let rendered = render_args(fixture(), "hello").unwrap();
+        assert!(rendered.contains("--map <Map<u32, bool>>\n"));
+        assert!(rendered.contains("--pairs <Option<Array<Tuple<Symbol, u32>>>> (optional)\n"));
+        assert!(rendered.contains("--point <{ x: i32, y: i32 }>\n    A point\n"));
+        assert!(render_args(fixture(), "missing").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 pub mod amount;
 pub mod asset;
+pub mod memo;
 pub mod transaction;
 
 pub use amount::Amount;
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::{
     config::{address, locator},
-    xdr::{self, AlphaNum12, AlphaNum4, AssetCode},
+    xdr::{self, AlphaNum12, AlphaNum4, AssetCode, AssetCode12, AssetCode4},
 };
 
 #[derive(Clone, Debug)]
@@ -15,6 +15,10 @@ pub enum Asset {
 pub enum Error {
     #[error("cannot parse asset: {0}, expected format: 'native' or 'code:issuer'")]
     CannotParseAsset(String),
+    #[error("invalid asset issuer: {0}, expected a `G...` public key or an identity alias")]
+    InvalidIssuer(String),
+    #[error("invalid asset code: {0:?}, {1}")]
+    InvalidAssetCode(String, &'static str),
     #[error(transparent)]
     Xdr(#[from] xdr::Error),
     #[error(transparent)]
@@ -25,15 +29,45 @@ impl FromStr for Asset {
     type Err = Error;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        if value == "native" {
+        if value == "native" || value == "XLM" {
             return Ok(Asset::Native);
         }
         let mut iter = value.splitn(2, ':');
         let (Some(code), Some(issuer), None) = (iter.next(), iter.next(), iter.next()) else {
             return Err(Error::CannotParseAsset(value.to_string()));
         };
-        Ok(Asset::Asset(code.parse()?, issuer.parse()?))
+        // Anything shaped like a public key must be one; otherwise it would silently be looked
+        // up as an identity alias and fail with a confusing "not found" error.
+        if issuer.len() == 56
+            && issuer.starts_with('G')
+            && stellar_strkey::ed25519::PublicKey::from_string(issuer).is_err()
+        {
+            return Err(Error::InvalidIssuer(issuer.to_string()));
+        }
+        Ok(Asset::Asset(parse_asset_code(code)?, issuer.parse()?))
+    }
+}
+
+/// Parse an asset code of 1 to 4 alphanumeric characters, as the code of a `CreditAlphanum4`
+/// asset, or of 5 to 12, as the code of a `CreditAlphanum12` one.
+pub fn parse_asset_code(code: &str) -> Result<AssetCode, Error> {
+    let invalid = |reason| Err(Error::InvalidAssetCode(code.to_string(), reason));
+    if !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return invalid("it can only contain the letters a-z and A-Z and the digits 0-9");
     }
+    Ok(match code.len() {
+        1..=4 => {
+            let mut bytes = [0; 4];
+            bytes[..code.len()].copy_from_slice(code.as_bytes());
+            AssetCode::CreditAlphanum4(AssetCode4(bytes))
+        }
+        5..=12 => {
+            let mut bytes = [0; 12];
+            bytes[..code.len()].copy_from_slice(code.as_bytes());
+            AssetCode::CreditAlphanum12(AssetCode12(bytes))
+        }
+        _ => return invalid("it must be 1 to 12 characters long"),
+    })
 }
 
 impl Asset {
@@ -54,3 +88,63 @@ impl Asset {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER: &str = "GAKSH6AD2IPJQELTHIOWDAPYX74YELUOWJLI2L4RIPIPZH6YQIFNUSDC";
+
+    #[test]
+    fn native() {
+        assert!(matches!("native".parse(), Ok(Asset::Native)));
+        assert!(matches!("XLM".parse(), Ok(Asset::Native)));
+    }
+
+    #[test]
+    fn asset_code_length() {
+        let Ok(Asset::Asset(AssetCode::CreditAlphanum4(AssetCode4(code)), _)) =
+            format!("USD:{ISSUER}").parse()
+        else {
+            panic!("expected a CreditAlphanum4 asset");
+        };
+        assert_eq!(&code, b"USD\0");
+        let Ok(Asset::Asset(AssetCode::CreditAlphanum12(AssetCode12(code)), _)) =
+            format!("USDC2:{ISSUER}").parse()
+        else {
+            panic!("expected a CreditAlphanum12 asset");
+        };
+        assert_eq!(&code[..6], b"USDC2\0");
+    }
+
+    #[test]
+    fn invalid_asset_code() {
+        for code in ["", "ABCDEFGHIJKLM", "US-D", "US D"] {
+            assert!(
+                matches!(
+                    format!("{code}:{ISSUER}").parse::<Asset>(),
+                    Err(Error::InvalidAssetCode(c, _)) if c == code
+                ),
+                "{code:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_issuer() {
+        // A public key with a bad checksum
+        let issuer = "GAKSH6AD2IPJQELTHIOWDAPYX74YELUOWJLI2L4RIPIPZH6YQIFNUSDD";
+        assert!(matches!(
+            format!("USD:{issuer}").parse::<Asset>(),
+            Err(Error::InvalidIssuer(i)) if i == issuer
+        ));
+    }
+
+    #[test]
+    fn identity_issuer() {
+        assert!(matches!(
+            "USD:alice".parse(),
+            Ok(Asset::Asset(_, address::UnresolvedMuxedAccount::AliasOrSecret(alias))) if alias == "alice"
+        ));
+    }
+}
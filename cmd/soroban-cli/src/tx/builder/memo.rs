@@ -0,0 +1,170 @@
+use super::TxExt;
+use crate::xdr::{Hash, Memo, StringM, Transaction};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("memo text is {0} bytes, but can be at most 28 bytes")]
+    TextTooLong(usize),
+    #[error("memo hash must be 32 bytes encoded as 64 hex characters, got {0:?}")]
+    InvalidHash(String),
+}
+
+#[derive(Debug, clap::Args, Clone, Default)]
+#[group(skip)]
+pub struct Args {
+    /// Text memo to attach to the transaction, at most 28 bytes
+    #[arg(long, value_parser = parse_text, conflicts_with_all = ["memo_id", "memo_hash", "memo_return"])]
+    pub memo_text: Option<StringM<28>>,
+    /// Id memo to attach to the transaction, an unsigned 64-bit integer
+    #[arg(long, conflicts_with_all = ["memo_hash", "memo_return"])]
+    pub memo_id: Option<u64>,
+    /// Hash memo to attach to the transaction, 32 bytes as hex
+    #[arg(long, value_parser = parse_hash, conflicts_with = "memo_return")]
+    pub memo_hash: Option<Hash>,
+    /// Return memo to attach to the transaction, the 32 byte hex hash of the transaction being
+    /// refunded
+    #[arg(long, value_parser = parse_hash)]
+    pub memo_return: Option<Hash>,
+}
+
+impl Args {
+    /// The memo set by the flags, if any.
+    pub fn memo(&self) -> Option<Memo> {
+        if let Some(text) = &self.memo_text {
+            Some(Memo::Text(text.clone()))
+        } else if let Some(id) = self.memo_id {
+            Some(Memo::Id(id))
+        } else if let Some(hash) = &self.memo_hash {
+            Some(Memo::Hash(hash.clone()))
+        } else {
+            self.memo_return.clone().map(Memo::Return)
+        }
+    }
+
+    /// `tx` with the memo set by the flags, if any.
+    pub fn add_to(&self, tx: Transaction) -> Transaction {
+        match self.memo() {
+            Some(memo) => tx.add_memo(memo),
+            None => tx,
+        }
+    }
+}
+
+pub fn parse_text(text: &str) -> Result<StringM<28>, Error> {
+    text.try_into().map_err(|_| Error::TextTooLong(text.len()))
+}
+
+pub fn parse_hash(hash: &str) -> Result<Hash, Error> {
+    hex::decode(hash)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .map(Hash)
+        .ok_or_else(|| Error::InvalidHash(hash.to_string()))
+}
+
+/// Human readable form of `memo`, or `None` for `Memo::None`.
+pub fn display(memo: &Memo) -> Option<String> {
+    match memo {
+        Memo::None => None,
+        Memo::Text(text) => Some(format!("text {:?}", text.to_utf8_string_lossy())),
+        Memo::Id(id) => Some(format!("id {id}")),
+        Memo::Hash(Hash(hash)) => Some(format!("hash {}", hex::encode(hash))),
+        Memo::Return(Hash(hash)) => Some(format!("return {}", hex::encode(hash))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+
+    #[test]
+    fn text() {
+        let args = Args {
+            memo_text: Some(parse_text("hello").unwrap()),
+            ..Default::default()
+        };
+        let memo = args.memo().unwrap();
+        assert_eq!(memo, Memo::Text("hello".try_into().unwrap()));
+        assert_eq!(display(&memo).unwrap(), r#"text "hello""#);
+    }
+
+    #[test]
+    fn text_too_long() {
+        assert!(parse_text(&"a".repeat(28)).is_ok());
+        assert!(matches!(
+            parse_text(&"a".repeat(29)),
+            Err(Error::TextTooLong(29))
+        ));
+        // The limit is in bytes, not characters
+        assert!(matches!(
+            parse_text(&"é".repeat(15)),
+            Err(Error::TextTooLong(30))
+        ));
+    }
+
+    #[test]
+    fn id() {
+        let args = Args {
+            memo_id: Some(42),
+            ..Default::default()
+        };
+        let memo = args.memo().unwrap();
+        assert_eq!(memo, Memo::Id(42));
+        assert_eq!(display(&memo).unwrap(), "id 42");
+    }
+
+    #[test]
+    fn hash() {
+        let args = Args {
+            memo_hash: Some(parse_hash(HASH).unwrap()),
+            ..Default::default()
+        };
+        let memo = args.memo().unwrap();
+        assert_eq!(memo, Memo::Hash(Hash([1; 32])));
+        assert_eq!(display(&memo).unwrap(), format!("hash {HASH}"));
+    }
+
+    #[test]
+    fn return_hash() {
+        let args = Args {
+            memo_return: Some(parse_hash(HASH).unwrap()),
+            ..Default::default()
+        };
+        let memo = args.memo().unwrap();
+        assert_eq!(memo, Memo::Return(Hash([1; 32])));
+        assert_eq!(display(&memo).unwrap(), format!("return {HASH}"));
+    }
+
+    #[test]
+    fn invalid_hash() {
+        assert!(matches!(parse_hash("0101"), Err(Error::InvalidHash(_))));
+        assert!(matches!(parse_hash("not hex"), Err(Error::InvalidHash(_))));
+    }
+
+    #[test]
+    fn none() {
+        assert_eq!(Args::default().memo(), None);
+        assert_eq!(display(&Memo::None), None);
+    }
+
+    #[test]
+    fn add_to() {
+        let tx = Transaction::new_tx(
+            crate::xdr::MuxedAccount::Ed25519(crate::xdr::Uint256([0; 32])),
+            100,
+            crate::xdr::SequenceNumber(1),
+            crate::xdr::Operation {
+                source_account: None,
+                body: crate::xdr::OperationBody::Inflation,
+            },
+        );
+        assert_eq!(Args::default().add_to(tx.clone()).memo, Memo::None);
+        let args = Args {
+            memo_id: Some(42),
+            ..Default::default()
+        };
+        assert_eq!(args.add_to(tx).memo, Memo::Id(42));
+    }
+}
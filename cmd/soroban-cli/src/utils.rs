@@ -1,11 +1,12 @@
 use phf::phf_map;
 use sha2::{Digest, Sha256};
 use stellar_strkey::ed25519::PrivateKey;
+use url::Url;
 
 use crate::xdr::{
-    self, Asset, ContractIdPreimage, Hash, HashIdPreimage, HashIdPreimageContractId, Limits, ScMap,
-    ScMapEntry, ScVal, Transaction, TransactionSignaturePayload,
-    TransactionSignaturePayloadTaggedTransaction, WriteXdr,
+    self, Asset, ContractIdPreimage, FeeBumpTransaction, Hash, HashIdPreimage,
+    HashIdPreimageContractId, Limits, ScMap, ScMapEntry, ScVal, Transaction,
+    TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction, WriteXdr,
 };
 
 pub use soroban_spec_tools::contract as contract_spec;
@@ -33,23 +34,54 @@ pub fn transaction_hash(
     Ok(Sha256::digest(signature_payload.to_xdr(Limits::none())?).into())
 }
 
+/// # Errors
+///
+/// Might return an error
+pub fn fee_bump_transaction_hash(
+    fee_bump_tx: &FeeBumpTransaction,
+    network_passphrase: &str,
+) -> Result<[u8; 32], xdr::Error> {
+    let signature_payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(network_passphrase).into()),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::TxFeeBump(
+            fee_bump_tx.clone(),
+        ),
+    };
+    Ok(Sha256::digest(signature_payload.to_xdr(Limits::none())?).into())
+}
+
 static EXPLORERS: phf::Map<&'static str, &'static str> = phf_map! {
     "Test SDF Network ; September 2015" => "https://stellar.expert/explorer/testnet",
     "Public Global Stellar Network ; September 2015" => "https://stellar.expert/explorer/public",
 };
 
-pub fn explorer_url_for_transaction(network: &Network, tx_hash: &str) -> Option<String> {
-    EXPLORERS
-        .get(&network.network_passphrase)
-        .map(|base_url| format!("{base_url}/tx/{tx_hash}"))
+/// The explorer to link to: `explorer_url` when set, otherwise the known explorer for the
+/// network's passphrase.
+fn explorer_base_url(network: &Network, explorer_url: Option<&Url>) -> Option<String> {
+    explorer_url.map_or_else(
+        || {
+            EXPLORERS
+                .get(&network.network_passphrase)
+                .map(ToString::to_string)
+        },
+        |url| Some(url.as_str().trim_end_matches('/').to_string()),
+    )
+}
+
+pub fn explorer_url_for_transaction(
+    network: &Network,
+    explorer_url: Option<&Url>,
+    tx_hash: &str,
+) -> Option<String> {
+    explorer_base_url(network, explorer_url).map(|base_url| format!("{base_url}/tx/{tx_hash}"))
 }
 
 pub fn explorer_url_for_contract(
     network: &Network,
+    explorer_url: Option<&Url>,
     contract_id: &stellar_strkey::Contract,
 ) -> Option<String> {
-    EXPLORERS
-        .get(&network.network_passphrase)
+    explorer_base_url(network, explorer_url)
         .map(|base_url| format!("{base_url}/contract/{contract_id}"))
 }
 
@@ -185,6 +217,22 @@ pub mod http {
             .expect("Failed to build reqwest client")
     }
 
+    /// Creates and returns a configured `reqwest::Client` whose connections and reads time out
+    /// after `timeout`. A single client should be shared by related requests so that its
+    /// connections are reused.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Client initialization fails.
+    pub fn client_with_timeout(timeout: std::time::Duration) -> reqwest::Client {
+        reqwest::Client::builder()
+            .user_agent(user_agent())
+            .connect_timeout(timeout)
+            .read_timeout(timeout)
+            .build()
+            .expect("Failed to build reqwest client")
+    }
+
     /// Creates and returns a configured `reqwest::blocking::Client`.
     ///
     /// # Panics
@@ -277,4 +325,56 @@ mod tests {
             Err(err) => panic!("Failed to parse contract id: {err}"),
         }
     }
+
+    #[test]
+    fn test_explorer_url_for_transaction() {
+        let network = |network_passphrase: &str| Network {
+            rpc_url: "http://localhost:8000/rpc".to_string(),
+            rpc_headers: Vec::new(),
+            default_identity: None,
+            network_passphrase: network_passphrase.to_string(),
+        };
+        let testnet = network("Test SDF Network ; September 2015");
+        let local = network("Standalone Network ; February 2017");
+        let custom: Url = "https://explorer.example.com/private/".parse().unwrap();
+
+        assert_eq!(
+            explorer_url_for_transaction(&testnet, None, "abc").as_deref(),
+            Some("https://stellar.expert/explorer/testnet/tx/abc")
+        );
+        assert_eq!(explorer_url_for_transaction(&local, None, "abc"), None);
+        assert_eq!(
+            explorer_url_for_transaction(&local, Some(&custom), "abc").as_deref(),
+            Some("https://explorer.example.com/private/tx/abc")
+        );
+        assert_eq!(
+            explorer_url_for_transaction(&testnet, Some(&custom), "abc").as_deref(),
+            Some("https://explorer.example.com/private/tx/abc")
+        );
+    }
+
+    #[test]
+    fn test_fee_bump_transaction_hash() {
+        use crate::xdr::{FeeBumpTransactionEnvelope, FeeBumpTransactionInnerTx, ReadXdr};
+
+        let tx_env = xdr::TransactionEnvelope::from_xdr_base64(
+            "AAAABQAAAAACAgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgAAAAAAAAGQAAAAAgAAAAABAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQAAAGQAAAAAAAAAAQAAAAAAAAAAAAAAAQAAAAAAAAALAAAAAAAAAAUAAAAAAAAAAAAAAAAAAAAA",
+            Limits::none(),
+        )
+        .unwrap();
+        let xdr::TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope { tx, .. }) = tx_env
+        else {
+            panic!("expected a fee bump envelope");
+        };
+        let passphrase = "Test SDF Network ; September 2015";
+        assert_eq!(
+            hex::encode(fee_bump_transaction_hash(&tx, passphrase).unwrap()),
+            "9999ca7432adb288d021107582ebf35a75d85a53e07d8ab2426fbdef9f072803"
+        );
+        let FeeBumpTransactionInnerTx::Tx(inner) = tx.inner_tx;
+        assert_eq!(
+            hex::encode(transaction_hash(&inner.tx, passphrase).unwrap()),
+            "c8be14edc28c5f0af4c73c1cb51f141e975b7e861d2de4a4dc9632c99198437c"
+        );
+    }
 }
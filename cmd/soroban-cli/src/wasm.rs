@@ -11,7 +11,7 @@ use stellar_xdr::curr::{ContractDataEntry, ContractExecutable, ScVal};
 use crate::{
     config::{
         locator,
-        network::{Error as NetworkError, Network},
+        network::{Error as NetworkError, Network, RpcOptions},
     },
     utils::{self, rpc::get_remote_wasm_from_hash},
     wasm::Error::{ContractIsStellarAsset, UnexpectedContractToken},
@@ -89,6 +89,13 @@ impl Args {
         Ok(Spec::new(&contents)?)
     }
 
+    /// # Errors
+    /// May fail to read wasm file, or if it isn't a valid contract, see [`Spec::validate`]
+    pub fn validate(&self) -> Result<Spec, Error> {
+        let contents = self.read()?;
+        Ok(Spec::validate(&contents)?)
+    }
+
     pub fn hash(&self) -> Result<Hash, Error> {
         Ok(Hash(Sha256::digest(self.read()?).into()))
     }
@@ -123,16 +130,21 @@ pub fn len(p: &Path) -> Result<u64, Error> {
 pub async fn fetch_from_contract(
     stellar_strkey::Contract(contract_id): &stellar_strkey::Contract,
     network: &Network,
+    options: RpcOptions,
 ) -> Result<Vec<u8>, Error> {
     tracing::trace!(?network);
-    let client = network.rpc_client()?;
-    client
-        .verify_network_passphrase(Some(&network.network_passphrase))
-        .await?;
-    let data_entry = client.get_contract_data(contract_id).await?;
+    let client = network.checked_rpc_client(options).await?;
+    network
+        .with_rpc_timeout(client.verify_network_passphrase(Some(&network.network_passphrase)))
+        .await??;
+    let data_entry = network
+        .with_rpc_timeout(client.get_contract_data(contract_id))
+        .await??;
     if let ScVal::ContractInstance(contract) = &data_entry.val {
         return match &contract.executable {
-            ContractExecutable::Wasm(hash) => Ok(get_remote_wasm_from_hash(&client, hash).await?),
+            ContractExecutable::Wasm(hash) => Ok(network
+                .with_rpc_timeout(get_remote_wasm_from_hash(&client, hash))
+                .await??),
             ContractExecutable::StellarAsset => Err(ContractIsStellarAsset),
         };
     }